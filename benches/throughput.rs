@@ -0,0 +1,73 @@
+use std::time::Duration;
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use mmss::core::semantic_task_processor::{ProcessorConfig, SemanticTaskProcessor};
+use mmss::core::types::{GeometricOperator, GeometricTaskCommand, Quaternion};
+
+fn task(operator: GeometricOperator) -> GeometricTaskCommand {
+    GeometricTaskCommand {
+        task_id: None,
+        task_name: "Benchmark task".to_string(),
+        geometric_operator: operator,
+        target_module: "sys7_core".to_string(),
+        parameters: serde_json::json!({ "theta": 0.8, "frequency_scale": 2.0 }),
+        expected_output_metric: "v_geometric".to_string(),
+        execute_at: None,
+        tags: Vec::new(),
+    }
+}
+
+/// Submit and execute batches of tasks with the simulated delay disabled.
+fn bench_task_throughput(c: &mut Criterion) {
+    let mut group = c.benchmark_group("task_throughput");
+
+    for batch in [1u64, 10, 100] {
+        group.throughput(Throughput::Elements(batch));
+        group.bench_with_input(
+            BenchmarkId::new("submit_execute", batch),
+            &batch,
+            |b, &batch| {
+                let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+                    exec_delay: Duration::ZERO,
+                    ..Default::default()
+                });
+                b.iter(|| {
+                    for i in 0..batch {
+                        let operator =
+                            GeometricOperator::ALL[i as usize % GeometricOperator::ALL.len()];
+                        let task_id = processor.submit_task(task(operator)).unwrap();
+                        black_box(processor.execute_task(task_id).unwrap());
+                    }
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+/// Compare slerp against nlerp for nearby, quarter-turn and far rotations.
+fn bench_interpolation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("quaternion_interpolation");
+    let from = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.3);
+
+    for (label, angle) in [
+        ("near", 0.31),
+        ("quarter", 0.3 + std::f64::consts::FRAC_PI_2),
+        ("far", 0.3 + 3.1),
+    ] {
+        let to = Quaternion::from_axis_angle([0.0, 1.0, 0.0], angle);
+
+        group.bench_with_input(BenchmarkId::new("slerp", label), &to, |b, to| {
+            b.iter(|| black_box(&from).slerp(black_box(to), black_box(0.37)));
+        });
+        group.bench_with_input(BenchmarkId::new("nlerp", label), &to, |b, to| {
+            b.iter(|| black_box(&from).nlerp(black_box(to), black_box(0.37)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_task_throughput, bench_interpolation);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+use std::path::Path;
+use std::process::Command;
+
+/// Bakes the current git commit into `MMSS_GIT_COMMIT` for the health
+/// endpoint. A value already set in the environment wins, for builds outside
+/// a checkout; otherwise it falls back to "unknown".
+fn main() {
+    println!("cargo:rerun-if-env-changed=MMSS_GIT_COMMIT");
+
+    let git_dir = Path::new(".git");
+    if git_dir.exists() {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        println!("cargo:rerun-if-changed=.git/packed-refs");
+        // HEAD only changes on checkout; the branch ref changes on commit
+        if let Ok(head) = std::fs::read_to_string(git_dir.join("HEAD")) {
+            if let Some(reference) = head.strip_prefix("ref: ") {
+                println!("cargo:rerun-if-changed=.git/{}", reference.trim());
+            }
+        }
+    }
+
+    let commit = std::env::var("MMSS_GIT_COMMIT")
+        .ok()
+        .filter(|commit| !commit.is_empty())
+        .or_else(|| {
+            let output = Command::new("git")
+                .args(["rev-parse", "--short=12", "HEAD"])
+                .output()
+                .ok()?;
+            let commit = String::from_utf8(output.stdout).ok()?;
+            (output.status.success() && !commit.trim().is_empty())
+                .then(|| commit.trim().to_string())
+        })
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=MMSS_GIT_COMMIT={}", commit);
+}
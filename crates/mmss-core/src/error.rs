@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+use crate::structex_bridge::PatternError;
+
+/// Errors from the export, import and pattern functions of this crate.
+#[derive(Debug, Error)]
+pub enum CoreError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Arrow error: {0}")]
+    Arrow(#[from] arrow2::error::Error),
+    #[error("Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    Pattern(#[from] PatternError),
+    /// A CSV file that was not written by `export_csv`
+    #[error("Malformed CSV: {0}")]
+    Csv(String),
+    /// Time bucketing whose buckets cannot be represented or would exceed
+    /// `MAX_TIME_BUCKETS`
+    #[error("Invalid time buckets: {0}")]
+    TimeBuckets(String),
+}
+
+pub type Result<T> = std::result::Result<T, CoreError>;
@@ -1,13 +1,45 @@
-﻿use arrow2::{
-    array::{Array, Int64Array, StringArray, UInt64Array},
+﻿use crate::error::{CoreError, Result};
+use crate::structex_bridge::MmssRecord;
+use arrow2::{
+    array::{Int64Array, UInt64Array, Utf8Array},
     chunk::Chunk,
     datatypes::{DataType, Field, Schema},
-    io::ipc::write::{FileWriter, WriteOptions},
+    io::ipc::{
+        read::{read_file_metadata, FileReader},
+        write::{self, FileWriter, WriteOptions},
+    },
 };
+use serde::{de::DeserializeOwned, Serialize};
 use std::{fs::File, path::Path};
-use crate::structex_bridge::MmssRecord;
 
-pub fn write_records_to_file(path: &Path, records: &[MmssRecord]) -> Result<(), Box<dyn std::error::Error>> {
+/// IPC buffer compression applied when writing Arrow files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Compression {
+    #[default]
+    None,
+    Lz4,
+    Zstd,
+}
+
+impl Compression {
+    fn to_ipc(self) -> Option<write::Compression> {
+        match self {
+            Compression::None => None,
+            Compression::Lz4 => Some(write::Compression::LZ4),
+            Compression::Zstd => Some(write::Compression::ZSTD),
+        }
+    }
+}
+
+pub fn write_records_to_file<P: Serialize>(path: &Path, records: &[MmssRecord<P>]) -> Result<()> {
+    write_records_to_file_compressed(path, records, Compression::None)
+}
+
+pub fn write_records_to_file_compressed<P: Serialize>(
+    path: &Path,
+    records: &[MmssRecord<P>],
+    compression: Compression,
+) -> Result<()> {
     let file = File::create(path)?;
     let schema = Schema::from(vec![
         Field::new("id", DataType::UInt64, false),
@@ -16,22 +48,155 @@ pub fn write_records_to_file(path: &Path, records: &[MmssRecord]) -> Result<(),
         Field::new("payload", DataType::Utf8, false),
     ]);
 
-    let mut writer = FileWriter::try_new(file, &schema, None, Default::default())?;
+    let options = WriteOptions {
+        compression: compression.to_ipc(),
+    };
+    let mut writer = FileWriter::try_new(file, schema, None, options)?;
     let ids: Vec<_> = records.iter().map(|r| r.id).collect();
     let kinds: Vec<_> = records.iter().map(|r| r.kind.as_str()).collect();
     let timestamps: Vec<_> = records.iter().map(|r| r.timestamp).collect();
-    let payloads: Vec<_> = records.iter().map(|r| serde_json::to_string(&r.payload).unwrap()).collect();
+    let payloads = records
+        .iter()
+        .map(|r| serde_json::to_string(&r.payload))
+        .collect::<serde_json::Result<Vec<_>>>()?;
     let id_array = UInt64Array::from_slice(&ids);
-    let kind_array = StringArray::from_slice(kinds);
+    let kind_array = Utf8Array::<i32>::from_slice(kinds);
     let timestamp_array = Int64Array::from_slice(&timestamps);
-    let payload_array = StringArray::from_slice(payloads);
+    let payload_array = Utf8Array::<i32>::from_slice(payloads);
     let chunk = Chunk::try_new(vec![
-        &id_array as &dyn Array,
-        &kind_array,
-        &timestamp_array,
-        &payload_array,
+        id_array.boxed(),
+        kind_array.boxed(),
+        timestamp_array.boxed(),
+        payload_array.boxed(),
     ])?;
     writer.write(&chunk, None)?;
     writer.finish()?;
     Ok(())
 }
+
+/// Read every record from an Arrow IPC file written by `write_records_to_file`.
+///
+/// Compressed files are decompressed transparently.
+pub fn read_records_from_file<P: DeserializeOwned>(path: &Path) -> Result<Vec<MmssRecord<P>>> {
+    let mut file = File::open(path)?;
+    let metadata = read_file_metadata(&mut file)?;
+    let reader = FileReader::new(file, metadata, None, None);
+
+    let mut records = Vec::new();
+    for chunk in reader {
+        let chunk = chunk?;
+        let columns = chunk.arrays();
+        let ids = downcast::<UInt64Array>(columns, 0)?;
+        let kinds = downcast::<Utf8Array<i32>>(columns, 1)?;
+        let timestamps = downcast::<Int64Array>(columns, 2)?;
+        let payloads = downcast::<Utf8Array<i32>>(columns, 3)?;
+
+        for row in 0..chunk.len() {
+            records.push(MmssRecord {
+                id: ids.value(row),
+                kind: kinds.value(row).to_string(),
+                timestamp: timestamps.value(row),
+                payload: serde_json::from_str(payloads.value(row))?,
+            });
+        }
+    }
+    Ok(records)
+}
+
+pub(crate) fn downcast<T: 'static>(
+    columns: &[Box<dyn arrow2::array::Array>],
+    index: usize,
+) -> Result<&T> {
+    columns
+        .get(index)
+        .and_then(|column| column.as_any().downcast_ref::<T>())
+        .ok_or_else(|| {
+            CoreError::Arrow(arrow2::error::Error::OutOfSpec(format!(
+                "unexpected type for column {index}"
+            )))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[derive(Serialize)]
+    struct MetricPayload {
+        value: f64,
+        unit: String,
+    }
+
+    #[test]
+    fn test_write_typed_and_untyped_records() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let typed = vec![MmssRecord {
+            id: 1,
+            kind: "cpu".to_string(),
+            timestamp: 1732400000,
+            payload: MetricPayload {
+                value: 12.0,
+                unit: "%".to_string(),
+            },
+        }];
+        let untyped = vec![MmssRecord {
+            id: 2,
+            kind: "disk".to_string(),
+            timestamp: 1732400060,
+            payload: json!({ "value": 3.5 }),
+        }];
+
+        let typed_path = dir.path().join("typed.arrow");
+        let untyped_path = dir.path().join("untyped.arrow");
+        write_records_to_file(&typed_path, &typed).unwrap();
+        write_records_to_file(&untyped_path, &untyped).unwrap();
+
+        assert!(std::fs::metadata(&typed_path).unwrap().len() > 0);
+        assert!(std::fs::metadata(&untyped_path).unwrap().len() > 0);
+    }
+
+    #[test]
+    fn test_unwritable_path_is_an_io_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("records.arrow");
+        let records: Vec<MmssRecord> = Vec::new();
+
+        let err = write_records_to_file(&path, &records).unwrap_err();
+        assert!(matches!(err, CoreError::Io(_)), "{err:?}");
+        let err = read_records_from_file::<serde_json::Value>(&path).unwrap_err();
+        assert!(matches!(err, CoreError::Io(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_zstd_output_is_smaller_and_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let records: Vec<MmssRecord> = (0..2000)
+            .map(|i| MmssRecord {
+                id: i,
+                kind: "cpu".to_string(),
+                timestamp: 1732400000 + i as i64 * 60,
+                payload: json!({ "value": 50.0, "unit": "%", "host": "host-1" }),
+            })
+            .collect();
+
+        let plain_path = dir.path().join("plain.arrow");
+        let zstd_path = dir.path().join("zstd.arrow");
+        write_records_to_file(&plain_path, &records).unwrap();
+        write_records_to_file_compressed(&zstd_path, &records, Compression::Zstd).unwrap();
+
+        let plain_len = std::fs::metadata(&plain_path).unwrap().len();
+        let zstd_len = std::fs::metadata(&zstd_path).unwrap().len();
+        assert!(zstd_len < plain_len);
+
+        let restored: Vec<MmssRecord> = read_records_from_file(&zstd_path).unwrap();
+        assert_eq!(restored.len(), records.len());
+        for (a, b) in restored.iter().zip(&records) {
+            assert_eq!(
+                (a.id, &a.kind, a.timestamp, &a.payload),
+                (b.id, &b.kind, b.timestamp, &b.payload)
+            );
+        }
+    }
+}
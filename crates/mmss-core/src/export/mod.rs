@@ -1 +1,2 @@
 ﻿pub mod arrow;
+pub mod query;
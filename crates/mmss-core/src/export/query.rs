@@ -0,0 +1,119 @@
+use crate::error::Result;
+use crate::export::arrow::downcast;
+use crate::structex_bridge::MmssRecord;
+use arrow2::{
+    array::{Int64Array, UInt64Array, Utf8Array},
+    io::ipc::read::{read_file_metadata, FileReader},
+};
+use std::{collections::HashMap, fs::File, path::Path};
+
+const ID_COLUMN: usize = 0;
+const KIND_COLUMN: usize = 1;
+const TIMESTAMP_COLUMN: usize = 2;
+const PAYLOAD_COLUMN: usize = 3;
+
+fn open_reader(path: &Path, projection: Option<Vec<usize>>) -> Result<FileReader<File>> {
+    let mut file = File::open(path)?;
+    let metadata = read_file_metadata(&mut file)?;
+    Ok(FileReader::new(file, metadata, projection, None))
+}
+
+/// Count records per `kind`, reading only the kind column.
+pub fn count_by_kind(path: &Path) -> Result<HashMap<String, usize>> {
+    let mut counts = HashMap::new();
+
+    for chunk in open_reader(path, Some(vec![KIND_COLUMN]))? {
+        let chunk = chunk?;
+        let kinds = downcast::<Utf8Array<i32>>(chunk.arrays(), 0)?;
+        for kind in kinds.values_iter() {
+            *counts.entry(kind.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    Ok(counts)
+}
+
+/// Return records with `start <= timestamp < end`.
+///
+/// The timestamp column is scanned first; payloads are only parsed for rows
+/// inside the range.
+pub fn filter_by_timestamp(path: &Path, start: i64, end: i64) -> Result<Vec<MmssRecord>> {
+    let mut records = Vec::new();
+
+    for chunk in open_reader(path, None)? {
+        let chunk = chunk?;
+        let columns = chunk.arrays();
+        let timestamps = downcast::<Int64Array>(columns, TIMESTAMP_COLUMN)?;
+
+        let mut rows = timestamps
+            .values()
+            .iter()
+            .enumerate()
+            .filter(|(_, ts)| (start..end).contains(*ts))
+            .map(|(row, _)| row)
+            .peekable();
+        if rows.peek().is_none() {
+            continue;
+        }
+
+        let ids = downcast::<UInt64Array>(columns, ID_COLUMN)?;
+        let kinds = downcast::<Utf8Array<i32>>(columns, KIND_COLUMN)?;
+        let payloads = downcast::<Utf8Array<i32>>(columns, PAYLOAD_COLUMN)?;
+        for row in rows {
+            records.push(MmssRecord {
+                id: ids.value(row),
+                kind: kinds.value(row).to_string(),
+                timestamp: timestamps.value(row),
+                payload: serde_json::from_str(payloads.value(row))?,
+            });
+        }
+    }
+
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::arrow::write_records_to_file;
+    use serde_json::json;
+
+    fn write_dataset(path: &Path) {
+        let records: Vec<MmssRecord> = (0..20)
+            .map(|i| MmssRecord {
+                id: i,
+                kind: ["cpu", "memory", "disk"][i as usize % 3].to_string(),
+                timestamp: 1000 + i as i64 * 10,
+                payload: json!({ "value": i }),
+            })
+            .collect();
+        write_records_to_file(path, &records).unwrap();
+    }
+
+    #[test]
+    fn test_count_by_kind() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.arrow");
+        write_dataset(&path);
+
+        let counts = count_by_kind(&path).unwrap();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts["cpu"], 7);
+        assert_eq!(counts["memory"], 7);
+        assert_eq!(counts["disk"], 6);
+    }
+
+    #[test]
+    fn test_filter_by_timestamp() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.arrow");
+        write_dataset(&path);
+
+        let records = filter_by_timestamp(&path, 1050, 1100).unwrap();
+        let ids: Vec<u64> = records.iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec![5, 6, 7, 8, 9]);
+        assert_eq!(records[0].payload["value"], 5);
+
+        assert!(filter_by_timestamp(&path, 5000, 6000).unwrap().is_empty());
+    }
+}
@@ -1,2 +1,5 @@
-﻿pub mod structex_bridge;
+﻿pub mod error;
 pub mod export;
+pub mod structex_bridge;
+
+pub use error::CoreError;
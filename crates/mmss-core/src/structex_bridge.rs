@@ -1,34 +1,881 @@
-﻿use serde_json::Value as JsonValue;
+﻿use jsonschema::JSONSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
 use thiserror::Error;
 
+use crate::error::CoreError;
+
 #[derive(Debug, Error)]
 pub enum PatternError {
     #[error("Pattern compilation failed: {0}")]
     CompileError(String),
     #[error("Matching error: {0}")]
     MatchError(String),
+    #[error("Record {id} does not match the payload schema: {message}")]
+    SchemaViolation { id: u64, message: String },
 }
 
+/// Filter over `MmssRecord`s.
+///
+/// A pattern is one or more comparisons joined by `&&`, each of the form
+/// `<field> <op> <json literal>`, e.g. `kind == "cpu" && payload.value > 50`.
+/// Fields are `id`, `kind`, `timestamp` or a dotted path into `payload`;
+/// operators are `==`, `!=`, `<`, `<=`, `>` and `>=`. An empty pattern
+/// matches every record.
 pub struct PatternMatcher {
     pattern: String,
+    clauses: Vec<Clause>,
+}
+
+struct Clause {
+    path: Vec<String>,
+    op: CompareOp,
+    expected: JsonValue,
+}
+
+#[derive(Clone, Copy)]
+enum CompareOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
 }
 
 impl PatternMatcher {
-    pub fn new(pattern: &str) -> Result<Self, PatternError> {
+    pub fn new(pattern: &str) -> Result<Self, CoreError> {
+        let clauses = split_conjunction(pattern)?
+            .into_iter()
+            .map(parse_clause)
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(Self {
             pattern: pattern.to_string(),
+            clauses,
         })
     }
 
-    pub fn matches(&self, _record: &MmssRecord) -> Result<bool, PatternError> {
+    pub fn pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn matches(&self, record: &MmssRecord) -> Result<bool, CoreError> {
+        for clause in &self.clauses {
+            if !clause.evaluate(record)? {
+                return Ok(false);
+            }
+        }
         Ok(true)
     }
 }
 
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct MmssRecord {
+impl Clause {
+    fn evaluate(&self, record: &MmssRecord) -> Result<bool, PatternError> {
+        let actual = match resolve_field(record, &self.path) {
+            Some(value) => value,
+            None => return Ok(matches!(self.op, CompareOp::Ne)),
+        };
+
+        match self.op {
+            CompareOp::Eq => Ok(json_eq(&actual, &self.expected)),
+            CompareOp::Ne => Ok(!json_eq(&actual, &self.expected)),
+            op => {
+                let ordering = json_cmp(&actual, &self.expected).ok_or_else(|| {
+                    PatternError::MatchError(format!(
+                        "cannot order {} against {}",
+                        actual, self.expected
+                    ))
+                })?;
+                Ok(match op {
+                    CompareOp::Lt => ordering.is_lt(),
+                    CompareOp::Le => ordering.is_le(),
+                    CompareOp::Gt => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                })
+            }
+        }
+    }
+}
+
+fn split_conjunction(pattern: &str) -> Result<Vec<&str>, PatternError> {
+    let mut parts = Vec::new();
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+    let bytes = pattern.as_bytes();
+
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+        } else if b == b'"' {
+            in_string = true;
+        } else if b == b'&' && bytes.get(i + 1) == Some(&b'&') {
+            parts.push(&pattern[start..i]);
+            start = i + 2;
+            i += 1;
+        }
+        i += 1;
+    }
+
+    if in_string {
+        return Err(PatternError::CompileError(
+            "unterminated string literal".into(),
+        ));
+    }
+    parts.push(&pattern[start..]);
+
+    if parts.len() == 1 && parts[0].trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(parts)
+}
+
+fn parse_clause(clause: &str) -> Result<Clause, PatternError> {
+    const OPERATORS: [(&str, CompareOp); 6] = [
+        ("==", CompareOp::Eq),
+        ("!=", CompareOp::Ne),
+        ("<=", CompareOp::Le),
+        (">=", CompareOp::Ge),
+        ("<", CompareOp::Lt),
+        (">", CompareOp::Gt),
+    ];
+
+    let (index, token, op) = OPERATORS
+        .iter()
+        .filter_map(|(token, op)| clause.find(token).map(|index| (index, *token, *op)))
+        .min_by_key(|(index, token, _)| (*index, usize::MAX - token.len()))
+        .ok_or_else(|| {
+            PatternError::CompileError(format!("missing operator in `{}`", clause.trim()))
+        })?;
+
+    let field = clause[..index].trim();
+    let literal = clause[index + token.len()..].trim();
+
+    let path: Vec<String> = field.split('.').map(str::to_string).collect();
+    if path.iter().any(String::is_empty) {
+        return Err(PatternError::CompileError(format!(
+            "invalid field `{}`",
+            field
+        )));
+    }
+    match path[0].as_str() {
+        "id" | "kind" | "timestamp" if path.len() == 1 => {}
+        "payload" => {}
+        _ => {
+            return Err(PatternError::CompileError(format!(
+                "unknown field `{}`",
+                field
+            )))
+        }
+    }
+
+    let expected = serde_json::from_str(literal).map_err(|err| {
+        PatternError::CompileError(format!("invalid literal `{}`: {}", literal, err))
+    })?;
+
+    Ok(Clause { path, op, expected })
+}
+
+fn resolve_field<S: AsRef<str>>(record: &MmssRecord, path: &[S]) -> Option<JsonValue> {
+    match path.first()?.as_ref() {
+        "id" if path.len() == 1 => Some(JsonValue::from(record.id)),
+        "kind" if path.len() == 1 => Some(JsonValue::from(record.kind.as_str())),
+        "timestamp" if path.len() == 1 => Some(JsonValue::from(record.timestamp)),
+        "payload" => path[1..]
+            .iter()
+            .try_fold(&record.payload, |value, key| value.get(key.as_ref()))
+            .cloned(),
+        _ => None,
+    }
+}
+
+fn resolve_path(record: &MmssRecord, path: &str) -> Option<JsonValue> {
+    let segments: Vec<&str> = path.split('.').collect();
+    resolve_field(record, &segments)
+}
+
+fn json_eq(a: &JsonValue, b: &JsonValue) -> bool {
+    match (a.as_f64(), b.as_f64()) {
+        (Some(x), Some(y)) => x == y,
+        _ => a == b,
+    }
+}
+
+fn json_cmp(a: &JsonValue, b: &JsonValue) -> Option<std::cmp::Ordering> {
+    match (a, b) {
+        (JsonValue::Number(_), JsonValue::Number(_)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (JsonValue::String(x), JsonValue::String(y)) => Some(x.cmp(y)),
+        _ => None,
+    }
+}
+
+/// A single ingested record.
+///
+/// The payload defaults to an untyped `JsonValue`; callers with a known
+/// schema can use `MmssRecord<MyPayload>` for typed access.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MmssRecord<P = JsonValue> {
     pub id: u64,
     pub kind: String,
     pub timestamp: i64,
-    pub payload: JsonValue,
+    pub payload: P,
+}
+
+impl MmssRecord {
+    /// Check the payload against a JSON Schema.
+    ///
+    /// Compiles `schema` on every call; use [`PayloadSchema`] to check many
+    /// records.
+    pub fn validate(&self, schema: &JsonValue) -> Result<(), CoreError> {
+        PayloadSchema::new(schema)?.check(self)
+    }
+}
+
+/// A compiled JSON Schema that record payloads must satisfy.
+pub struct PayloadSchema {
+    compiled: JSONSchema,
+}
+
+impl PayloadSchema {
+    pub fn new(schema: &JsonValue) -> Result<Self, CoreError> {
+        let compiled = JSONSchema::compile(schema).map_err(|err| {
+            PatternError::CompileError(format!("invalid payload schema: {}", err))
+        })?;
+        Ok(Self { compiled })
+    }
+
+    /// Fails with [`PatternError::SchemaViolation`] naming the record's `id`
+    /// and every violation found.
+    pub fn check(&self, record: &MmssRecord) -> Result<(), CoreError> {
+        self.compiled.validate(&record.payload).map_err(|errors| {
+            let message = errors
+                .map(|err| {
+                    let path = err.instance_path.to_string();
+                    if path.is_empty() {
+                        err.to_string()
+                    } else {
+                        format!("{} at {}", err, path)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("; ");
+            PatternError::SchemaViolation {
+                id: record.id,
+                message,
+            }
+            .into()
+        })
+    }
+
+    /// Pass through conforming records, turning non-conforming ones into
+    /// errors. Useful after [`read_ndjson`] or an Arrow read.
+    pub fn filter<'a, I>(
+        &'a self,
+        records: I,
+    ) -> impl Iterator<Item = Result<MmssRecord, CoreError>> + 'a
+    where
+        I: IntoIterator<Item = MmssRecord>,
+        I::IntoIter: 'a,
+    {
+        records
+            .into_iter()
+            .map(move |record| self.check(&record).map(|()| record))
+    }
+}
+
+/// Lazily parse one `MmssRecord` per line from an NDJSON source.
+///
+/// Blank lines are skipped. A malformed line yields an `Err` for that line
+/// only; iteration continues with the next line.
+pub fn read_ndjson(
+    reader: impl BufRead,
+) -> impl Iterator<Item = Result<MmssRecord, serde_json::Error>> {
+    reader.lines().filter_map(|line| match line {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => Some(serde_json::from_str(&line)),
+        Err(err) => Some(Err(serde_json::Error::io(err))),
+    })
+}
+
+const CSV_HEADER: [&str; 4] = ["id", "kind", "timestamp", "payload"];
+
+/// Write records as CSV with an `id,kind,timestamp,payload` header; the
+/// payload column holds each payload as a JSON string.
+pub fn export_csv<P: Serialize>(path: &Path, records: &[MmssRecord<P>]) -> Result<(), CoreError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    writeln!(writer, "{}", CSV_HEADER.join(","))?;
+    for record in records {
+        let payload = serde_json::to_string(&record.payload)?;
+        writeln!(
+            writer,
+            "{},{},{},{}",
+            record.id,
+            csv_field(&record.kind),
+            record.timestamp,
+            csv_field(&payload)
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Write records as a single JSON array.
+pub fn export_json<P: Serialize>(path: &Path, records: &[MmssRecord<P>]) -> Result<(), CoreError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(&mut writer, records)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Read records back from a file written by [`export_csv`].
+///
+/// Fails on the first malformed row, naming its line.
+pub fn import_csv(path: &Path) -> Result<Vec<MmssRecord>, CoreError> {
+    let mut text = String::new();
+    File::open(path)?.read_to_string(&mut text)?;
+
+    let mut rows = parse_csv(&text).map_err(CoreError::Csv)?.into_iter();
+    match rows.next() {
+        Some((_, header)) if header == CSV_HEADER => {}
+        Some((line, header)) => {
+            return Err(CoreError::Csv(format!(
+                "line {line}: expected header {}, got {}",
+                CSV_HEADER.join(","),
+                header.join(",")
+            )))
+        }
+        None => return Err(CoreError::Csv("missing CSV header".into())),
+    }
+
+    rows.map(|(line, fields)| parse_csv_record(line, fields).map_err(CoreError::Csv))
+        .collect()
+}
+
+fn parse_csv_record(line: usize, fields: Vec<String>) -> Result<MmssRecord, String> {
+    let [id, kind, timestamp, payload]: [String; 4] =
+        fields.try_into().map_err(|fields: Vec<String>| {
+            format!("line {line}: expected 4 fields, got {}", fields.len())
+        })?;
+    Ok(MmssRecord {
+        id: id
+            .parse()
+            .map_err(|err| format!("line {line}: invalid id: {err}"))?,
+        kind,
+        timestamp: timestamp
+            .parse()
+            .map_err(|err| format!("line {line}: invalid timestamp: {err}"))?,
+        payload: serde_json::from_str(&payload)
+            .map_err(|err| format!("line {line}: invalid payload: {err}"))?,
+    })
+}
+
+/// Read records back from a file written by [`export_json`].
+pub fn import_json(path: &Path) -> Result<Vec<MmssRecord>, CoreError> {
+    let reader = BufReader::new(File::open(path)?);
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Split CSV text into rows of fields, each tagged with the line it starts on.
+/// Quoted fields may contain separators, doubled quotes and line breaks.
+fn parse_csv(text: &str) -> Result<Vec<(usize, Vec<String>)>, String> {
+    let mut rows = Vec::new();
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_start = 1;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if field.is_empty() => in_quotes = true,
+            '\n' if in_quotes => {
+                line += 1;
+                field.push(c);
+            }
+            _ if in_quotes => field.push(c),
+            ',' => fields.push(std::mem::take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                rows.push((row_start, std::mem::take(&mut fields)));
+                line += 1;
+                row_start = line;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(format!("line {row_start}: unterminated quoted field"));
+    }
+    if !field.is_empty() || !fields.is_empty() {
+        fields.push(field);
+        rows.push((row_start, fields));
+    }
+    Ok(rows)
+}
+
+/// Summary statistics for a numeric field within one group.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Aggregate {
+    pub count: usize,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+}
+
+impl Aggregate {
+    fn new(value: f64) -> Self {
+        Self {
+            count: 1,
+            sum: value,
+            min: value,
+            max: value,
+            mean: value,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        self.sum += value;
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.mean = self.sum / self.count as f64;
+    }
+}
+
+/// Group records by the field at `group_by` and summarise the numeric value
+/// at `metric_path` (e.g. `aggregate(&records, "kind", "payload.value")`).
+///
+/// Records lacking either field, or whose metric is not numeric, are skipped.
+pub fn aggregate(
+    records: &[MmssRecord],
+    group_by: &str,
+    metric_path: &str,
+) -> HashMap<String, Aggregate> {
+    let mut groups: HashMap<String, Aggregate> = HashMap::new();
+
+    for record in records {
+        let Some(value) = resolve_path(record, metric_path).and_then(|v| v.as_f64()) else {
+            continue;
+        };
+        let Some(key) = resolve_path(record, group_by) else {
+            continue;
+        };
+        let key = match key {
+            JsonValue::String(text) => text,
+            other => other.to_string(),
+        };
+
+        groups
+            .entry(key)
+            .and_modify(|agg| agg.push(value))
+            .or_insert_with(|| Aggregate::new(value));
+    }
+
+    groups
+}
+
+/// Upper bound on the number of buckets [`bucket_by_time`] will produce,
+/// including the zero-filled gaps.
+pub const MAX_TIME_BUCKETS: u64 = 100_000;
+
+/// Sum the numeric value at `metric_path` into fixed-width, epoch-aligned
+/// time buckets and return `(bucket_start, sum)` pairs sorted by time.
+///
+/// Buckets between the first and last populated bucket are filled with zero,
+/// so records spanning more than [`MAX_TIME_BUCKETS`] buckets are rejected
+/// with [`CoreError::TimeBuckets`], as are timestamps whose bucket would
+/// start before `i64::MIN`. Records lacking the metric are skipped; a
+/// non-positive `bucket_secs` yields no buckets.
+pub fn bucket_by_time(
+    records: &[MmssRecord],
+    bucket_secs: i64,
+    metric_path: &str,
+) -> Result<Vec<(i64, f64)>, CoreError> {
+    if bucket_secs <= 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut buckets: BTreeMap<i64, f64> = BTreeMap::new();
+    for record in records {
+        if let Some(value) = resolve_path(record, metric_path).and_then(|v| v.as_f64()) {
+            let start = record
+                .timestamp
+                .checked_sub(record.timestamp.rem_euclid(bucket_secs))
+                .ok_or_else(|| {
+                    CoreError::TimeBuckets(format!(
+                        "timestamp {} has no representable bucket",
+                        record.timestamp
+                    ))
+                })?;
+            *buckets.entry(start).or_insert(0.0) += value;
+        }
+    }
+
+    let (first, last) = match (buckets.keys().next(), buckets.keys().next_back()) {
+        (Some(&first), Some(&last)) => (first, last),
+        _ => return Ok(Vec::new()),
+    };
+
+    let count = last.abs_diff(first) / bucket_secs as u64 + 1;
+    if count > MAX_TIME_BUCKETS {
+        return Err(CoreError::TimeBuckets(format!(
+            "{} buckets of {}s exceed the limit of {}",
+            count, bucket_secs, MAX_TIME_BUCKETS
+        )));
+    }
+
+    // Every start lies in [first, last], so the arithmetic cannot overflow
+    Ok((0..count as i64)
+        .map(|i| first + i * bucket_secs)
+        .map(|start| (start, buckets.get(&start).copied().unwrap_or(0.0)))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_ndjson_skips_malformed_lines() {
+        let input = concat!(
+            r#"{"id":1,"kind":"cpu","timestamp":1732400000,"payload":{"value":12.5}}"#,
+            "\n",
+            r#"{"id":2,"kind":"memory","timestamp":"#,
+            "\n",
+            "\n",
+            r#"{"id":3,"kind":"disk","timestamp":1732400120,"payload":{"value":40.0}}"#,
+            "\n",
+        );
+
+        let results: Vec<_> = read_ndjson(Cursor::new(input)).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[1].is_err());
+
+        let good: Vec<_> = results.into_iter().filter_map(Result::ok).collect();
+        assert_eq!(good.iter().map(|r| r.id).collect::<Vec<_>>(), vec![1, 3]);
+        assert_eq!(good[0].kind, "cpu");
+        assert_eq!(good[1].payload["value"], 40.0);
+    }
+
+    fn nested_records() -> Vec<MmssRecord> {
+        vec![
+            MmssRecord {
+                id: 1,
+                kind: "cpu".to_string(),
+                timestamp: 1732400000,
+                payload: serde_json::json!({ "value": 12.5, "unit": "%" }),
+            },
+            MmssRecord {
+                id: 2,
+                kind: "net, \"eth0\"".to_string(),
+                timestamp: -60,
+                payload: serde_json::json!({
+                    "host": { "name": "host-1", "tags": ["a,b", "line\nbreak"] },
+                    "samples": [{ "rx": 1 }, { "tx": 2.5 }],
+                    "note": null
+                }),
+            },
+            MmssRecord {
+                id: 3,
+                kind: "empty".to_string(),
+                timestamp: 0,
+                payload: serde_json::json!({}),
+            },
+        ]
+    }
+
+    fn assert_same_records(restored: &[MmssRecord], original: &[MmssRecord]) {
+        assert_eq!(restored.len(), original.len());
+        for (a, b) in restored.iter().zip(original) {
+            assert_eq!(
+                (a.id, &a.kind, a.timestamp, &a.payload),
+                (b.id, &b.kind, b.timestamp, &b.payload)
+            );
+        }
+    }
+
+    #[test]
+    fn test_csv_export_import_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.csv");
+        let records = nested_records();
+
+        export_csv(&path, &records).unwrap();
+        assert_same_records(&import_csv(&path).unwrap(), &records);
+
+        std::fs::write(&path, "id,kind,timestamp,payload\n1,cpu,oops,{}\n").unwrap();
+        let err = import_csv(&path).unwrap_err().to_string();
+        assert!(err.contains("line 2"), "{}", err);
+    }
+
+    #[test]
+    fn test_json_export_import_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("records.json");
+        let records = nested_records();
+
+        export_json(&path, &records).unwrap();
+        assert_same_records(&import_json(&path).unwrap(), &records);
+    }
+
+    fn value_schema() -> JsonValue {
+        serde_json::json!({
+            "type": "object",
+            "properties": { "value": { "type": "number" } },
+            "required": ["value"]
+        })
+    }
+
+    #[test]
+    fn test_validate_payload_against_schema() {
+        let schema = value_schema();
+        let good = record(7, "cpu", 12.5);
+        assert!(good.validate(&schema).is_ok());
+
+        let bad = MmssRecord {
+            id: 42,
+            kind: "cpu".to_string(),
+            timestamp: 1732400000,
+            payload: serde_json::json!({ "value": "high" }),
+        };
+        let err = bad.validate(&schema).unwrap_err();
+        assert!(matches!(
+            err,
+            CoreError::Pattern(PatternError::SchemaViolation { id: 42, .. })
+        ));
+        let message = err.to_string();
+        assert!(message.contains("Record 42"), "{}", message);
+        assert!(message.contains("/value"), "{}", message);
+
+        assert!(matches!(
+            good.validate(&serde_json::json!({ "type": 12 })),
+            Err(CoreError::Pattern(PatternError::CompileError(_)))
+        ));
+    }
+
+    #[test]
+    fn test_payload_schema_filter() {
+        let schema = PayloadSchema::new(&value_schema()).unwrap();
+        let mut missing = record(2, "cpu", 0.0);
+        missing.payload = serde_json::json!({});
+        let records = vec![record(1, "cpu", 1.0), missing, record(3, "disk", 3.0)];
+
+        let results: Vec<_> = schema.filter(records).collect();
+        assert_eq!(results.len(), 3);
+        assert!(results[0].is_ok() && results[2].is_ok());
+        assert!(matches!(
+            results[1],
+            Err(CoreError::Pattern(PatternError::SchemaViolation {
+                id: 2,
+                ..
+            }))
+        ));
+    }
+
+    fn record(id: u64, kind: &str, value: f64) -> MmssRecord {
+        MmssRecord {
+            id,
+            kind: kind.to_string(),
+            timestamp: 1732400000 + id as i64 * 60,
+            payload: serde_json::json!({ "value": value, "unit": "%" }),
+        }
+    }
+
+    #[test]
+    fn test_pattern_matcher_filters_records() {
+        let matcher = PatternMatcher::new(r#"kind == "cpu" && payload.value >= 50"#).unwrap();
+
+        assert!(matcher.matches(&record(1, "cpu", 75.0)).unwrap());
+        assert!(!matcher.matches(&record(2, "cpu", 10.0)).unwrap());
+        assert!(!matcher.matches(&record(3, "disk", 75.0)).unwrap());
+        assert!(PatternMatcher::new("")
+            .unwrap()
+            .matches(&record(4, "disk", 0.0))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_pattern_matcher_errors() {
+        assert!(matches!(
+            PatternMatcher::new("kind"),
+            Err(CoreError::Pattern(PatternError::CompileError(_)))
+        ));
+        assert!(matches!(
+            PatternMatcher::new("host == \"a\""),
+            Err(CoreError::Pattern(PatternError::CompileError(_)))
+        ));
+        assert!(matches!(
+            PatternMatcher::new("kind == cpu"),
+            Err(CoreError::Pattern(PatternError::CompileError(_)))
+        ));
+
+        let matcher = PatternMatcher::new("kind > 3").unwrap();
+        assert!(matches!(
+            matcher.matches(&record(1, "cpu", 1.0)),
+            Err(CoreError::Pattern(PatternError::MatchError(_)))
+        ));
+    }
+
+    fn generated_records() -> Vec<MmssRecord> {
+        (0..100)
+            .map(|i| {
+                let kind = match i % 4 {
+                    0 => "cpu",
+                    1 => "memory",
+                    2 => "network",
+                    _ => "disk",
+                };
+                let mut record = record(i, kind, i as f64);
+                if i == 99 {
+                    record.payload = serde_json::json!({ "unit": "%" });
+                }
+                record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_aggregate_by_kind() {
+        let groups = aggregate(&generated_records(), "kind", "payload.value");
+
+        assert_eq!(groups.len(), 4);
+        assert_eq!(groups["cpu"].count, 25);
+        assert_eq!(groups["memory"].count, 25);
+        assert_eq!(groups["network"].count, 25);
+        assert_eq!(groups["disk"].count, 24);
+
+        let cpu = &groups["cpu"];
+        assert_eq!(cpu.min, 0.0);
+        assert_eq!(cpu.max, 96.0);
+        for agg in groups.values() {
+            assert_eq!(agg.mean, agg.sum / agg.count as f64);
+        }
+    }
+
+    #[test]
+    fn test_bucket_by_time() {
+        let mut records: Vec<MmssRecord> =
+            [(125, 1.0), (10, 2.0), (70, 3.0), (59, 4.0), (300, 5.0)]
+                .iter()
+                .enumerate()
+                .map(|(i, &(timestamp, value))| {
+                    let mut record = record(i as u64, "cpu", value);
+                    record.timestamp = timestamp;
+                    record
+                })
+                .collect();
+        records.push(MmssRecord {
+            id: 99,
+            kind: "cpu".into(),
+            timestamp: 130,
+            payload: serde_json::json!({}),
+        });
+
+        let buckets = bucket_by_time(&records, 60, "payload.value").unwrap();
+
+        assert_eq!(
+            buckets,
+            vec![
+                (0, 6.0),
+                (60, 3.0),
+                (120, 1.0),
+                (180, 0.0),
+                (240, 0.0),
+                (300, 5.0)
+            ]
+        );
+        assert!(bucket_by_time(&records, 0, "payload.value")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_bucket_by_time_rejects_unbounded_spans() {
+        let at = |timestamp: i64| MmssRecord {
+            timestamp,
+            ..record(0, "cpu", 1.0)
+        };
+
+        let sparse = [at(0), at(1_700_000_000)];
+        assert!(matches!(
+            bucket_by_time(&sparse, 1, "payload.value"),
+            Err(CoreError::TimeBuckets(_))
+        ));
+        assert_eq!(
+            bucket_by_time(&sparse, 1_700_000_000, "payload.value").unwrap(),
+            vec![(0, 1.0), (1_700_000_000, 1.0)]
+        );
+
+        assert!(matches!(
+            bucket_by_time(&[at(i64::MIN)], 7, "payload.value"),
+            Err(CoreError::TimeBuckets(_))
+        ));
+        assert_eq!(
+            bucket_by_time(&[at(i64::MAX)], 7, "payload.value").unwrap(),
+            vec![(i64::MAX - i64::MAX.rem_euclid(7), 1.0)]
+        );
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct MetricPayload {
+        value: f64,
+        unit: String,
+        host: String,
+    }
+
+    #[test]
+    fn test_typed_payload_round_trip() {
+        let typed = MmssRecord {
+            id: 7,
+            kind: "cpu".to_string(),
+            timestamp: 1732400000,
+            payload: MetricPayload {
+                value: 42.5,
+                unit: "%".to_string(),
+                host: "host-1".to_string(),
+            },
+        };
+
+        let json = serde_json::to_string(&typed).unwrap();
+        let parsed: MmssRecord<MetricPayload> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.payload, typed.payload);
+
+        let untyped: MmssRecord = serde_json::from_str(&json).unwrap();
+        assert_eq!(untyped.payload["host"], "host-1");
+    }
 }
@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tracing::warn;
+
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::error::{Error, Result};
+
+/// When a [`CircuitBreaker`] opens and how long it stays open
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// How long calls are rejected before a trial request is let through
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BreakerState {
+    Closed {
+        failures: u32,
+    },
+    /// Rejecting calls until `until`
+    Open {
+        until: DateTime<Utc>,
+    },
+    /// One trial call is in flight; another is allowed after `retry_at` in
+    /// case the trial never reports back
+    HalfOpen {
+        retry_at: DateTime<Utc>,
+    },
+}
+
+/// Fails calls fast while a backend is down.
+///
+/// After `failure_threshold` consecutive failures the circuit opens and every
+/// call is rejected for `cooldown`. The first call after that is a trial: its
+/// success closes the circuit, its failure opens it for another cooldown.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    clock: Arc<dyn Clock>,
+    state: Mutex<BreakerState>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default(), Arc::new(SystemClock))
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config,
+            clock,
+            state: Mutex::new(BreakerState::Closed { failures: 0 }),
+        }
+    }
+
+    /// Ask to make a call; fails with `Error::LlmCommunication("circuit open")`
+    /// while the circuit is open.
+    pub fn try_acquire(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = self.clock.now();
+        match *state {
+            BreakerState::Closed { .. } => Ok(()),
+            BreakerState::Open { until } | BreakerState::HalfOpen { retry_at: until }
+                if now >= until =>
+            {
+                *state = BreakerState::HalfOpen {
+                    retry_at: now + self.config.cooldown,
+                };
+                Ok(())
+            }
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => {
+                Err(Error::LlmCommunication("circuit open".into()))
+            }
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        *state = BreakerState::Closed { failures: 0 };
+    }
+
+    pub fn record_failure(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let failures = match *state {
+            BreakerState::Closed { failures } => failures + 1,
+            // A failed trial reopens the circuit straight away
+            BreakerState::Open { .. } | BreakerState::HalfOpen { .. } => {
+                self.config.failure_threshold
+            }
+        };
+
+        *state = if failures >= self.config.failure_threshold {
+            warn!(
+                "Circuit opened after {} consecutive failures; retrying in {:?}",
+                failures, self.config.cooldown
+            );
+            BreakerState::Open {
+                until: self.clock.now() + self.config.cooldown,
+            }
+        } else {
+            BreakerState::Closed { failures }
+        };
+    }
+
+    /// True while calls are being rejected or a trial is in flight
+    pub fn is_open(&self) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        !matches!(*state, BreakerState::Closed { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::MockClock;
+
+    #[test]
+    fn test_half_open_allows_a_single_trial() {
+        let clock = MockClock::default();
+        let breaker = CircuitBreaker::new(
+            CircuitBreakerConfig {
+                failure_threshold: 1,
+                cooldown: Duration::from_secs(10),
+            },
+            Arc::new(clock.clone()),
+        );
+
+        breaker.record_failure();
+        assert!(breaker.try_acquire().is_err());
+
+        clock.advance(Duration::from_secs(10));
+        assert!(breaker.try_acquire().is_ok());
+        // The trial has not reported back yet
+        assert!(breaker.try_acquire().is_err());
+
+        // A failed trial reopens for a full cooldown
+        breaker.record_failure();
+        clock.advance(Duration::from_secs(9));
+        assert!(breaker.try_acquire().is_err());
+        clock.advance(Duration::from_secs(1));
+        assert!(breaker.try_acquire().is_ok());
+        breaker.record_success();
+        assert!(!breaker.is_open());
+    }
+}
@@ -1,8 +1,29 @@
-use crate::core::error::{Error, Result};
-use crate::core::types::{GeometricMetrics, SystemState};
-use std::path::Path;
+use crate::core::clock::{Clock, SystemClock};
+use crate::core::error::Result;
+use crate::core::semantic_task_processor::TaskStatus;
+use crate::core::types::{GeometricMetrics, GeometricTaskCommand, SystemState};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
 
-/// Very small persistence stub until real storage is implemented.
+/// Committed metrics, under the base path
+pub const METRICS_FILE: &str = "metrics.json";
+/// Write-ahead log for [`METRICS_FILE`]: holds the one pending entry, and is
+/// empty when nothing is pending
+pub const METRICS_WAL_FILE: &str = "metrics.wal";
+/// Attempts at updating [`METRICS_FILE`] once the WAL entry is durable
+const PERSIST_ATTEMPTS: u32 = 3;
+
+/// Durable storage for the processor's metrics under a base directory.
+///
+/// Metrics are committed through a single-entry write-ahead log; see
+/// [`persist_metrics`](Self::persist_metrics). Saving the full system state
+/// is not implemented yet.
 pub struct DataIoGateway;
 
 impl DataIoGateway {
@@ -10,10 +31,332 @@ impl DataIoGateway {
         Ok(None)
     }
 
-    pub fn persist_metrics(_base_path: &Path, _metrics: &GeometricMetrics) -> Result<()> {
-        Err(Error::Io(std::io::Error::new(
-            std::io::ErrorKind::Unsupported,
-            "data persistence not implemented",
-        )))
+    /// Durably replace the persisted metrics.
+    ///
+    /// The metrics are first written to the WAL as its pending entry and
+    /// fsynced; from then on they are committed, even if the process dies
+    /// before [`METRICS_FILE`] is updated, since
+    /// [`load_metrics`](Self::load_metrics) finishes the job. The canonical file is replaced atomically via a
+    /// temporary file and rename, retried a few times, and the WAL is
+    /// truncated once it succeeds.
+    pub fn persist_metrics(base_path: &Path, metrics: &GeometricMetrics) -> Result<()> {
+        fs::create_dir_all(base_path)?;
+        Self::write_pending(base_path, metrics)?;
+        Self::commit_wal(base_path, metrics)
+    }
+
+    /// The persisted metrics, if any, after replaying a WAL entry left
+    /// behind by an interrupted [`persist_metrics`](Self::persist_metrics).
+    /// Call this on startup before anything is persisted again.
+    pub fn load_metrics(base_path: &Path) -> Result<Option<GeometricMetrics>> {
+        let wal_path = base_path.join(METRICS_WAL_FILE);
+        match fs::read(&wal_path) {
+            Ok(bytes) if bytes.is_empty() => {}
+            Ok(bytes) => match serde_json::from_slice::<GeometricMetrics>(&bytes) {
+                Ok(pending) => {
+                    warn!("Replaying uncommitted metrics from {}", wal_path.display());
+                    Self::commit_wal(base_path, &pending)?;
+                }
+                // The crash came before the entry was durable, so it was
+                // never committed; the canonical file is still current
+                Err(err) => {
+                    warn!("Discarding torn metrics WAL entry: {}", err);
+                    Self::truncate_wal(base_path)?;
+                }
+            },
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.into()),
+        }
+
+        match fs::read(base_path.join(METRICS_FILE)) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Make `metrics` the WAL's pending entry, replacing any earlier one: an
+    /// uncommitted entry is superseded by the newer metrics, so the WAL never
+    /// holds more than one.
+    fn write_pending(base_path: &Path, metrics: &GeometricMetrics) -> Result<()> {
+        let mut wal = File::create(base_path.join(METRICS_WAL_FILE))?;
+        wal.write_all(&serde_json::to_vec(metrics)?)?;
+        wal.sync_all()?;
+        Ok(())
+    }
+
+    fn commit_wal(base_path: &Path, metrics: &GeometricMetrics) -> Result<()> {
+        let bytes = serde_json::to_vec_pretty(metrics)?;
+        let mut attempt = 1;
+        loop {
+            match Self::replace_canonical(base_path, &bytes) {
+                Ok(()) => break,
+                Err(err) if attempt < PERSIST_ATTEMPTS => {
+                    warn!(
+                        "Writing {} failed (attempt {}): {}",
+                        METRICS_FILE, attempt, err
+                    );
+                    std::thread::sleep(std::time::Duration::from_millis(10 * u64::from(attempt)));
+                    attempt += 1;
+                }
+                // The WAL entry stays, so the next load still applies it
+                Err(err) => return Err(err),
+            }
+        }
+        Self::truncate_wal(base_path)
+    }
+
+    fn replace_canonical(base_path: &Path, bytes: &[u8]) -> Result<()> {
+        let tmp_path = base_path.join(format!("{}.tmp", METRICS_FILE));
+        let mut tmp = File::create(&tmp_path)?;
+        tmp.write_all(bytes)?;
+        tmp.sync_all()?;
+        fs::rename(&tmp_path, base_path.join(METRICS_FILE))?;
+        Ok(())
+    }
+
+    fn truncate_wal(base_path: &Path) -> Result<()> {
+        File::create(base_path.join(METRICS_WAL_FILE))?.sync_all()?;
+        Ok(())
+    }
+}
+
+/// Something worth recording about the processor's lifecycle
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JournalEvent {
+    TaskSubmitted {
+        task_id: Uuid,
+        command: GeometricTaskCommand,
+    },
+    TaskStatusChanged {
+        task_id: Uuid,
+        status: TaskStatus,
+    },
+    Metrics {
+        metrics: GeometricMetrics,
+    },
+}
+
+/// One line of the journal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub timestamp: DateTime<Utc>,
+    pub event: JournalEvent,
+}
+
+/// Append-only record of [`JournalEvent`]s under a directory.
+///
+/// Entries are written one JSON object per line to `events-YYYYMMDD.ndjson`,
+/// rotating to a new file when the (UTC) date of the entry changes.
+#[derive(Debug)]
+pub struct Journal {
+    base_path: PathBuf,
+    clock: Arc<dyn Clock>,
+}
+
+impl Journal {
+    /// Journal into `base_path`, creating the directory if needed
+    pub fn open(base_path: impl Into<PathBuf>) -> Result<Self> {
+        Self::with_clock(base_path, Arc::new(SystemClock))
+    }
+
+    pub fn with_clock(base_path: impl Into<PathBuf>, clock: Arc<dyn Clock>) -> Result<Self> {
+        let base_path = base_path.into();
+        fs::create_dir_all(&base_path)?;
+        Ok(Self { base_path, clock })
+    }
+
+    /// Timestamp `event` with the journal's clock and append it
+    pub fn append(&self, event: JournalEvent) -> Result<JournalEntry> {
+        let entry = JournalEntry {
+            timestamp: self.clock.now(),
+            event,
+        };
+
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        let path = self.base_path.join(Self::file_name(entry.timestamp));
+        // A single write per line, so concurrent appenders never interleave
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?
+            .write_all(&line)?;
+
+        Ok(entry)
+    }
+
+    /// Every entry under `base_path`, oldest file first and in append order
+    /// within a file. Lines that do not parse, such as one torn by a crash
+    /// mid-write, are logged and skipped.
+    pub fn replay(base_path: &Path) -> Result<impl Iterator<Item = JournalEntry>> {
+        let mut files: Vec<PathBuf> = fs::read_dir(base_path)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("events-") && name.ends_with(".ndjson"))
+            })
+            .collect();
+        // YYYYMMDD names sort chronologically
+        files.sort();
+
+        Ok(files.into_iter().flat_map(|path| {
+            let lines = match File::open(&path) {
+                Ok(file) => Some(BufReader::new(file).lines()),
+                Err(err) => {
+                    warn!("Skipping journal file {}: {}", path.display(), err);
+                    None
+                }
+            };
+            lines
+                .into_iter()
+                .flatten()
+                .map_while(move |line| {
+                    line.map_err(|err| warn!("Stopped reading {}: {}", path.display(), err))
+                        .ok()
+                })
+                .filter(|line| !line.trim().is_empty())
+                .filter_map(|line| match serde_json::from_str(&line) {
+                    Ok(entry) => Some(entry),
+                    Err(err) => {
+                        warn!("Skipping malformed journal line: {}", err);
+                        None
+                    }
+                })
+        }))
+    }
+
+    fn file_name(timestamp: DateTime<Utc>) -> String {
+        format!("events-{}.ndjson", timestamp.format("%Y%m%d"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::MockClock;
+    use std::time::Duration;
+
+    #[test]
+    fn test_metrics_wal_recovers_after_crash_before_canonical_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = GeometricMetrics {
+            v_geometric: 1.0,
+            ..Default::default()
+        };
+        let second = GeometricMetrics {
+            v_geometric: 2.0,
+            ..Default::default()
+        };
+
+        assert_eq!(DataIoGateway::load_metrics(dir.path()).unwrap(), None);
+        DataIoGateway::persist_metrics(dir.path(), &first).unwrap();
+        assert!(fs::read(dir.path().join(METRICS_WAL_FILE))
+            .unwrap()
+            .is_empty());
+
+        // Crash after the WAL fsync, before the canonical file is touched
+        DataIoGateway::write_pending(dir.path(), &second).unwrap();
+        assert_eq!(
+            DataIoGateway::load_metrics(dir.path()).unwrap(),
+            Some(second.clone())
+        );
+        assert!(fs::read(dir.path().join(METRICS_WAL_FILE))
+            .unwrap()
+            .is_empty());
+        let canonical = fs::read(dir.path().join(METRICS_FILE)).unwrap();
+        assert_eq!(
+            serde_json::from_slice::<GeometricMetrics>(&canonical).unwrap(),
+            second
+        );
+
+        // Crash mid-append: the torn entry was never committed
+        fs::write(dir.path().join(METRICS_WAL_FILE), b"{\"v_geometric\": 3").unwrap();
+        assert_eq!(
+            DataIoGateway::load_metrics(dir.path()).unwrap(),
+            Some(second)
+        );
+    }
+
+    #[test]
+    fn test_journal_replays_in_append_order_across_days() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = MockClock::default();
+        let journal = Journal::with_clock(dir.path(), Arc::new(clock.clone())).unwrap();
+
+        let task_id = Uuid::new_v4();
+        let mut appended = vec![journal
+            .append(JournalEvent::TaskStatusChanged {
+                task_id,
+                status: TaskStatus::InProgress,
+            })
+            .unwrap()];
+        // Past midnight, so the rest lands in the next day's file
+        clock.advance(Duration::from_secs(86_400));
+        appended.push(
+            journal
+                .append(JournalEvent::TaskStatusChanged {
+                    task_id,
+                    status: TaskStatus::Failed("stale".into()),
+                })
+                .unwrap(),
+        );
+
+        let mut files: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["events-19700101.ndjson", "events-19700102.ndjson"]);
+
+        // A torn trailing line is skipped rather than ending the replay
+        let mut last = OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(&files[0]))
+            .unwrap();
+        last.write_all(b"{\"timestamp\":").unwrap();
+
+        let replayed: Vec<_> = Journal::replay(dir.path()).unwrap().collect();
+        assert_eq!(replayed, appended);
+    }
+
+    #[test]
+    fn test_journal_replay_rebuilds_metric_trajectory() {
+        let dir = tempfile::tempdir().unwrap();
+        let clock = MockClock::default();
+        let journal = Journal::with_clock(dir.path(), Arc::new(clock.clone())).unwrap();
+
+        let trajectory: Vec<GeometricMetrics> = (0..5)
+            .map(|i| GeometricMetrics {
+                v_geometric: 1.0 + i as f64 * 0.1,
+                s_geometric: 0.5 / (i + 1) as f64,
+                ..Default::default()
+            })
+            .collect();
+        for metrics in &trajectory {
+            journal
+                .append(JournalEvent::Metrics {
+                    metrics: metrics.clone(),
+                })
+                .unwrap();
+            journal
+                .append(JournalEvent::TaskStatusChanged {
+                    task_id: Uuid::new_v4(),
+                    status: TaskStatus::Completed(metrics.clone()),
+                })
+                .unwrap();
+            clock.advance(Duration::from_secs(3_600 * 7));
+        }
+
+        let rebuilt: Vec<GeometricMetrics> = Journal::replay(dir.path())
+            .unwrap()
+            .filter_map(|entry| match entry.event {
+                JournalEvent::Metrics { metrics } => Some(metrics),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(rebuilt, trajectory);
     }
 }
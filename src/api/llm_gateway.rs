@@ -1,18 +1,42 @@
+use crate::api::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::config::LlmConfig;
 use crate::core::{
+    clock::{Clock, SystemClock},
     error::{Error, Result},
-    types::GeometricTaskCommand,
+    types::{GeometricOperator, GeometricTaskCommand},
 };
+use futures_util::{stream, StreamExt};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::debug;
 
-const MISTRAL_ENDPOINT: &str = "https://api.mistral.ai/v1/chat/completions";
+/// Base URL used when `MISTRAL_BASE_URL` is unset.
+pub const DEFAULT_MISTRAL_BASE_URL: &str = "https://api.mistral.ai/v1";
+/// Model used when `MISTRAL_MODEL` is unset.
+pub const DEFAULT_MISTRAL_MODEL: &str = "mistral-small-latest";
+/// Requests in flight at once for [`LlmGateway::submit_geometric_queries`].
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+/// Longest total time a query waits out API rate limits before giving up.
+pub const DEFAULT_MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(5);
+
+/// Assumed delay when a rate-limited response has no usable `Retry-After`
+const DEFAULT_RATE_LIMIT_RETRY_AFTER: Duration = Duration::from_secs(1);
+/// Retries per query after rate-limited responses
+const RATE_LIMIT_RETRIES: u32 = 3;
 
 #[derive(Clone)]
 pub struct LlmGateway {
     client: reqwest::Client,
     api_key: String,
     model: String,
+    base_url: String,
+    /// Shared by clones, so they trip together
+    breaker: Arc<CircuitBreaker>,
+    batch_concurrency: usize,
+    max_rate_limit_wait: Duration,
 }
 
 impl LlmGateway {
@@ -24,17 +48,125 @@ impl LlmGateway {
         Ok(Self {
             client: reqwest::Client::new(),
             api_key: key,
-            model: env::var("MISTRAL_MODEL").unwrap_or_else(|_| "mistral-small-latest".into()),
+            model: env::var("MISTRAL_MODEL").unwrap_or_else(|_| DEFAULT_MISTRAL_MODEL.into()),
+            base_url: env::var("MISTRAL_BASE_URL")
+                .unwrap_or_else(|_| DEFAULT_MISTRAL_BASE_URL.into()),
+            breaker: Arc::new(CircuitBreaker::default()),
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            max_rate_limit_wait: DEFAULT_MAX_RATE_LIMIT_WAIT,
+        })
+    }
+
+    /// Build a gateway from loaded configuration; fails without an API key.
+    pub fn from_config(config: &LlmConfig) -> Result<Self> {
+        let key = config
+            .api_key
+            .clone()
+            .ok_or_else(|| Error::LlmCommunication("Missing MISTRAL_API_KEY".into()))?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_key: key,
+            model: config.model.clone(),
+            base_url: config.base_url.clone(),
+            breaker: Arc::new(CircuitBreaker::new(
+                CircuitBreakerConfig {
+                    failure_threshold: config.circuit_failure_threshold,
+                    cooldown: Duration::from_secs(config.circuit_cooldown_secs),
+                },
+                Arc::new(SystemClock),
+            )),
+            batch_concurrency: config.batch_concurrency,
+            max_rate_limit_wait: Duration::from_secs(config.max_rate_limit_wait_secs),
         })
     }
 
+    /// Replace the circuit breaker, e.g. to change its thresholds or drive it
+    /// from a mock clock.
+    pub fn with_circuit_breaker(
+        mut self,
+        config: CircuitBreakerConfig,
+        clock: Arc<dyn Clock>,
+    ) -> Self {
+        self.breaker = Arc::new(CircuitBreaker::new(config, clock));
+        self
+    }
+
+    /// Cap the requests a batched query keeps in flight; zero is treated as one.
+    pub fn with_batch_concurrency(mut self, limit: usize) -> Self {
+        self.batch_concurrency = limit;
+        self
+    }
+
+    /// Cap the total time a query waits out API rate limits; zero returns
+    /// `Error::CapacityExceeded` on the first rate-limited response.
+    pub fn with_max_rate_limit_wait(mut self, max_wait: Duration) -> Self {
+        self.max_rate_limit_wait = max_wait;
+        self
+    }
+
+    /// Point the gateway at a different API root, e.g. a proxy or mock server.
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Model used when a call does not name one.
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn completions_url(&self) -> String {
+        format!("{}/chat/completions", self.base_url.trim_end_matches('/'))
+    }
+
     pub async fn submit_geometric_query(
         &self,
         query: &str,
         context: &Value,
+    ) -> Result<GeometricTaskCommand> {
+        self.submit_geometric_query_with_model(query, context, None)
+            .await
+    }
+
+    /// Submit several `(query, context)` pairs concurrently, at most
+    /// `batch_concurrency` at a time, returning one result per query in input
+    /// order. Each call goes through the circuit breaker, so once it opens the
+    /// remaining queries fail fast.
+    pub async fn submit_geometric_queries(
+        &self,
+        queries: Vec<(String, Value)>,
+    ) -> Vec<Result<GeometricTaskCommand>> {
+        stream::iter(queries)
+            .map(|(query, context)| async move {
+                self.submit_geometric_query(&query, &context).await
+            })
+            .buffered(self.batch_concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Like [`LlmGateway::submit_geometric_query`], but sends the request to
+    /// `model` instead of the configured default when given.
+    ///
+    /// Transport and API failures count towards the circuit breaker; while it
+    /// is open this fails immediately with `Error::LlmCommunication("circuit
+    /// open")`. When the API rate-limits the call, it is retried after the
+    /// requested delay as long as the total wait stays within
+    /// `max_rate_limit_wait`; otherwise `Error::CapacityExceeded` is returned
+    /// with the API's `Retry-After`.
+    pub async fn submit_geometric_query_with_model(
+        &self,
+        query: &str,
+        context: &Value,
+        model: Option<&str>,
     ) -> Result<GeometricTaskCommand> {
         let payload = LlmRequest {
-            model: self.model.clone(),
+            model: model.unwrap_or(&self.model).to_string(),
             response_format: ResponseFormat {
                 r#type: "json_object".into(),
             },
@@ -50,15 +182,59 @@ impl LlmGateway {
             ],
         };
 
+        let mut waited = Duration::ZERO;
+        let mut retries = 0;
+        let content = loop {
+            self.breaker.try_acquire()?;
+            let outcome = self.request_completion(&payload).await;
+            match outcome {
+                Err(Error::LlmCommunication(_)) => self.breaker.record_failure(),
+                _ => self.breaker.record_success(),
+            }
+            match outcome {
+                Err(Error::CapacityExceeded { retry_after, .. })
+                    if retries < RATE_LIMIT_RETRIES
+                        && waited + retry_after <= self.max_rate_limit_wait =>
+                {
+                    debug!("Rate limited by the LLM API; retrying in {:?}", retry_after);
+                    tokio::time::sleep(retry_after).await;
+                    waited += retry_after;
+                    retries += 1;
+                }
+                outcome => break outcome?,
+            }
+        };
+
+        let mut raw: Value = serde_json::from_str(&content).map_err(Error::Serialization)?;
+        normalize_geometric_operator(&mut raw);
+        serde_json::from_value(raw).map_err(Error::Serialization)
+    }
+
+    /// Send one completion request and return the message content.
+    async fn request_completion(&self, payload: &LlmRequest) -> Result<String> {
         let response = self
             .client
-            .post(MISTRAL_ENDPOINT)
+            .post(self.completions_url())
             .bearer_auth(&self.api_key)
-            .json(&payload)
+            .json(payload)
             .send()
             .await
             .map_err(|err| Error::LlmCommunication(format!("HTTP error: {err}")))?;
 
+        if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_RATE_LIMIT_RETRY_AFTER);
+            return Err(Error::CapacityExceeded {
+                message: "Mistral API rate limit reached".into(),
+                retry_after,
+            });
+        }
+
         if !response.status().is_success() {
             let status = response.status();
             let body = response.text().await.unwrap_or_default();
@@ -72,15 +248,10 @@ impl LlmGateway {
             .await
             .map_err(|err| Error::LlmCommunication(format!("Failed to parse response: {err}")))?;
 
-        let content = body
-            .choices
+        body.choices
             .first()
             .and_then(|choice| choice.message.content.clone())
-            .ok_or_else(|| Error::LlmCommunication("Empty response from Mistral".into()))?;
-
-        let mut raw: Value = serde_json::from_str(&content).map_err(Error::Serialization)?;
-        normalize_geometric_operator(&mut raw);
-        serde_json::from_value(raw).map_err(Error::Serialization)
+            .ok_or_else(|| Error::LlmCommunication("Empty response from Mistral".into()))
     }
 }
 
@@ -120,44 +291,449 @@ struct ChoiceMessage {
     content: Option<String>,
 }
 
+/// Map the model's `geometric_operator` onto an operator name.
+///
+/// Every rewrite is logged at debug level with the original and mapped value,
+/// so reclassifications can be traced by enabling
+/// `RUST_LOG=mmss::api::llm_gateway=debug` without adding noise by default.
 fn normalize_geometric_operator(payload: &mut Value) {
     if let Some(operator_value) = payload.get_mut("geometric_operator") {
         if let Some(raw_text) = operator_value.as_str() {
-            let normalized = map_llm_response_to_operator(raw_text);
-            *operator_value = Value::String(normalized.to_string());
+            let normalized = json!(map_llm_response_to_operator(raw_text));
+            if normalized != raw_text {
+                debug!(
+                    "Normalized LLM geometric_operator '{}' -> '{}'",
+                    raw_text,
+                    normalized.as_str().unwrap_or_default()
+                );
+            }
+            *operator_value = normalized;
         }
     }
 }
 
-fn map_llm_response_to_operator(raw: &str) -> &'static str {
+/// Keyword weights per operator, in tiebreak priority order: when two
+/// operators score the same, the one listed first wins.
+const OPERATOR_KEYWORDS: [(GeometricOperator, &[(&str, u32)]); 4] = [
+    (
+        GeometricOperator::Zitterbewegung,
+        &[("zitter", 3), ("oscillation", 2)],
+    ),
+    (
+        GeometricOperator::GeometricDerivation,
+        &[("derivation", 3), ("derive", 2), ("stabilize", 1)],
+    ),
+    (
+        GeometricOperator::SemanticSynthesis,
+        &[("semantic", 3), ("synthesis", 2), ("anchor", 2)],
+    ),
+    (
+        GeometricOperator::QuaternionRotation,
+        &[
+            ("quaternion", 3),
+            ("rotation", 2),
+            ("coherence", 1),
+            ("optimize", 1),
+        ],
+    ),
+];
+
+/// Classify free text from the model as an operator.
+///
+/// An exact operator name (ignoring case) is taken as is. Otherwise each
+/// operator scores the summed weights of its keywords found in the text and
+/// the highest score wins, ties going to the earlier entry in
+/// [`OPERATOR_KEYWORDS`]. Text with no keywords falls back to
+/// `QuaternionRotation`.
+fn map_llm_response_to_operator(raw: &str) -> GeometricOperator {
     let lowered = raw.trim().to_lowercase();
 
-    if lowered.contains("zitter") || lowered.contains("oscillation") {
-        "Zitterbewegung"
-    } else if lowered.contains("stabilize") || lowered.contains("derivation") {
-        "GeometricDerivation"
-    } else if lowered.contains("semantic") || lowered.contains("anchor") {
-        "SemanticSynthesis"
-    } else if lowered.contains("coherence")
-        || lowered.contains("optimize")
-        || lowered.contains("quaternion")
+    if let Some(exact) = GeometricOperator::ALL
+        .into_iter()
+        .find(|operator| format!("{:?}", operator).to_lowercase() == lowered)
     {
-        "QuaternionRotation"
-    } else if matches!(
-        lowered.as_str(),
-        "quaternionrotation" |
-            "zitterbewegung" |
-            "geometricderivation" |
-            "semanticsynthesis"
-    ) {
-        match lowered.as_str() {
-            "quaternionrotation" => "QuaternionRotation",
-            "zitterbewegung" => "Zitterbewegung",
-            "geometricderivation" => "GeometricDerivation",
-            "semanticsynthesis" => "SemanticSynthesis",
-            _ => "QuaternionRotation",
+        return exact;
+    }
+
+    let mut best = (0, GeometricOperator::QuaternionRotation);
+    for (operator, keywords) in OPERATOR_KEYWORDS {
+        let score: u32 = keywords
+            .iter()
+            .filter(|(keyword, _)| lowered.contains(keyword))
+            .map(|(_, weight)| weight)
+            .sum();
+        if score > best.0 {
+            best = (score, operator);
         }
-    } else {
-        "QuaternionRotation"
+    }
+    best.1
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::GeometricOperator;
+    use axum::{http::HeaderMap, routing::post, Json, Router};
+    use serde_json::json;
+
+    #[test]
+    fn test_operator_matcher_scores_ambiguous_text() {
+        let cases = [
+            // Exact names are kept, whatever their keywords say
+            ("semanticsynthesis", GeometricOperator::SemanticSynthesis),
+            ("QuaternionRotation", GeometricOperator::QuaternionRotation),
+            // "quaternion" + "coherence" outweigh "stabilize"
+            (
+                "stabilize the quaternion coherence",
+                GeometricOperator::QuaternionRotation,
+            ),
+            (
+                "derivation of the quaternion",
+                GeometricOperator::GeometricDerivation,
+            ),
+            // Tied at 3: Zitterbewegung is listed before SemanticSynthesis
+            ("semantic zitter", GeometricOperator::Zitterbewegung),
+            (
+                "anchor the semantic rotation",
+                GeometricOperator::SemanticSynthesis,
+            ),
+            ("optimize stabilize", GeometricOperator::GeometricDerivation),
+            ("", GeometricOperator::QuaternionRotation),
+            ("no idea", GeometricOperator::QuaternionRotation),
+        ];
+        for (raw, expected) in cases {
+            assert_eq!(map_llm_response_to_operator(raw), expected, "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn test_operator_rewrite_is_logged_at_debug() {
+        use crate::telemetry::{subscriber, LogCapture, LogFormat};
+        use tracing_subscriber::EnvFilter;
+
+        let rewrite = |filter: &str, operator: &str| {
+            let capture = LogCapture::default();
+            let writer = capture.clone();
+            let _guard = tracing::subscriber::set_default(subscriber(
+                LogFormat::Pretty,
+                EnvFilter::new(filter),
+                move || writer.clone(),
+            ));
+            let mut payload = json!({ "geometric_operator": operator });
+            normalize_geometric_operator(&mut payload);
+            (payload["geometric_operator"].clone(), capture.contents())
+        };
+
+        let (mapped, logs) = rewrite("debug", "stabilize the field");
+        assert_eq!(mapped, "GeometricDerivation");
+        assert!(
+            logs.contains(
+                "Normalized LLM geometric_operator 'stabilize the field' -> 'GeometricDerivation'"
+            ),
+            "{}",
+            logs
+        );
+
+        // Off at the default level, and silent when nothing was rewritten
+        let (_, logs) = rewrite("info", "stabilize the field");
+        assert!(logs.is_empty(), "{}", logs);
+        let (_, logs) = rewrite("debug", "Zitterbewegung");
+        assert!(logs.is_empty(), "{}", logs);
+    }
+
+    #[test]
+    fn test_completions_url_from_base_url() {
+        let gateway = LlmGateway::new(Some("key".into()))
+            .unwrap()
+            .with_base_url("http://localhost:9000/v1/");
+        assert_eq!(
+            gateway.completions_url(),
+            "http://localhost:9000/v1/chat/completions"
+        );
+    }
+
+    type Received = tokio::sync::mpsc::UnboundedReceiver<(HeaderMap, Value)>;
+
+    /// Mock completions endpoint that records each request and answers with
+    /// a fixed command. Returns the gateway base URL.
+    async fn mock_completions() -> (String, Received) {
+        let (sender, received) = tokio::sync::mpsc::unbounded_channel();
+        let mock = Router::new().route(
+            "/v1/chat/completions",
+            post(move |headers: HeaderMap, Json(body): Json<Value>| {
+                let sender = sender.clone();
+                async move {
+                    sender.send((headers, body)).unwrap();
+                    let command = json!({
+                        "task_name": "Mock task",
+                        "geometric_operator": "zitter oscillation",
+                        "target_module": "sys7_core",
+                        "parameters": {},
+                        "expected_output_metric": "s_geometric"
+                    });
+                    Json(json!({
+                        "choices": [{ "message": { "content": command.to_string() } }]
+                    }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, mock).await.unwrap() });
+
+        (format!("http://{}/v1", addr), received)
+    }
+
+    #[tokio::test]
+    async fn test_submit_geometric_query_uses_base_url() {
+        let (base_url, mut received) = mock_completions().await;
+        let gateway = LlmGateway::new(Some("test-key".into()))
+            .unwrap()
+            .with_base_url(base_url);
+        let command = gateway
+            .submit_geometric_query("stabilize the field", &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(command.task_name, "Mock task");
+        assert_eq!(
+            command.geometric_operator,
+            GeometricOperator::Zitterbewegung
+        );
+
+        let (headers, body) = received.recv().await.unwrap();
+        assert_eq!(headers["authorization"], "Bearer test-key");
+        assert!(body["messages"][1]["content"]
+            .as_str()
+            .unwrap()
+            .contains("stabilize the field"));
+    }
+
+    #[tokio::test]
+    async fn test_submit_geometric_query_model_override() {
+        let (base_url, mut received) = mock_completions().await;
+        let gateway = LlmGateway::new(Some("test-key".into()))
+            .unwrap()
+            .with_base_url(base_url);
+
+        gateway
+            .submit_geometric_query_with_model("rotate", &json!({}), Some("mistral-large-latest"))
+            .await
+            .unwrap();
+        let (_, body) = received.recv().await.unwrap();
+        assert_eq!(body["model"], "mistral-large-latest");
+
+        gateway
+            .submit_geometric_query_with_model("rotate", &json!({}), None)
+            .await
+            .unwrap();
+        let (_, body) = received.recv().await.unwrap();
+        assert_eq!(body["model"], gateway.model());
+    }
+
+    #[tokio::test]
+    async fn test_submit_geometric_queries_bounded_and_ordered() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let echo = {
+            let (in_flight, peak) = (in_flight.clone(), peak.clone());
+            Router::new().route(
+                "/v1/chat/completions",
+                post(move |Json(body): Json<Value>| async move {
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    // Later queries answer sooner, so completion order differs
+                    // from input order
+                    let content = body["messages"][1]["content"].as_str().unwrap();
+                    let query = content.rsplit("Query: ").next().unwrap().to_string();
+                    let index: u64 = query.trim_start_matches("query ").parse().unwrap();
+                    tokio::time::sleep(Duration::from_millis(40 - 3 * index)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                    let command = json!({
+                        "task_name": query,
+                        "geometric_operator": "QuaternionRotation",
+                        "target_module": "sys7_core",
+                        "parameters": {},
+                        "expected_output_metric": "v_geometric"
+                    });
+                    Json(json!({
+                        "choices": [{ "message": { "content": command.to_string() } }]
+                    }))
+                }),
+            )
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, echo).await.unwrap() });
+
+        let gateway = LlmGateway::new(Some("test-key".into()))
+            .unwrap()
+            .with_base_url(format!("http://{}/v1", addr))
+            .with_batch_concurrency(3);
+        let queries: Vec<_> = (0..10)
+            .map(|i| (format!("query {}", i), json!({})))
+            .collect();
+
+        let results = gateway.submit_geometric_queries(queries).await;
+        let names: Vec<_> = results
+            .into_iter()
+            .map(|result| result.unwrap().task_name)
+            .collect();
+        let expected: Vec<_> = (0..10).map(|i| format!("query {}", i)).collect();
+        assert_eq!(names, expected);
+        assert!(peak.load(Ordering::SeqCst) <= 3);
+        assert!(peak.load(Ordering::SeqCst) > 1, "queries ran serially");
+    }
+
+    /// Mock completions endpoint answering 429 with `retry_after` to the
+    /// first `limited` requests and a command after that. Returns the gateway
+    /// base URL and the request counter.
+    async fn rate_limited_completions(
+        limited: usize,
+        retry_after: &'static str,
+    ) -> (String, Arc<std::sync::atomic::AtomicUsize>) {
+        use axum::{http::StatusCode, response::IntoResponse};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let mock = {
+            let hits = hits.clone();
+            Router::new().route(
+                "/v1/chat/completions",
+                post(move || async move {
+                    if hits.fetch_add(1, Ordering::SeqCst) < limited {
+                        return (
+                            StatusCode::TOO_MANY_REQUESTS,
+                            [("retry-after", retry_after)],
+                        )
+                            .into_response();
+                    }
+                    let command = json!({
+                        "task_name": "After the limit",
+                        "geometric_operator": "QuaternionRotation",
+                        "target_module": "sys7_core",
+                        "parameters": {},
+                        "expected_output_metric": "v_geometric"
+                    });
+                    Json(json!({
+                        "choices": [{ "message": { "content": command.to_string() } }]
+                    }))
+                    .into_response()
+                }),
+            )
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, mock).await.unwrap() });
+
+        (format!("http://{}/v1", addr), hits)
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_waits_are_capped() {
+        use std::sync::atomic::Ordering;
+
+        // A short Retry-After is waited out and the query retried
+        let (base_url, hits) = rate_limited_completions(1, "0").await;
+        let gateway = LlmGateway::new(Some("test-key".into()))
+            .unwrap()
+            .with_base_url(base_url);
+        let command = gateway
+            .submit_geometric_query("rotate", &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(command.task_name, "After the limit");
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        // A longer one than the cap is handed back to the caller at once
+        let (base_url, hits) = rate_limited_completions(1, "120").await;
+        let gateway = LlmGateway::new(Some("test-key".into()))
+            .unwrap()
+            .with_base_url(base_url)
+            .with_max_rate_limit_wait(Duration::from_secs(5));
+        let started = std::time::Instant::now();
+        let err = gateway
+            .submit_geometric_query("rotate", &json!({}))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::CapacityExceeded { retry_after, .. } if retry_after == Duration::from_secs(120)
+        ));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+        // Rate limiting is not an outage
+        assert!(!gateway.breaker.is_open());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_opens_and_half_open_probe_closes() {
+        use crate::core::clock::MockClock;
+        use axum::{http::StatusCode, response::IntoResponse};
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        let failing = Arc::new(AtomicBool::new(true));
+        let hits = Arc::new(AtomicUsize::new(0));
+        let flaky = {
+            let (failing, hits) = (failing.clone(), hits.clone());
+            Router::new().route(
+                "/v1/chat/completions",
+                post(move || async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    if failing.load(Ordering::SeqCst) {
+                        return StatusCode::SERVICE_UNAVAILABLE.into_response();
+                    }
+                    let command = json!({
+                        "task_name": "Recovered",
+                        "geometric_operator": "QuaternionRotation",
+                        "target_module": "sys7_core",
+                        "parameters": {},
+                        "expected_output_metric": "v_geometric"
+                    });
+                    Json(json!({
+                        "choices": [{ "message": { "content": command.to_string() } }]
+                    }))
+                    .into_response()
+                }),
+            )
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, flaky).await.unwrap() });
+
+        let clock = MockClock::default();
+        let gateway = LlmGateway::new(Some("test-key".into()))
+            .unwrap()
+            .with_base_url(format!("http://{}/v1", addr))
+            .with_circuit_breaker(
+                CircuitBreakerConfig {
+                    failure_threshold: 3,
+                    cooldown: Duration::from_secs(30),
+                },
+                Arc::new(clock.clone()),
+            );
+        let context = json!({});
+        let query = || gateway.submit_geometric_query("rotate", &context);
+
+        for _ in 0..3 {
+            assert!(query().await.is_err());
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // Open: calls fail fast without reaching the API, even once it is back
+        failing.store(false, Ordering::SeqCst);
+        match query().await {
+            Err(Error::LlmCommunication(message)) => assert_eq!(message, "circuit open"),
+            other => panic!("expected open circuit, got {:?}", other),
+        }
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+
+        // After the cooldown a trial request goes through and closes the circuit
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(query().await.unwrap().task_name, "Recovered");
+        assert!(query().await.is_ok());
+        assert_eq!(hits.load(Ordering::SeqCst), 5);
     }
 }
@@ -1,19 +1,22 @@
 use mmss::core::semantic_task_processor::SemanticTaskProcessor;
-use mmss::core::types::{GeometricOperator, GeometricTaskCommand};
+use mmss::core::types::{GeometricMetrics, GeometricTaskCommand};
+use mmss::telemetry::{self, LogFormat};
 
 fn main() {
-    env_logger::init();
+    if let Err(err) = telemetry::init(LogFormat::Pretty) {
+        eprintln!("{err}");
+    }
     println!("MMSS CLI placeholder");
 
     let processor = SemanticTaskProcessor::new();
 
     let task = GeometricTaskCommand {
-        task_name: "Inspect Quaternion Cohesion".to_string(),
-        geometric_operator: GeometricOperator::QuaternionRotation,
         target_module: "emergence_logic".to_string(),
-        parameters: serde_json::json!({ "sample": "placeholder" }),
-        expected_output_metric: "v_geometric".to_string(),
-        task_id: None,
+        ..GeometricTaskCommand::quaternion_rotation(
+            "Inspect Quaternion Cohesion",
+            [0.0, 1.0, 0.0],
+            0.5,
+        )
     };
 
     match processor.submit_task(task) {
@@ -23,6 +26,11 @@ fn main() {
                 println!("Task success: {}", result.success);
                 println!("{:?}", result.metrics);
             }
+            if let Ok(history) = processor.metrics_history() {
+                if let Ok(line) = GeometricMetrics::sparkline(&history, "v_geometric") {
+                    println!("v_geometric {line}");
+                }
+            }
         }
         Err(err) => eprintln!("Failed to submit task: {err}"),
     }
@@ -1,37 +1,46 @@
-use axum::handler::HandlerWithoutStateExt;
-use axum::routing::get_service;
-use axum::Router;
+use mmss::config;
 use mmss::routes;
+use mmss::state::scheduler::DEFAULT_SCHEDULER_TICK;
+use mmss::state::sweeper::{DEFAULT_MAX_EXECUTION_AGE, DEFAULT_SWEEP_INTERVAL};
 use mmss::state::AppState;
+use mmss::telemetry;
 use tokio::net::TcpListener;
 use tokio::signal;
-use tower_http::{cors::CorsLayer, services::ServeDir, trace::TraceLayer};
+use tracing::info;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     dotenvy::dotenv().ok();
-    env_logger::init();
+    let log_format = config::parse_log_format(std::env::var("MMSS_LOG_FORMAT").ok().as_deref())?;
+    telemetry::init(log_format)?;
 
-    let state = AppState::initialize(None)?;
-    let api_router = routes::build_router().with_state(state.clone());
+    let app_config = config::AppConfig::load()?;
+    let state = AppState::initialize(&app_config)?;
+    state.spawn_scheduler(DEFAULT_SCHEDULER_TICK);
+    state.spawn_stale_sweeper(DEFAULT_SWEEP_INTERVAL, DEFAULT_MAX_EXECUTION_AGE);
+    state.spawn_metrics_persister(app_config.data_dir.clone());
+    state.spawn_journal(app_config.journal_dir())?;
+    let api_router =
+        routes::build_router_with_body_limit(app_config.max_body_bytes).with_state(state.clone());
 
-    let static_service = get_service(ServeDir::new("src/web")).into_service();
+    // No CORS origins means permissive CORS, which is for local development only.
+    let cors = app_config.cors_policy()?;
 
-    let app = Router::new()
-        .nest("/api", api_router)
-        .fallback_service(static_service)
-        .layer(CorsLayer::permissive())
-        .layer(TraceLayer::new_for_http());
+    let static_dir = config::resolve_static_dir(&app_config.static_dir)?;
+    info!("Serving static files from {}", static_dir.display());
 
-    let addr = std::env::var("MMSS_BIND").unwrap_or_else(|_| "127.0.0.1:8080".into());
-    let listener = TcpListener::bind(&addr).await?;
+    let app = routes::build_app(api_router, static_dir).layer(cors.layer());
+    let app = telemetry::with_request_tracing(app);
 
-    println!("MMSS server listening on http://{}", addr);
+    let addr = app_config.bind_addr()?;
+    let listener = TcpListener::bind(addr).await?;
+
+    info!("MMSS server listening on http://{}", addr);
 
     axum::serve(listener, app.into_make_service())
         .with_graceful_shutdown(async {
             let _ = signal::ctrl_c().await;
-            println!("Shutting down by signal");
+            info!("Shutting down by signal");
         })
         .await?;
 
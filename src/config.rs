@@ -0,0 +1,543 @@
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use axum::http::{header, HeaderValue, Method};
+use serde::Deserialize;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+use crate::api::circuit_breaker::CircuitBreakerConfig;
+use crate::api::llm_gateway::{
+    DEFAULT_BATCH_CONCURRENCY, DEFAULT_MAX_RATE_LIMIT_WAIT, DEFAULT_MISTRAL_BASE_URL,
+    DEFAULT_MISTRAL_MODEL,
+};
+use crate::core::emergence_logic::RuleSet;
+use crate::core::error::{Error, Result};
+use crate::state::idempotency::DEFAULT_IDEMPOTENCY_TTL;
+use crate::telemetry::LogFormat;
+
+pub const DEFAULT_BIND: &str = "127.0.0.1:8080";
+pub const DEFAULT_STATIC_DIR: &str = "src/web";
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+pub const DEFAULT_DATA_DIR: &str = "data";
+/// Subdirectory of the data directory holding the event journal
+pub const JOURNAL_SUBDIR: &str = "journal";
+/// Environment variable naming the optional TOML configuration file
+pub const CONFIG_PATH_VAR: &str = "MMSS_CONFIG";
+
+/// Parse a `host:port` bind address such as `127.0.0.1:8080`, `0.0.0.0:8080`
+/// or `[::]:8080`.
+pub fn parse_bind_addr(s: &str) -> Result<SocketAddr> {
+    s.trim().parse().map_err(|_| {
+        Error::InvalidParameter(
+            "MMSS_BIND".into(),
+            format!(
+                "'{}' is not a valid bind address; expected host:port such as {} or [::]:8080",
+                s, DEFAULT_BIND
+            ),
+        )
+    })
+}
+
+/// Parse `MMSS_MAX_BODY_BYTES`, defaulting to 1 MiB when unset.
+pub fn parse_max_body_bytes(value: Option<&str>) -> Result<usize> {
+    match value.map(str::trim) {
+        None | Some("") => Ok(DEFAULT_MAX_BODY_BYTES),
+        Some(raw) => raw.parse().map_err(|_| {
+            Error::InvalidParameter(
+                "MMSS_MAX_BODY_BYTES".into(),
+                format!("'{}' is not a byte count", raw),
+            )
+        }),
+    }
+}
+
+/// Parse `MMSS_IDEMPOTENCY_TTL_SECS`, defaulting to 24 hours when unset.
+pub fn parse_idempotency_ttl(value: Option<&str>) -> Result<Duration> {
+    match value.map(str::trim) {
+        None | Some("") => Ok(DEFAULT_IDEMPOTENCY_TTL),
+        Some(raw) => raw.parse().map(Duration::from_secs).map_err(|_| {
+            Error::InvalidParameter(
+                "MMSS_IDEMPOTENCY_TTL_SECS".into(),
+                format!("'{}' is not a whole number of seconds", raw),
+            )
+        }),
+    }
+}
+
+/// Parse `MMSS_LOG_FORMAT` (`pretty` or `json`), defaulting to pretty when unset.
+pub fn parse_log_format(value: Option<&str>) -> Result<LogFormat> {
+    match value.map(|raw| raw.trim().to_ascii_lowercase()).as_deref() {
+        None | Some("") | Some("pretty") => Ok(LogFormat::Pretty),
+        Some("json") => Ok(LogFormat::Json),
+        Some(_) => Err(Error::InvalidParameter(
+            "MMSS_LOG_FORMAT".into(),
+            format!(
+                "'{}' is not a log format; expected pretty or json",
+                value.unwrap_or_default()
+            ),
+        )),
+    }
+}
+
+/// Resolve the static file root to an absolute path, failing if it is not
+/// an existing directory.
+pub fn resolve_static_dir(dir: impl AsRef<Path>) -> Result<PathBuf> {
+    let dir = dir.as_ref();
+    let resolved = dir.canonicalize().map_err(|err| {
+        Error::InvalidParameter(
+            "MMSS_STATIC_DIR".into(),
+            format!("cannot resolve '{}': {}", dir.display(), err),
+        )
+    })?;
+
+    if !resolved.is_dir() {
+        return Err(Error::InvalidParameter(
+            "MMSS_STATIC_DIR".into(),
+            format!("'{}' is not a directory", resolved.display()),
+        ));
+    }
+
+    Ok(resolved)
+}
+
+/// Which origins the API accepts cross-origin requests from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CorsPolicy {
+    /// Any origin, method and header. Development only.
+    Permissive,
+    /// Only the listed origins, with `GET`/`POST`/`DELETE` and the
+    /// `Authorization`/`Content-Type` headers.
+    Allowlist(Vec<HeaderValue>),
+}
+
+impl CorsPolicy {
+    /// Build the policy from a comma-separated `MMSS_CORS_ORIGINS` value.
+    /// An unset or empty value falls back to [`CorsPolicy::Permissive`].
+    pub fn from_origins(origins: Option<&str>) -> Result<Self> {
+        let origins = origins
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(|origin| {
+                HeaderValue::from_str(origin).map_err(|_| {
+                    Error::InvalidParameter(
+                        "MMSS_CORS_ORIGINS".into(),
+                        format!("'{}' is not a valid origin", origin),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if origins.is_empty() {
+            Ok(CorsPolicy::Permissive)
+        } else {
+            Ok(CorsPolicy::Allowlist(origins))
+        }
+    }
+
+    pub fn layer(&self) -> CorsLayer {
+        match self {
+            CorsPolicy::Permissive => CorsLayer::permissive(),
+            CorsPolicy::Allowlist(origins) => CorsLayer::new()
+                .allow_origin(AllowOrigin::list(origins.iter().cloned()))
+                .allow_methods([Method::GET, Method::POST, Method::DELETE])
+                .allow_headers([header::AUTHORIZATION, header::CONTENT_TYPE]),
+        }
+    }
+}
+
+/// LLM gateway settings, the `[llm]` table of the config file.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LlmConfig {
+    /// `MISTRAL_API_KEY`; LLM routes answer 503 without one
+    pub api_key: Option<String>,
+    /// `MISTRAL_MODEL`
+    pub model: String,
+    /// `MISTRAL_BASE_URL`
+    pub base_url: String,
+    /// Consecutive failed calls before the gateway stops calling the API
+    pub circuit_failure_threshold: u32,
+    /// Seconds to fail fast once the failure threshold is hit
+    pub circuit_cooldown_secs: u64,
+    /// Requests kept in flight at once by batched queries
+    pub batch_concurrency: usize,
+    /// Seconds a query may spend waiting out API rate limits before the
+    /// server answers 429 itself
+    pub max_rate_limit_wait_secs: u64,
+}
+
+impl Default for LlmConfig {
+    fn default() -> Self {
+        Self {
+            api_key: None,
+            model: DEFAULT_MISTRAL_MODEL.into(),
+            base_url: DEFAULT_MISTRAL_BASE_URL.into(),
+            circuit_failure_threshold: CircuitBreakerConfig::default().failure_threshold,
+            circuit_cooldown_secs: CircuitBreakerConfig::default().cooldown.as_secs(),
+            batch_concurrency: DEFAULT_BATCH_CONCURRENCY,
+            max_rate_limit_wait_secs: DEFAULT_MAX_RATE_LIMIT_WAIT.as_secs(),
+        }
+    }
+}
+
+/// Server configuration.
+///
+/// Read from the TOML file named by `MMSS_CONFIG`, if set; any of the
+/// environment variables named on the fields then override the file. Missing
+/// keys take their defaults.
+///
+/// ```toml
+/// bind = "0.0.0.0:8080"
+/// cors_origins = ["https://app.example"]
+///
+/// [llm]
+/// model = "mistral-large-latest"
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AppConfig {
+    /// `MMSS_BIND`, a `host:port` address
+    pub bind: String,
+    /// `MMSS_DATA_DIR`, where persisted data is kept
+    pub data_dir: PathBuf,
+    /// `MMSS_STATIC_DIR`, the root served for non-API paths
+    pub static_dir: PathBuf,
+    /// `MMSS_CORS_ORIGINS` (comma-separated); empty allows any origin
+    pub cors_origins: Vec<String>,
+    /// `MMSS_MAX_BODY_BYTES`
+    pub max_body_bytes: usize,
+    /// `MMSS_IDEMPOTENCY_TTL_SECS`
+    pub idempotency_ttl_secs: u64,
+    /// `MMSS_RULES_PATH`, an emergence rule set in JSON
+    pub rules_path: Option<PathBuf>,
+    pub llm: LlmConfig,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            bind: DEFAULT_BIND.into(),
+            data_dir: DEFAULT_DATA_DIR.into(),
+            static_dir: DEFAULT_STATIC_DIR.into(),
+            cors_origins: Vec::new(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            idempotency_ttl_secs: DEFAULT_IDEMPOTENCY_TTL.as_secs(),
+            rules_path: None,
+            llm: LlmConfig::default(),
+        }
+    }
+}
+
+impl AppConfig {
+    /// Load from `MMSS_CONFIG` and the process environment.
+    pub fn load() -> Result<Self> {
+        Self::load_with(|name| std::env::var(name).ok())
+    }
+
+    /// Load using `env` to look up environment variables.
+    pub fn load_with(env: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let mut config = match env(CONFIG_PATH_VAR).filter(|path| !path.trim().is_empty()) {
+            Some(path) => Self::from_toml_file(path.trim())?,
+            None => Self::default(),
+        };
+        config.apply_env(env)?;
+        Ok(config)
+    }
+
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| toml::from_str(&text).map_err(|err| err.to_string()))
+            .map_err(|err| {
+                Error::InvalidParameter(
+                    CONFIG_PATH_VAR.into(),
+                    format!("cannot load config from '{}': {}", path.display(), err),
+                )
+            })
+    }
+
+    fn apply_env(&mut self, env: impl Fn(&str) -> Option<String>) -> Result<()> {
+        // An empty variable counts as unset, as with the individual parsers
+        let var = |name: &str| env(name).filter(|value| !value.trim().is_empty());
+
+        if let Some(bind) = var("MMSS_BIND") {
+            self.bind = bind;
+        }
+        if let Some(dir) = var("MMSS_DATA_DIR") {
+            self.data_dir = dir.into();
+        }
+        if let Some(dir) = var("MMSS_STATIC_DIR") {
+            self.static_dir = dir.into();
+        }
+        if let Some(origins) = var("MMSS_CORS_ORIGINS") {
+            self.cors_origins = origins
+                .split(',')
+                .map(str::trim)
+                .filter(|origin| !origin.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Some(raw) = var("MMSS_MAX_BODY_BYTES") {
+            self.max_body_bytes = parse_max_body_bytes(Some(&raw))?;
+        }
+        if let Some(raw) = var("MMSS_IDEMPOTENCY_TTL_SECS") {
+            self.idempotency_ttl_secs = parse_idempotency_ttl(Some(&raw))?.as_secs();
+        }
+        if let Some(path) = var("MMSS_RULES_PATH") {
+            self.rules_path = Some(path.into());
+        }
+        if let Some(key) = var("MISTRAL_API_KEY") {
+            self.llm.api_key = Some(key);
+        }
+        if let Some(model) = var("MISTRAL_MODEL") {
+            self.llm.model = model;
+        }
+        if let Some(url) = var("MISTRAL_BASE_URL") {
+            self.llm.base_url = url;
+        }
+        Ok(())
+    }
+
+    pub fn bind_addr(&self) -> Result<SocketAddr> {
+        parse_bind_addr(&self.bind)
+    }
+
+    pub fn cors_policy(&self) -> Result<CorsPolicy> {
+        CorsPolicy::from_origins(Some(&self.cors_origins.join(",")))
+    }
+
+    /// Where the event journal is kept, under `data_dir`
+    pub fn journal_dir(&self) -> PathBuf {
+        self.data_dir.join(JOURNAL_SUBDIR)
+    }
+
+    pub fn idempotency_ttl(&self) -> Duration {
+        Duration::from_secs(self.idempotency_ttl_secs)
+    }
+
+    /// The rule set at `rules_path`, if one is configured.
+    pub fn rule_set(&self) -> Result<Option<RuleSet>> {
+        let Some(path) = &self.rules_path else {
+            return Ok(None);
+        };
+        RuleSet::from_json_file(path).map(Some).map_err(|err| {
+            Error::InvalidParameter(
+                "MMSS_RULES_PATH".into(),
+                format!("cannot load rules from '{}': {}", path.display(), err),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parse_bind_addr_ipv4() {
+        let addr = parse_bind_addr("0.0.0.0:8080").unwrap();
+        assert!(addr.is_ipv4());
+        assert_eq!(addr.port(), 8080);
+    }
+
+    #[test]
+    fn test_parse_bind_addr_ipv6() {
+        let addr = parse_bind_addr("[::]:9000").unwrap();
+        assert!(addr.is_ipv6());
+        assert_eq!(addr.port(), 9000);
+    }
+
+    #[test]
+    fn test_parse_bind_addr_invalid() {
+        let err = parse_bind_addr("localhost8080").unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("localhost8080"));
+        assert!(message.contains("host:port"));
+    }
+
+    #[test]
+    fn test_resolve_static_dir_existing() {
+        let dir = tempfile::tempdir().unwrap();
+        let resolved = resolve_static_dir(dir.path()).unwrap();
+        assert!(resolved.is_absolute());
+        assert!(resolved.is_dir());
+    }
+
+    #[test]
+    fn test_resolve_static_dir_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("does-not-exist");
+        let err = resolve_static_dir(&missing).unwrap_err();
+        assert!(err.to_string().contains("MMSS_STATIC_DIR"));
+    }
+
+    #[test]
+    fn test_cors_policy_allowlist() {
+        let policy =
+            CorsPolicy::from_origins(Some("https://a.example, https://b.example")).unwrap();
+        assert_eq!(
+            policy,
+            CorsPolicy::Allowlist(vec![
+                HeaderValue::from_static("https://a.example"),
+                HeaderValue::from_static("https://b.example"),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cors_policy_permissive_when_unset_or_empty() {
+        assert_eq!(
+            CorsPolicy::from_origins(None).unwrap(),
+            CorsPolicy::Permissive
+        );
+        assert_eq!(
+            CorsPolicy::from_origins(Some(" ")).unwrap(),
+            CorsPolicy::Permissive
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cors_allowlist_layer_rejects_unlisted_origin() {
+        use axum::{body::Body, http::Request, routing::get, Router};
+        use tower::ServiceExt;
+
+        let policy = CorsPolicy::from_origins(Some("https://a.example")).unwrap();
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(policy.layer());
+
+        for (origin, allowed) in [("https://a.example", true), ("https://evil.example", false)] {
+            let request = Request::get("/")
+                .header(header::ORIGIN, origin)
+                .body(Body::empty())
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            let allow = response.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN);
+            assert_eq!(allow.is_some(), allowed, "origin {}", origin);
+        }
+    }
+
+    #[test]
+    fn test_parse_max_body_bytes() {
+        assert_eq!(parse_max_body_bytes(None).unwrap(), DEFAULT_MAX_BODY_BYTES);
+        assert_eq!(parse_max_body_bytes(Some("2048")).unwrap(), 2048);
+        assert!(parse_max_body_bytes(Some("1MB")).is_err());
+    }
+
+    #[test]
+    fn test_parse_idempotency_ttl() {
+        assert_eq!(
+            parse_idempotency_ttl(None).unwrap(),
+            DEFAULT_IDEMPOTENCY_TTL
+        );
+        assert_eq!(
+            parse_idempotency_ttl(Some("90")).unwrap(),
+            Duration::from_secs(90)
+        );
+        assert!(parse_idempotency_ttl(Some("-1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_log_format() {
+        assert_eq!(parse_log_format(None).unwrap(), LogFormat::Pretty);
+        assert_eq!(parse_log_format(Some("")).unwrap(), LogFormat::Pretty);
+        assert_eq!(parse_log_format(Some("JSON")).unwrap(), LogFormat::Json);
+        assert!(matches!(
+            parse_log_format(Some("xml")),
+            Err(Error::InvalidParameter(name, _)) if name == "MMSS_LOG_FORMAT"
+        ));
+    }
+
+    fn env_from(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn write_config(dir: &tempfile::TempDir) -> String {
+        let path = dir.path().join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+bind = "0.0.0.0:9090"
+data_dir = "/var/lib/mmss"
+cors_origins = ["https://a.example"]
+max_body_bytes = 4096
+
+[llm]
+api_key = "file-key"
+model = "mistral-large-latest"
+"#,
+        )
+        .unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_app_config_from_toml_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(&dir);
+
+        let config = AppConfig::load_with(env_from(&[(CONFIG_PATH_VAR, &path)])).unwrap();
+        assert_eq!(config.bind_addr().unwrap().port(), 9090);
+        assert_eq!(config.data_dir, PathBuf::from("/var/lib/mmss"));
+        assert_eq!(config.max_body_bytes, 4096);
+        assert_eq!(
+            config.cors_policy().unwrap(),
+            CorsPolicy::Allowlist(vec![HeaderValue::from_static("https://a.example")])
+        );
+        assert_eq!(config.llm.api_key.as_deref(), Some("file-key"));
+        assert_eq!(config.llm.model, "mistral-large-latest");
+        // Keys missing from the file keep their defaults
+        assert_eq!(config.static_dir, PathBuf::from(DEFAULT_STATIC_DIR));
+        assert_eq!(config.llm.base_url, DEFAULT_MISTRAL_BASE_URL);
+    }
+
+    #[test]
+    fn test_app_config_env_overrides_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_config(&dir);
+
+        let config = AppConfig::load_with(env_from(&[
+            (CONFIG_PATH_VAR, &path),
+            ("MMSS_BIND", "127.0.0.1:7000"),
+            ("MISTRAL_MODEL", "mistral-small-latest"),
+            ("MMSS_CORS_ORIGINS", ""),
+        ]))
+        .unwrap();
+        assert_eq!(config.bind, "127.0.0.1:7000");
+        assert_eq!(config.llm.model, "mistral-small-latest");
+        // Empty variables do not override
+        assert_eq!(config.cors_origins, ["https://a.example"]);
+        assert_eq!(config.llm.api_key.as_deref(), Some("file-key"));
+
+        let invalid = env_from(&[(CONFIG_PATH_VAR, &path), ("MMSS_MAX_BODY_BYTES", "lots")]);
+        assert!(matches!(
+            AppConfig::load_with(invalid),
+            Err(Error::InvalidParameter(name, _)) if name == "MMSS_MAX_BODY_BYTES"
+        ));
+    }
+
+    #[test]
+    fn test_app_config_rejects_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, "bnid = \"0.0.0.0:80\"\n").unwrap();
+
+        assert!(matches!(
+            AppConfig::from_toml_file(&path),
+            Err(Error::InvalidParameter(name, _)) if name == CONFIG_PATH_VAR
+        ));
+        assert_eq!(
+            AppConfig::load_with(env_from(&[])).unwrap(),
+            AppConfig::default()
+        );
+    }
+}
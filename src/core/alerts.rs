@@ -0,0 +1,180 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::core::error::{Error, Result};
+use crate::core::types::GeometricMetrics;
+
+/// Called with every alert raised by an [`AlertMonitor`]
+pub type AlertCallback = Arc<dyn Fn(Alert) + Send + Sync>;
+
+/// Which bound of a threshold was crossed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertBound {
+    Min,
+    Max,
+}
+
+/// A metric that moved outside its configured range
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Alert {
+    pub field: String,
+    pub value: f64,
+    pub bound: AlertBound,
+    /// The threshold that was crossed
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Threshold {
+    min: Option<f64>,
+    max: Option<f64>,
+    /// The bound the metric is currently outside of, if any
+    breached: Option<AlertBound>,
+}
+
+impl Threshold {
+    fn check(&self, value: f64) -> Option<(AlertBound, f64)> {
+        match (self.min, self.max) {
+            (Some(min), _) if value < min => Some((AlertBound::Min, min)),
+            (_, Some(max)) if value > max => Some((AlertBound::Max, max)),
+            _ => None,
+        }
+    }
+}
+
+/// Per-metric thresholds with edge-triggered alerts.
+///
+/// An alert is raised when a metric first moves outside its range, not on
+/// every update while it stays there; it re-arms once the metric is back in
+/// range (or crosses to the other bound).
+#[derive(Default)]
+pub struct AlertMonitor {
+    thresholds: BTreeMap<String, Threshold>,
+    callback: Option<AlertCallback>,
+}
+
+impl fmt::Debug for AlertMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlertMonitor")
+            .field("thresholds", &self.thresholds)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+impl AlertMonitor {
+    /// Watch `field` for values below `min` or above `max`, replacing any
+    /// existing threshold on it. With neither bound the field is unwatched.
+    pub fn set(&mut self, field: String, min: Option<f64>, max: Option<f64>) -> Result<()> {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min > max {
+                return Err(Error::InvalidParameter(
+                    "alert".into(),
+                    format!("min {} is greater than max {}", min, max),
+                ));
+            }
+        }
+        if [min, max].into_iter().flatten().any(f64::is_nan) {
+            return Err(Error::InvalidParameter(
+                "alert".into(),
+                "thresholds must not be NaN".into(),
+            ));
+        }
+
+        if min.is_none() && max.is_none() {
+            self.thresholds.remove(&field);
+        } else {
+            self.thresholds.insert(
+                field,
+                Threshold {
+                    min,
+                    max,
+                    breached: None,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    pub fn set_callback(&mut self, callback: AlertCallback) {
+        self.callback = Some(callback);
+    }
+
+    pub fn callback(&self) -> Option<AlertCallback> {
+        self.callback.clone()
+    }
+
+    /// Check `metrics` against every threshold and return the alerts newly
+    /// raised. Fields missing from `metrics` are skipped.
+    pub fn evaluate(&mut self, metrics: &GeometricMetrics) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        for (field, threshold) in self.thresholds.iter_mut() {
+            let Some(value) = metrics.field(field) else {
+                continue;
+            };
+
+            let breach = threshold.check(value);
+            let bound = breach.map(|(bound, _)| bound);
+            if let Some((bound, limit)) = breach {
+                if threshold.breached != Some(bound) {
+                    alerts.push(Alert {
+                        field: field.clone(),
+                        value,
+                        bound,
+                        threshold: limit,
+                    });
+                }
+            }
+            threshold.breached = bound;
+        }
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_v(v_geometric: f64) -> GeometricMetrics {
+        GeometricMetrics {
+            v_geometric,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_alerts_fire_once_per_breach() {
+        let mut monitor = AlertMonitor::default();
+        monitor
+            .set("v_geometric".into(), Some(0.0), Some(1.0))
+            .unwrap();
+
+        assert!(monitor.evaluate(&with_v(0.5)).is_empty());
+        let alerts = monitor.evaluate(&with_v(1.5));
+        assert_eq!(
+            alerts,
+            vec![Alert {
+                field: "v_geometric".into(),
+                value: 1.5,
+                bound: AlertBound::Max,
+                threshold: 1.0,
+            }]
+        );
+        // Still above: no new alert
+        assert!(monitor.evaluate(&with_v(2.0)).is_empty());
+
+        // Back in range re-arms, crossing again fires again
+        assert!(monitor.evaluate(&with_v(0.9)).is_empty());
+        assert_eq!(monitor.evaluate(&with_v(1.1)).len(), 1);
+        // Jumping straight to the other bound is a new breach
+        assert_eq!(monitor.evaluate(&with_v(-1.0))[0].bound, AlertBound::Min);
+
+        assert!(monitor
+            .set("v_geometric".into(), Some(2.0), Some(1.0))
+            .is_err());
+    }
+}
@@ -1,15 +1,98 @@
+use crate::core::error::Result;
 use crate::core::types::{GeometricMetrics, GeometricOperator, Quaternion};
 use crate::state::{
-    compute_electron_mass, compute_fine_structure, compute_quaternion_coherence, compute_zitter_entropy,
-    C, HBAR, ZITTER_AMPLITUDE,
+    compute_electron_mass, compute_fine_structure, compute_quaternion_coherence,
+    compute_zitter_entropy, C, HBAR, ZITTER_AMPLITUDE,
 };
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
+use std::path::Path;
 
-/// Simple placeholder for emergence logic parameters.
-#[derive(Debug, Clone)]
-pub struct EmergenceConfig {
-    pub step_size: f64,
+/// Per-operator coefficients used by [`EmergenceLogic::apply_operator`].
+///
+/// Fields missing from a JSON rule file keep their default values.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RuleSet {
+    pub quaternion_rotation: RotationRules,
+    pub zitterbewegung: ZitterRules,
+    pub geometric_derivation: DerivationRules,
+    pub semantic_synthesis: SynthesisRules,
+}
+
+impl RuleSet {
+    /// Load a rule set from a JSON file.
+    pub fn from_json_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RotationRules {
+    /// Coherence gained per unit of `|sin(theta / 2)|` times the axis length
+    pub coherence_gain: f64,
+    /// Upper bound on quaternion coherence
+    pub max_coherence: f64,
+}
+
+impl Default for RotationRules {
+    fn default() -> Self {
+        Self {
+            coherence_gain: 0.005,
+            max_coherence: 0.9999,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ZitterRules {
+    /// Winding added per unit of `frequency_scale` above 1
+    pub winding_gain: f64,
+}
+
+impl Default for ZitterRules {
+    fn default() -> Self {
+        Self {
+            winding_gain: 0.0001,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DerivationRules {
+    /// Entropy added per unit of `delta`
+    pub entropy_gain: f64,
+}
+
+impl Default for DerivationRules {
+    fn default() -> Self {
+        Self {
+            entropy_gain: 0.001,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SynthesisRules {
+    /// Anchor strength per unit of coherence times `coherence_hint`
+    pub strength_gain: f64,
+    /// `coherence_hint` used when the task does not supply one
+    pub default_coherence_hint: f64,
+}
+
+impl Default for SynthesisRules {
+    fn default() -> Self {
+        Self {
+            strength_gain: 10.0,
+            default_coherence_hint: 0.95,
+        }
+    }
 }
 
 fn normalize_axis(arr: &[Value]) -> Option<[f64; 3]> {
@@ -17,7 +100,7 @@ fn normalize_axis(arr: &[Value]) -> Option<[f64; 3]> {
         return None;
     }
 
-    let x = arr.get(0).and_then(Value::as_f64)?;
+    let x = arr.first().and_then(Value::as_f64)?;
     let y = arr.get(1).and_then(Value::as_f64)?;
     let z = arr.get(2).and_then(Value::as_f64)?;
     Some([x, y, z])
@@ -45,29 +128,114 @@ impl EmergenceLogic {
     }
 }
 
-impl Default for EmergenceConfig {
-    fn default() -> Self {
-        Self { step_size: 0.01 }
-    }
+/// Additive change one emergence rule made to one metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleContribution {
+    /// Rule responsible, e.g. `quaternion_rotation` or `fine_structure_coupling`
+    pub rule: String,
+    /// Scalar field or custom metric that changed
+    pub field: String,
+    pub delta: f64,
+}
+
+/// Contributions for every metric that differs between `before` and `after`.
+/// Custom metrics missing from `before` count as zero.
+fn contributions_between(
+    rule: &str,
+    before: &GeometricMetrics,
+    after: &GeometricMetrics,
+) -> Vec<RuleContribution> {
+    let scalars = before
+        .scalar_fields()
+        .into_iter()
+        .zip(after.scalar_fields())
+        .map(|((field, old), (_, new))| (field.to_string(), new - old));
+
+    let mut custom: Vec<_> = after
+        .custom_metrics
+        .iter()
+        .map(|(field, new)| {
+            let old = before.custom_metrics.get(field).copied().unwrap_or(0.0);
+            (field.clone(), new - old)
+        })
+        .collect();
+    custom.sort_by(|a, b| a.0.cmp(&b.0));
+
+    scalars
+        .chain(custom)
+        .filter(|(_, delta)| *delta != 0.0)
+        .map(|(field, delta)| RuleContribution {
+            rule: rule.to_string(),
+            field,
+            delta,
+        })
+        .collect()
 }
 
 /// Basic SYS7-SYS1 cascade placeholder.
 #[derive(Debug, Clone)]
 pub struct EmergenceLogic {
-    config: EmergenceConfig,
+    rules: RuleSet,
     metrics: GeometricMetrics,
 }
 
 impl EmergenceLogic {
-    pub fn new(config: Option<EmergenceConfig>) -> Self {
+    /// Start from the baseline metrics, using `rules` or the default
+    /// coefficients when `None`.
+    pub fn new(rules: Option<RuleSet>) -> Self {
         Self {
-            config: config.unwrap_or_default(),
+            rules: rules.unwrap_or_default(),
             metrics: Self::baseline_metrics(),
         }
     }
 
-    pub fn apply_operator(&mut self, op: GeometricOperator, params: &Value) -> &GeometricMetrics {
+    /// Apply `op` and return the new metrics along with the contribution of
+    /// each rule that changed them. The contributions for a field sum to its
+    /// overall change.
+    pub fn apply_operator(
+        &mut self,
+        op: GeometricOperator,
+        params: &Value,
+    ) -> (GeometricMetrics, Vec<RuleContribution>) {
+        let before = self.metrics.clone();
+        self.apply_operator_rule(op, params);
+        let mut contributions =
+            contributions_between(operator_rule_name(op), &before, &self.metrics);
+
+        let before = self.metrics.clone();
+        self.metrics.fine_structure_constant =
+            (compute_fine_structure() / self.metrics.quaternion_coherence.max(1e-6)).min(1.0);
+        contributions.extend(contributions_between(
+            "fine_structure_coupling",
+            &before,
+            &self.metrics,
+        ));
+
+        let before = self.metrics.clone();
+        if self.metrics.zitterbewegung_entropy <= 0.0 {
+            self.metrics.zitterbewegung_entropy = compute_zitter_entropy();
+        }
+        if self.metrics.emergent_electron_mass <= 0.0 {
+            self.metrics.emergent_electron_mass = compute_electron_mass();
+        }
+        if self.metrics.quaternion_coherence <= 0.0 {
+            self.metrics.quaternion_coherence = compute_quaternion_coherence();
+        }
+        if self.metrics.topological_winding <= 0.0 {
+            self.metrics.topological_winding = self.metrics.q_oscillator;
+        }
+        contributions.extend(contributions_between(
+            "baseline_floor",
+            &before,
+            &self.metrics,
+        ));
+
+        (self.metrics.clone(), contributions)
+    }
+
+    fn apply_operator_rule(&mut self, op: GeometricOperator, params: &Value) {
         let magnitude = extract_scalar(params).unwrap_or(1.0);
+        let rules = &self.rules;
 
         match op {
             GeometricOperator::QuaternionRotation => {
@@ -82,10 +250,13 @@ impl EmergenceLogic {
                     .unwrap_or([0.0, 1.0, 0.0]);
 
                 let axis_norm = (axis[0].powi(2) + axis[1].powi(2) + axis[2].powi(2)).sqrt();
-                let coherence_boost = (theta * 0.5).sin().abs() * 0.005 * axis_norm.max(1e-6);
+                let coherence_boost = (theta * 0.5).sin().abs()
+                    * rules.quaternion_rotation.coherence_gain
+                    * axis_norm.max(1e-6);
 
-                self.metrics.quaternion_coherence = (self.metrics.quaternion_coherence + coherence_boost)
-                    .clamp(0.0, 0.9999);
+                self.metrics.quaternion_coherence = (self.metrics.quaternion_coherence
+                    + coherence_boost)
+                    .clamp(0.0, rules.quaternion_rotation.max_coherence);
                 self.metrics.v_geometric = self.metrics.quaternion_coherence;
             }
             GeometricOperator::Zitterbewegung => {
@@ -96,8 +267,9 @@ impl EmergenceLogic {
                 let scaled_amplitude = (ZITTER_AMPLITUDE / freq_scale.max(1e-6)).abs();
 
                 self.metrics.emergent_electron_mass = HBAR / (2.0 * C * scaled_amplitude);
-                self.metrics.topological_winding =
-                    (self.metrics.topological_winding + (freq_scale - 1.0) * 0.0001).max(0.0);
+                self.metrics.topological_winding = (self.metrics.topological_winding
+                    + (freq_scale - 1.0) * rules.zitterbewegung.winding_gain)
+                    .max(0.0);
                 self.metrics.q_oscillator = self.metrics.topological_winding.max(0.0);
             }
             GeometricOperator::GeometricDerivation => {
@@ -105,43 +277,30 @@ impl EmergenceLogic {
                     .get("delta")
                     .and_then(Value::as_f64)
                     .unwrap_or(magnitude);
-                self.metrics.s_geometric = (self.metrics.s_geometric + delta * 0.001).clamp(0.0001, 1.0);
+                self.metrics.s_geometric = (self.metrics.s_geometric
+                    + delta * rules.geometric_derivation.entropy_gain)
+                    .clamp(0.0001, 1.0);
                 self.metrics.zitterbewegung_entropy = self.metrics.s_geometric;
             }
             GeometricOperator::SemanticSynthesis => {
                 let coherence_hint = params
                     .get("coherence_hint")
                     .and_then(Value::as_f64)
-                    .unwrap_or(0.95);
+                    .unwrap_or(rules.semantic_synthesis.default_coherence_hint);
                 let anchor_name = params
                     .get("anchor")
                     .and_then(Value::as_str)
                     .unwrap_or("quantum-atom");
 
-                let semantic_strength =
-                    (self.metrics.quaternion_coherence * coherence_hint * 10.0).max(0.0);
+                let semantic_strength = (self.metrics.quaternion_coherence
+                    * coherence_hint
+                    * rules.semantic_synthesis.strength_gain)
+                    .max(0.0);
                 self.metrics
                     .custom_metrics
                     .insert(format!("anchor:{}", anchor_name), semantic_strength);
             }
         }
-
-        self.metrics.fine_structure_constant =
-            (compute_fine_structure() / self.metrics.quaternion_coherence.max(1e-6)).min(1.0);
-        if self.metrics.zitterbewegung_entropy <= 0.0 {
-            self.metrics.zitterbewegung_entropy = compute_zitter_entropy();
-        }
-        if self.metrics.emergent_electron_mass <= 0.0 {
-            self.metrics.emergent_electron_mass = compute_electron_mass();
-        }
-        if self.metrics.quaternion_coherence <= 0.0 {
-            self.metrics.quaternion_coherence = compute_quaternion_coherence();
-        }
-        if self.metrics.topological_winding <= 0.0 {
-            self.metrics.topological_winding = self.metrics.q_oscillator;
-        }
-
-        &self.metrics
     }
 
     pub fn integrate_quaternion(&mut self, q: Quaternion) -> &GeometricMetrics {
@@ -152,9 +311,34 @@ impl EmergenceLogic {
         &self.metrics
     }
 
+    /// Continue from `metrics` instead of the baseline
+    pub fn with_metrics(mut self, metrics: GeometricMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
     pub fn metrics(&self) -> &GeometricMetrics {
         &self.metrics
     }
+
+    pub fn rules(&self) -> &RuleSet {
+        &self.rules
+    }
+
+    /// Use `rules` from the next operator on, keeping the current metrics
+    pub fn set_rules(&mut self, rules: RuleSet) {
+        self.rules = rules;
+    }
+}
+
+/// Rule name for an operator, matching its key in [`RuleSet`].
+fn operator_rule_name(op: GeometricOperator) -> &'static str {
+    match op {
+        GeometricOperator::QuaternionRotation => "quaternion_rotation",
+        GeometricOperator::Zitterbewegung => "zitterbewegung",
+        GeometricOperator::GeometricDerivation => "geometric_derivation",
+        GeometricOperator::SemanticSynthesis => "semantic_synthesis",
+    }
 }
 
 fn extract_scalar(params: &Value) -> Option<f64> {
@@ -172,3 +356,137 @@ fn extract_scalar(params: &Value) -> Option<f64> {
 
     None
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use serde_json::json;
+
+    #[test]
+    fn test_rule_sets_change_metric_deltas() {
+        let params = json!({ "delta": 0.1 });
+        let mut gentle = EmergenceLogic::new(None);
+        let mut strong = EmergenceLogic::new(Some(RuleSet {
+            geometric_derivation: DerivationRules { entropy_gain: 0.01 },
+            ..Default::default()
+        }));
+
+        let before = gentle.metrics().s_geometric;
+        let gentle_delta = gentle
+            .apply_operator(GeometricOperator::GeometricDerivation, &params)
+            .0
+            .s_geometric
+            - before;
+        let strong_delta = strong
+            .apply_operator(GeometricOperator::GeometricDerivation, &params)
+            .0
+            .s_geometric
+            - before;
+
+        assert_relative_eq!(strong_delta, gentle_delta * 10.0, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_default_rules_match_original_coefficients() {
+        let mut logic = EmergenceLogic::new(None);
+        assert_eq!(logic.rules(), &RuleSet::default());
+
+        let before = logic.metrics().s_geometric;
+        let after = logic
+            .apply_operator(
+                GeometricOperator::GeometricDerivation,
+                &json!({ "delta": 0.2 }),
+            )
+            .0
+            .s_geometric;
+        assert_relative_eq!(after - before, 0.0002, max_relative = 1e-9);
+
+        let before = logic.metrics().topological_winding;
+        let after = logic
+            .apply_operator(
+                GeometricOperator::Zitterbewegung,
+                &json!({ "frequency_scale": 3.0 }),
+            )
+            .0
+            .topological_winding;
+        assert_relative_eq!(after - before, 0.0002, max_relative = 1e-9);
+    }
+
+    #[test]
+    fn test_rule_set_from_partial_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rules.json");
+        std::fs::write(&path, r#"{ "zitterbewegung": { "winding_gain": 0.5 } }"#).unwrap();
+
+        let rules = RuleSet::from_json_file(&path).unwrap();
+        assert_eq!(rules.zitterbewegung.winding_gain, 0.5);
+        assert_eq!(rules.quaternion_rotation, RotationRules::default());
+
+        std::fs::write(&path, r#"{ "zitterbewegung": { "winding_gian": 0.5 } }"#).unwrap();
+        assert!(RuleSet::from_json_file(&path).is_err());
+    }
+
+    #[test]
+    fn test_contributions_sum_to_metric_delta() {
+        let cases = [
+            (
+                GeometricOperator::QuaternionRotation,
+                json!({ "theta": 0.3 }),
+            ),
+            (
+                GeometricOperator::Zitterbewegung,
+                json!({ "frequency_scale": 2.5 }),
+            ),
+            (
+                GeometricOperator::GeometricDerivation,
+                json!({ "delta": -0.4 }),
+            ),
+            (
+                GeometricOperator::SemanticSynthesis,
+                json!({ "anchor": "probe" }),
+            ),
+        ];
+
+        for (op, params) in cases {
+            let mut logic = EmergenceLogic::new(None);
+            let before = logic.metrics().clone();
+            let (after, contributions) = logic.apply_operator(op, &params);
+            assert!(!contributions.is_empty(), "{:?}", op);
+            assert!(contributions
+                .iter()
+                .any(|c| c.rule == operator_rule_name(op)));
+
+            for (field, old) in before.scalar_fields() {
+                let new = after
+                    .scalar_fields()
+                    .iter()
+                    .find(|(f, _)| *f == field)
+                    .unwrap()
+                    .1;
+                let explained: f64 = contributions
+                    .iter()
+                    .filter(|c| c.field == field)
+                    .map(|c| c.delta)
+                    .sum();
+                assert_relative_eq!(explained, new - old, epsilon = 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_contributions_cover_custom_metrics() {
+        let mut logic = EmergenceLogic::new(None);
+        let (after, contributions) = logic.apply_operator(
+            GeometricOperator::SemanticSynthesis,
+            &json!({ "anchor": "probe" }),
+        );
+
+        let anchor = contributions
+            .iter()
+            .find(|c| c.field == "anchor:probe")
+            .unwrap();
+        assert_eq!(anchor.rule, "semantic_synthesis");
+        assert_eq!(anchor.delta, after.custom_metrics["anchor:probe"]);
+    }
+}
@@ -1,9 +1,12 @@
+use crate::core::error::Result;
+use crate::core::types::Quaternion;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Fundamental fields in EQGFT v2.1
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct EQGFTFields {
     /// Unit quaternion rotor field Q(x) = q_0(x) + i q_1(x) + j q_2(x) + k q_3(x)
     pub quaternion_field: QuaternionField,
@@ -15,8 +18,39 @@ pub struct EQGFTFields {
     pub metric: Metric,
 }
 
+impl Default for EQGFTFields {
+    /// Vacuum configuration: identity rotor at the origin, zero gauge field and
+    /// flat Minkowski metric.
+    fn default() -> Self {
+        let signature = [-1, 1, 1, 1];
+        let mut tensor = [[0.0; 4]; 4];
+        for (i, row) in tensor.iter_mut().enumerate() {
+            row[i] = f64::from(signature[i]);
+        }
+
+        Self {
+            quaternion_field: QuaternionField {
+                q0: 1.0,
+                q1: 0.0,
+                q2: 0.0,
+                q3: 0.0,
+                coordinates: [0.0; 4],
+            },
+            dirac_spinor: DiracSpinor {
+                components: [num_complex::Complex::new(0.0, 0.0); 4],
+                vacuum_seed: [1.0, 0.0, 0.0, 0.0],
+            },
+            gauge_field: GaugeField {
+                potential: [0.0; 4],
+                field_strength: [[0.0; 4]; 4],
+            },
+            metric: Metric { tensor, signature },
+        }
+    }
+}
+
 /// Unit quaternion rotor field
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct QuaternionField {
     pub q0: f64,
     pub q1: f64,
@@ -26,17 +60,109 @@ pub struct QuaternionField {
     pub coordinates: [f64; 4],
 }
 
+impl QuaternionField {
+    /// Rotor components as a quaternion
+    pub fn rotor(&self) -> Quaternion {
+        Quaternion::new(self.q0, self.q1, self.q2, self.q3)
+    }
+
+    /// Slerp the rotor and linearly interpolate the coordinates
+    pub fn slerp(&self, other: &QuaternionField, t: f64) -> QuaternionField {
+        let rotor = self
+            .rotor()
+            .normalize()
+            .slerp(&other.rotor().normalize(), t);
+        let mut coordinates = [0.0; 4];
+        for (i, c) in coordinates.iter_mut().enumerate() {
+            *c = self.coordinates[i] + t * (other.coordinates[i] - self.coordinates[i]);
+        }
+
+        QuaternionField {
+            q0: rotor.w,
+            q1: rotor.x,
+            q2: rotor.y,
+            q3: rotor.z,
+            coordinates,
+        }
+    }
+
+    /// Fields at the points of the rotor's [`Quaternion::slerp_path`], with
+    /// coordinates interpolated linearly alongside
+    pub fn slerp_path(&self, other: &QuaternionField, steps: usize) -> Vec<QuaternionField> {
+        let rotors = self.rotor().slerp_path(&other.rotor(), steps);
+        let segments = (rotors.len() - 1) as f64;
+
+        rotors
+            .into_iter()
+            .enumerate()
+            .map(|(i, rotor)| {
+                let t = i as f64 / segments;
+                let mut coordinates = [0.0; 4];
+                for (j, c) in coordinates.iter_mut().enumerate() {
+                    *c = self.coordinates[j] + t * (other.coordinates[j] - self.coordinates[j]);
+                }
+
+                QuaternionField {
+                    q0: rotor.w,
+                    q1: rotor.x,
+                    q2: rotor.y,
+                    q3: rotor.z,
+                    coordinates,
+                }
+            })
+            .collect()
+    }
+}
+
 /// Derived Dirac spinor field
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct DiracSpinor {
-    /// Spinor components (4 complex numbers)
+    /// Spinor components (4 complex numbers), serialized as `{"re": .., "im": ..}`
+    #[serde(with = "complex_components")]
+    #[schema(value_type = Vec<Object>)]
     pub components: [num_complex::Complex<f64>; 4],
     /// Vacuum seed spinor
     pub vacuum_seed: [f64; 4],
 }
 
+impl DiracSpinor {
+    /// Sum of |c|² over the spinor components
+    pub fn norm_squared(&self) -> f64 {
+        self.components.iter().map(|c| c.norm_sqr()).sum()
+    }
+}
+
+/// Serde adapter writing complex numbers as `{"re": .., "im": ..}` objects
+/// instead of num_complex's default `[re, im]` pairs.
+mod complex_components {
+    use num_complex::Complex;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct ComplexRepr {
+        re: f64,
+        im: f64,
+    }
+
+    pub fn serialize<S: Serializer>(
+        values: &[Complex<f64>; 4],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        values
+            .map(|c| ComplexRepr { re: c.re, im: c.im })
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[Complex<f64>; 4], D::Error> {
+        let reprs = <[ComplexRepr; 4]>::deserialize(deserializer)?;
+        Ok(reprs.map(|c| Complex::new(c.re, c.im)))
+    }
+}
+
 /// U(1) gauge field
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct GaugeField {
     /// Gauge potential A_μ
     pub potential: [f64; 4],
@@ -45,7 +171,7 @@ pub struct GaugeField {
 }
 
 /// Lorentzian metric
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct Metric {
     /// Metric tensor g_μν
     pub tensor: [[f64; 4]; 4],
@@ -54,7 +180,7 @@ pub struct Metric {
 }
 
 /// EQGFT v2.1 action terms
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct EQGFTAction {
     /// Gravity term: (1/2κ) R
     pub gravity: f64,
@@ -69,7 +195,8 @@ pub struct EQGFTAction {
 }
 
 /// Visualization packet for EQGFT v2.1
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[schema(as = EqgftVisualizationPacket)]
 pub struct VisualizationPacket {
     /// Unique identifier for this visualization
     pub id: Uuid,
@@ -87,8 +214,25 @@ pub struct VisualizationPacket {
     pub metadata: serde_json::Value,
 }
 
+/// zstd level used for packets sent over the wire
+const PACKET_ZSTD_LEVEL: i32 = 3;
+
+impl VisualizationPacket {
+    /// Serialize to JSON and compress with zstd
+    pub fn to_compressed_bytes(&self) -> Result<Vec<u8>> {
+        let json = serde_json::to_vec(self)?;
+        Ok(zstd::encode_all(json.as_slice(), PACKET_ZSTD_LEVEL)?)
+    }
+
+    /// Inverse of [`VisualizationPacket::to_compressed_bytes`]
+    pub fn from_compressed_bytes(bytes: &[u8]) -> Result<Self> {
+        let json = zstd::decode_all(bytes)?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
 /// Type of visualization
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum VisualizationType {
     /// 2D plot
     Plot2D,
@@ -103,18 +247,19 @@ pub enum VisualizationType {
 }
 
 /// Request for visualization
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VisualizationRequest {
     /// Type of visualization to generate
     pub visualization_type: VisualizationType,
     /// Parameters for the visualization
+    #[serde(default)]
     pub parameters: serde_json::Value,
     /// Optional callback URL for async processing
     pub callback_url: Option<String>,
 }
 
 /// Response from visualization service
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VisualizationResponse {
     /// Status of the visualization request
     pub status: VisualizationStatus,
@@ -127,7 +272,7 @@ pub struct VisualizationResponse {
 }
 
 /// Status of visualization processing
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum VisualizationStatus {
     /// Visualization is queued for processing
     Queued,
@@ -211,3 +356,105 @@ impl Default for ToolRegistry {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn field(axis: [f64; 3], angle: f64, coordinates: [f64; 4]) -> QuaternionField {
+        let q = Quaternion::from_axis_angle(axis, angle);
+        QuaternionField {
+            q0: q.w,
+            q1: q.x,
+            q2: q.y,
+            q3: q.z,
+            coordinates,
+        }
+    }
+
+    #[test]
+    fn test_quaternion_field_slerp() {
+        let a = field([0.0, 0.0, 1.0], 0.2, [0.0, 0.0, 0.0, 0.0]);
+        let b = field([1.0, 0.0, 0.0], 2.0, [2.0, 4.0, -2.0, 1.0]);
+
+        let start = a.slerp(&b, 0.0);
+        let end = a.slerp(&b, 1.0);
+        for (got, want) in [(&start, &a), (&end, &b)] {
+            assert_relative_eq!(got.q0, want.q0, epsilon = 1e-10);
+            assert_relative_eq!(got.q1, want.q1, epsilon = 1e-10);
+            assert_relative_eq!(got.q2, want.q2, epsilon = 1e-10);
+            assert_relative_eq!(got.q3, want.q3, epsilon = 1e-10);
+            assert_eq!(got.coordinates, want.coordinates);
+        }
+
+        let mid = a.slerp(&b, 0.5);
+        assert_relative_eq!(mid.rotor().norm(), 1.0, epsilon = 1e-10);
+        assert_eq!(mid.coordinates, [1.0, 2.0, -1.0, 0.5]);
+    }
+
+    fn spinor() -> DiracSpinor {
+        DiracSpinor {
+            components: [
+                num_complex::Complex::new(1.0, 2.0),
+                num_complex::Complex::new(0.0, -1.0),
+                num_complex::Complex::new(3.0, 0.0),
+                num_complex::Complex::new(0.5, 0.5),
+            ],
+            vacuum_seed: [1.0, 0.0, 0.0, 0.0],
+        }
+    }
+
+    #[test]
+    fn test_dirac_spinor_serialization_shape() {
+        let value = serde_json::to_value(spinor()).unwrap();
+        assert_eq!(
+            value["components"][0],
+            serde_json::json!({ "re": 1.0, "im": 2.0 })
+        );
+        assert_eq!(
+            value["components"][1],
+            serde_json::json!({ "re": 0.0, "im": -1.0 })
+        );
+
+        let restored: DiracSpinor = serde_json::from_value(value).unwrap();
+        assert_eq!(restored.components, spinor().components);
+        assert_eq!(restored.vacuum_seed, spinor().vacuum_seed);
+    }
+
+    #[test]
+    fn test_dirac_spinor_norm_squared() {
+        // |1+2i|² + |-i|² + |3|² + |0.5+0.5i|² = 5 + 1 + 9 + 0.5
+        assert_relative_eq!(spinor().norm_squared(), 15.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_visualization_packet_compression_round_trip() {
+        let fields = EQGFTFields {
+            dirac_spinor: spinor(),
+            ..EQGFTFields::default()
+        };
+        let frames: Vec<_> = (0..32)
+            .map(|i| field([0.0, 1.0, 0.0], i as f64 * 0.1, [0.0, 0.0, 0.0, i as f64]))
+            .collect();
+        let packet = VisualizationPacket {
+            id: Uuid::new_v4(),
+            timestamp: chrono::Utc::now(),
+            fields,
+            action: EQGFTAction::default(),
+            metrics: HashMap::from([
+                ("v_geometric".to_string(), 0.9997),
+                ("s_geometric".to_string(), 0.0003),
+            ]),
+            visualization_type: VisualizationType::Animation,
+            metadata: serde_json::json!({ "frames": frames }),
+        };
+
+        let compressed = packet.to_compressed_bytes().unwrap();
+        let restored = VisualizationPacket::from_compressed_bytes(&compressed).unwrap();
+        assert_eq!(restored, packet);
+
+        let raw = serde_json::to_vec(&packet).unwrap();
+        assert!(compressed.len() < raw.len());
+    }
+}
@@ -1,6 +1,25 @@
+use serde::Serialize;
+use std::time::Duration;
 use thiserror::Error;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+/// A single validation failure, located by a path such as `parameters.axis[2]`
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct FieldError {
+    pub path: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
 /// Main error type for the MMSS system
 #[derive(Error, Debug)]
 pub enum Error {
@@ -16,10 +35,21 @@ pub enum Error {
     #[error("Invalid parameter '{0}': {1}")]
     InvalidParameter(String, String),
 
+    /// One or more fields failed validation
+    #[error("Validation failed: {}", format_field_errors(.0))]
+    Validation(Vec<FieldError>),
+
     /// LLM communication error
     #[error("LLM communication error: {0}")]
     LlmCommunication(String),
 
+    /// A limit was hit; the caller may try again after `retry_after`
+    #[error("Capacity exceeded: {message}")]
+    CapacityExceeded {
+        message: String,
+        retry_after: Duration,
+    },
+
     /// Serialization/deserialization error
     #[error("Serialization error: {0}")]
     Serialization(#[from] serde_json::Error),
@@ -33,5 +63,13 @@ pub enum Error {
     Other(#[from] anyhow::Error),
 }
 
+fn format_field_errors(errors: &[FieldError]) -> String {
+    errors
+        .iter()
+        .map(|e| format!("{}: {}", e.path, e.message))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
 /// Result type for the MMSS system
 pub type Result<T> = std::result::Result<T, Error>;
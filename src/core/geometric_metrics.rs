@@ -1,10 +1,225 @@
+use crate::core::error::{Error, FieldError, Result};
 use crate::core::types::GeometricMetrics;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use utoipa::ToSchema;
 
 /// Function signature for dynamic metric rules.
 type RuleFn = Arc<dyn Fn(&mut GeometricMetrics) + Send + Sync>;
 
+impl GeometricMetrics {
+    /// Named scalar fields, excluding custom metrics.
+    pub fn scalar_fields(&self) -> [(&'static str, f64); 8] {
+        [
+            ("v_geometric", self.v_geometric),
+            ("s_geometric", self.s_geometric),
+            ("q_oscillator", self.q_oscillator),
+            ("quaternion_coherence", self.quaternion_coherence),
+            ("emergent_electron_mass", self.emergent_electron_mass),
+            ("fine_structure_constant", self.fine_structure_constant),
+            ("zitterbewegung_entropy", self.zitterbewegung_entropy),
+            ("topological_winding", self.topological_winding),
+        ]
+    }
+
+    /// Value of a named scalar field or custom metric.
+    pub fn field(&self, name: &str) -> Option<f64> {
+        self.scalar_fields()
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| *value)
+            .or_else(|| self.custom_metrics.get(name).copied())
+    }
+
+    /// Copy with every scalar field and custom metric rounded to `decimals`
+    /// decimal places. Values too large to scale are left unchanged.
+    pub fn rounded(&self, decimals: u32) -> Self {
+        let scale = 10f64.powi(decimals.min(i32::MAX as u32) as i32);
+        let round = |value: f64| {
+            let rounded = (value * scale).round() / scale;
+            if rounded.is_finite() {
+                rounded
+            } else {
+                value
+            }
+        };
+
+        Self {
+            v_geometric: round(self.v_geometric),
+            s_geometric: round(self.s_geometric),
+            q_oscillator: round(self.q_oscillator),
+            quaternion_coherence: round(self.quaternion_coherence),
+            emergent_electron_mass: round(self.emergent_electron_mass),
+            fine_structure_constant: round(self.fine_structure_constant),
+            zitterbewegung_entropy: round(self.zitterbewegung_entropy),
+            topological_winding: round(self.topological_winding),
+            custom_metrics: self
+                .custom_metrics
+                .iter()
+                .map(|(name, value)| (name.clone(), round(*value)))
+                .collect(),
+        }
+    }
+
+    /// Render one field across `history` as a Unicode sparkline, one block
+    /// per snapshot, scaled between the series' minimum and maximum. A
+    /// constant series renders as a flat line of the lowest block and an empty
+    /// history as an empty string. Fails with `InvalidParameter` when a
+    /// snapshot has no such field.
+    pub fn sparkline(history: &[GeometricMetrics], field: &str) -> Result<String> {
+        const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+        let values = history
+            .iter()
+            .map(|metrics| {
+                metrics.field(field).ok_or_else(|| {
+                    Error::InvalidParameter(
+                        field.to_string(),
+                        format!("unknown metric '{}'", field),
+                    )
+                })
+            })
+            .collect::<Result<Vec<f64>>>()?;
+
+        let min = values.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let range = max - min;
+        let top = (BLOCKS.len() - 1) as f64;
+
+        Ok(values
+            .iter()
+            .map(|value| {
+                let level = if range > 0.0 {
+                    (value - min) / range * top
+                } else {
+                    0.0
+                };
+                BLOCKS[level.round() as usize]
+            })
+            .collect())
+    }
+}
+
+/// Pick the named scalar fields or custom metrics. Every unknown name is
+/// reported as a validation error at `fields[i]`.
+pub fn select_fields(metrics: &GeometricMetrics, names: &[&str]) -> Result<BTreeMap<String, f64>> {
+    let mut selected = BTreeMap::new();
+    let mut errors = Vec::new();
+
+    for (i, name) in names.iter().enumerate() {
+        match metrics.field(name) {
+            Some(value) => {
+                selected.insert(name.to_string(), value);
+            }
+            None => errors.push(FieldError::new(
+                format!("fields[{}]", i),
+                format!("unknown metric '{}'", name),
+            )),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(selected)
+    } else {
+        Err(Error::Validation(errors))
+    }
+}
+
+/// Per-field change between two metric snapshots.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct MetricsDiff {
+    /// `to - from` for every scalar field and every custom metric present in
+    /// either snapshot (as `custom_metrics.<name>`, missing values count as 0).
+    pub deltas: BTreeMap<String, f64>,
+    /// Fields that changed, largest absolute delta first.
+    pub most_changed: Vec<String>,
+}
+
+/// Compute the field-by-field difference `to - from`.
+pub fn diff_metrics(from: &GeometricMetrics, to: &GeometricMetrics) -> MetricsDiff {
+    let mut deltas = BTreeMap::new();
+    for ((name, before), (_, after)) in from.scalar_fields().into_iter().zip(to.scalar_fields()) {
+        deltas.insert(name.to_string(), after - before);
+    }
+    for key in from.custom_metrics.keys().chain(to.custom_metrics.keys()) {
+        let before = from.custom_metrics.get(key).copied().unwrap_or(0.0);
+        let after = to.custom_metrics.get(key).copied().unwrap_or(0.0);
+        deltas.insert(format!("custom_metrics.{}", key), after - before);
+    }
+
+    let mut most_changed: Vec<(&String, f64)> = deltas
+        .iter()
+        .filter(|(_, delta)| **delta != 0.0)
+        .map(|(name, delta)| (name, delta.abs()))
+        .collect();
+    most_changed.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let most_changed = most_changed
+        .into_iter()
+        .map(|(name, _)| name.clone())
+        .collect();
+
+    MetricsDiff {
+        deltas,
+        most_changed,
+    }
+}
+
+/// Number of snapshots before the latest one used as the anomaly baseline.
+pub const ANOMALY_WINDOW: usize = 64;
+
+/// A scalar field whose latest value deviates sharply from recent history.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct Anomaly {
+    pub field: String,
+    pub value: f64,
+    pub mean: f64,
+    pub std_dev: f64,
+    /// `(value - mean) / std_dev`; infinite when the baseline is constant
+    pub z_score: f64,
+}
+
+/// Flag scalar fields of the last snapshot whose |z-score| against the
+/// preceding `ANOMALY_WINDOW` snapshots exceeds `z_threshold`. Needs at least
+/// two baseline snapshots; returns nothing otherwise.
+pub fn detect_anomalies(history: &[GeometricMetrics], z_threshold: f64) -> Vec<Anomaly> {
+    let Some((latest, earlier)) = history.split_last() else {
+        return Vec::new();
+    };
+    let window = &earlier[earlier.len().saturating_sub(ANOMALY_WINDOW)..];
+    if window.len() < 2 {
+        return Vec::new();
+    }
+
+    let n = window.len() as f64;
+    let mut anomalies = Vec::new();
+    for (i, (field, value)) in latest.scalar_fields().into_iter().enumerate() {
+        let samples = window.iter().map(|m| m.scalar_fields()[i].1);
+        let mean = samples.clone().sum::<f64>() / n;
+        let std_dev = (samples.map(|x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+
+        let deviation = value - mean;
+        let z_score = if std_dev > f64::EPSILON {
+            deviation / std_dev
+        } else if deviation.abs() > f64::EPSILON {
+            f64::INFINITY.copysign(deviation)
+        } else {
+            0.0
+        };
+
+        if z_score.abs() > z_threshold {
+            anomalies.push(Anomaly {
+                field: field.to_string(),
+                value,
+                mean,
+                std_dev,
+                z_score,
+            });
+        }
+    }
+    anomalies
+}
+
 /// Engine that stores and applies dynamic metric rules.
 #[derive(Default)]
 pub struct GeometricMetricEngine {
@@ -76,10 +291,100 @@ mod tests {
             v_geometric: 1.0,
             s_geometric: 1.0,
             q_oscillator: 1.0,
+            quaternion_coherence: 0.0,
+            emergent_electron_mass: 0.0,
+            fine_structure_constant: 0.0,
+            zitterbewegung_entropy: 0.0,
+            topological_winding: 0.0,
             custom_metrics: HashMap::new(),
         };
 
         assert!(engine.apply_rule("boost_v", &mut metrics));
         assert_eq!(metrics.v_geometric, 1.5);
     }
+
+    #[test]
+    fn test_detect_anomalies_constant_baseline() {
+        let stable = GeometricMetrics {
+            v_geometric: 1.0,
+            s_geometric: 1.0,
+            q_oscillator: 1.0,
+            quaternion_coherence: 0.5,
+            emergent_electron_mass: 0.0,
+            fine_structure_constant: 0.0,
+            zitterbewegung_entropy: 0.0,
+            topological_winding: 0.0,
+            custom_metrics: HashMap::new(),
+        };
+        let mut history = vec![stable.clone(); 5];
+        assert!(detect_anomalies(&history, 1.0).is_empty());
+
+        let mut jump = stable;
+        jump.quaternion_coherence = 0.25;
+        history.push(jump);
+        let anomalies = detect_anomalies(&history, 1.0);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].field, "quaternion_coherence");
+        assert_eq!(anomalies[0].z_score, f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_select_fields() {
+        let mut metrics = GeometricMetrics {
+            v_geometric: 1.0,
+            s_geometric: 2.0,
+            q_oscillator: 3.0,
+            quaternion_coherence: 0.5,
+            emergent_electron_mass: 0.0,
+            fine_structure_constant: 0.0,
+            zitterbewegung_entropy: 0.0,
+            topological_winding: 0.0,
+            custom_metrics: HashMap::new(),
+        };
+        metrics.custom_metrics.insert("boost".to_string(), 7.0);
+
+        let selected = select_fields(&metrics, &["s_geometric", "boost"]).unwrap();
+        assert_eq!(selected.len(), 2);
+        assert_eq!(selected["s_geometric"], 2.0);
+        assert_eq!(selected["boost"], 7.0);
+
+        match select_fields(&metrics, &["v_geometric", "nope", "custom_metrics"]) {
+            Err(Error::Validation(errors)) => {
+                let paths: Vec<_> = errors.iter().map(|e| e.path.as_str()).collect();
+                assert_eq!(paths, ["fields[1]", "fields[2]"]);
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_sparkline() {
+        let series = |values: &[f64]| -> Vec<GeometricMetrics> {
+            values
+                .iter()
+                .map(|v| GeometricMetrics {
+                    s_geometric: *v,
+                    ..Default::default()
+                })
+                .collect()
+        };
+
+        let rising = series(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0]);
+        assert_eq!(
+            GeometricMetrics::sparkline(&rising, "s_geometric").unwrap(),
+            "▁▂▃▄▅▆▇█"
+        );
+
+        let flat = series(&[0.42; 5]);
+        assert_eq!(
+            GeometricMetrics::sparkline(&flat, "s_geometric").unwrap(),
+            "▁▁▁▁▁"
+        );
+
+        assert_eq!(GeometricMetrics::sparkline(&[], "s_geometric").unwrap(), "");
+        assert!(matches!(
+            GeometricMetrics::sparkline(&rising, "nope"),
+            Err(Error::InvalidParameter(..))
+        ));
+    }
 }
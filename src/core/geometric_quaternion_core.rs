@@ -1,5 +1,13 @@
+use crate::core::error::{Error, Result};
 use crate::core::types::Quaternion;
+use serde::Deserialize;
+use std::collections::VecDeque;
 use std::f64::consts::PI;
+use tracing::warn;
+
+/// `|sin(pitch)|` above which [`Quaternion::to_euler_checked`] reports gimbal
+/// lock, roughly 0.8° from vertical.
+pub const GIMBAL_LOCK_SIN_PITCH: f64 = 0.9999;
 
 impl Quaternion {
     /// Create a new quaternion
@@ -7,6 +15,34 @@ impl Quaternion {
         Self { w, x, y, z }
     }
 
+    /// Build from `[w, x, y, z]`, scalar first. Same as
+    /// [`Quaternion::from_wxyz`].
+    pub fn from_array(components: [f64; 4]) -> Self {
+        Self::from_wxyz(components)
+    }
+
+    /// Components as `[w, x, y, z]`, scalar first.
+    pub fn to_array(&self) -> [f64; 4] {
+        [self.w, self.x, self.y, self.z]
+    }
+
+    /// Build from scalar-first `[w, x, y, z]`, the order used by this crate
+    /// and by `QuaternionField`'s `q0..q3`.
+    pub fn from_wxyz([w, x, y, z]: [f64; 4]) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Build from scalar-last `[x, y, z, w]`, the order used by most GPU and
+    /// game-engine buffers.
+    pub fn from_xyzw([x, y, z, w]: [f64; 4]) -> Self {
+        Self { w, x, y, z }
+    }
+
+    /// Components as scalar-last `[x, y, z, w]`.
+    pub fn to_xyzw(&self) -> [f64; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
     /// Create a quaternion from axis-angle representation
     pub fn from_axis_angle(axis: [f64; 3], angle_rad: f64) -> Self {
         let half_angle = angle_rad / 2.0;
@@ -27,6 +63,24 @@ impl Quaternion {
         }
     }
 
+    /// Recover the rotation axis (unit length) and angle in radians, in
+    /// `[0, π]`. `q` and `-q` give the same result; the identity returns
+    /// angle 0 about `[1, 0, 0]`.
+    pub fn to_axis_angle(&self) -> ([f64; 3], f64) {
+        let mut q = self.normalize();
+        if q.w < 0.0 {
+            q = Quaternion::new(-q.w, -q.x, -q.y, -q.z);
+        }
+
+        let sin_half = (q.x * q.x + q.y * q.y + q.z * q.z).sqrt();
+        if sin_half < 1e-10 {
+            return ([1.0, 0.0, 0.0], 0.0);
+        }
+
+        let angle = 2.0 * sin_half.atan2(q.w);
+        ([q.x / sin_half, q.y / sin_half, q.z / sin_half], angle)
+    }
+
     /// Get the identity quaternion
     pub fn identity() -> Self {
         Self {
@@ -57,6 +111,21 @@ impl Quaternion {
         }
     }
 
+    /// The representative of this rotation's double cover (`q` and `-q` are the
+    /// same rotation) with `w >= 0`. When `w == 0` the first nonzero of
+    /// `x, y, z` is made positive instead.
+    pub fn canonical(&self) -> Self {
+        let leading = [self.w, self.x, self.y, self.z]
+            .into_iter()
+            .find(|c| *c != 0.0)
+            .unwrap_or(0.0);
+        if leading < 0.0 {
+            Self::new(-self.w, -self.x, -self.y, -self.z)
+        } else {
+            *self
+        }
+    }
+
     /// Quaternion norm (length)
     pub fn norm(&self) -> f64 {
         (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
@@ -64,16 +133,22 @@ impl Quaternion {
 
     /// Normalize the quaternion
     pub fn normalize(&self) -> Self {
+        let mut q = *self;
+        q.normalize_mut();
+        q
+    }
+
+    /// Normalize in place; a near-zero quaternion becomes the identity.
+    pub fn normalize_mut(&mut self) {
         let n = self.norm();
         if n < 1e-10 {
-            return Self::identity();
-        }
-        Self {
-            w: self.w / n,
-            x: self.x / n,
-            y: self.y / n,
-            z: self.z / n,
+            *self = Self::identity();
+            return;
         }
+        self.w /= n;
+        self.x /= n;
+        self.y /= n;
+        self.z /= n;
     }
 
     /// Rotate a 3D vector using this quaternion
@@ -91,6 +166,69 @@ impl Quaternion {
         [p_rotated.x, p_rotated.y, p_rotated.z]
     }
 
+    /// Row-major rotation matrix, `m[row][col]`, such that `m * v` equals
+    /// [`rotate_vector`](Self::rotate_vector)`(v)`.
+    pub fn to_rotation_matrix(&self) -> [[f64; 3]; 3] {
+        let Quaternion { w, x, y, z } = self.normalize();
+        [
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y - w * z),
+                2.0 * (x * z + w * y),
+            ],
+            [
+                2.0 * (x * y + w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z - w * x),
+            ],
+            [
+                2.0 * (x * z - w * y),
+                2.0 * (y * z + w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        ]
+    }
+
+    /// The rotated X, Y and Z axes, i.e. the columns of
+    /// [`to_rotation_matrix`](Self::to_rotation_matrix).
+    pub fn basis_vectors(&self) -> ([f64; 3], [f64; 3], [f64; 3]) {
+        let Quaternion { w, x, y, z } = self.normalize();
+        (
+            [
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y + w * z),
+                2.0 * (x * z - w * y),
+            ],
+            [
+                2.0 * (x * y - w * z),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z + w * x),
+            ],
+            [
+                2.0 * (x * z + w * y),
+                2.0 * (y * z - w * x),
+                1.0 - 2.0 * (x * x + y * y),
+            ],
+        )
+    }
+
+    /// 4x4 homogeneous transform that rotates, then translates.
+    ///
+    /// Column-major, as WebGL and glTF expect: `m[col][row]`, so `m[3]` is
+    /// `[tx, ty, tz, 1]` and the bottom row is `[0, 0, 0, 1]`. Note this is
+    /// the transpose of the indexing used by
+    /// [`to_rotation_matrix`](Self::to_rotation_matrix).
+    pub fn to_homogeneous(&self, translation: [f64; 3]) -> [[f64; 4]; 4] {
+        let r = self.to_rotation_matrix();
+        let [tx, ty, tz] = translation;
+        [
+            [r[0][0], r[1][0], r[2][0], 0.0],
+            [r[0][1], r[1][1], r[2][1], 0.0],
+            [r[0][2], r[1][2], r[2][2], 0.0],
+            [tx, ty, tz, 1.0],
+        ]
+    }
+
     /// Convert to Euler angles (roll, pitch, yaw)
     pub fn to_euler(&self) -> (f64, f64, f64) {
         // Roll (x-axis rotation)
@@ -114,45 +252,560 @@ impl Quaternion {
         (roll, pitch, yaw)
     }
 
-    /// Spherical linear interpolation between two quaternions
+    /// Like [`Quaternion::to_euler`], plus a flag that is set near gimbal lock
+    /// (`|sin(pitch)| > GIMBAL_LOCK_SIN_PITCH`).
+    ///
+    /// At ±90° pitch only `yaw - roll` (or `yaw + roll` at -90°) is
+    /// determined, so near the singularity roll is reported as 0 and the
+    /// whole rotation about the vertical is folded into yaw, in `[-π, π]`.
+    /// Angles follow the `to_euler` convention: the rotation is yaw about z,
+    /// then pitch about y, then roll about x.
+    pub fn to_euler_checked(&self) -> (f64, f64, f64, bool) {
+        let q = self.normalize().canonical();
+        let sinp = 2.0 * (q.w * q.y - q.z * q.x);
+        if sinp.abs() <= GIMBAL_LOCK_SIN_PITCH {
+            let (roll, pitch, yaw) = q.to_euler();
+            return (roll, pitch, yaw, false);
+        }
+
+        let pitch = sinp.clamp(-1.0, 1.0).asin();
+        let yaw = -2.0 * q.x.atan2(q.w) * sinp.signum();
+        (0.0, pitch, yaw, true)
+    }
+
+    /// Spherical linear interpolation between two quaternions.
+    ///
+    /// Use a [`SlerpInterpolator`] to sample many `t` between the same pair.
     pub fn slerp(&self, other: &Self, t: f64) -> Self {
-        let mut dot = self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z;
+        SlerpInterpolator::new(*self, *other).at(t)
+    }
+
+    /// [`slerp`](Self::slerp) with `t` first mapped through `easing`
+    pub fn slerp_eased(&self, other: &Self, t: f64, easing: Easing) -> Self {
+        self.slerp(other, easing.apply(t))
+    }
+
+    /// `steps + 1` unit rotors evenly spaced along the slerp arc from `self`
+    /// to `other`, both ends included. With `steps == 0` only the two
+    /// endpoints are returned.
+    ///
+    /// Like [`Quaternion::slerp`], the path takes the shorter arc, so the last
+    /// rotor is `-other` (the same rotation) when the inputs' dot product is
+    /// negative, whatever the number of steps.
+    pub fn slerp_path(&self, other: &Self, steps: usize) -> Vec<Self> {
+        let path = SlerpInterpolator::new(self.normalize(), other.normalize());
+        if steps == 0 {
+            return vec![path.at(0.0).normalize(), path.at(1.0).normalize()];
+        }
+
+        (0..=steps)
+            .map(|i| path.at(i as f64 / steps as f64).normalize())
+            .collect()
+    }
+
+    /// Normalized linear interpolation along the shorter arc.
+    ///
+    /// Cheaper than [`Quaternion::slerp`] and shares its endpoints, but does not
+    /// move at constant angular velocity.
+    pub fn nlerp(&self, other: &Self, t: f64) -> Self {
+        let dot = self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z;
+        let sign = if dot < 0.0 { -1.0 } else { 1.0 };
+
+        Quaternion {
+            w: self.w + t * (sign * other.w - self.w),
+            x: self.x + t * (sign * other.x - self.x),
+            y: self.y + t * (sign * other.y - self.y),
+            z: self.z + t * (sign * other.z - self.z),
+        }
+        .normalize()
+    }
+
+    /// Advance this orientation by a world-frame angular velocity (rad/s)
+    /// over `dt` seconds: `exp(0.5 * omega * dt) * self`, renormalized.
+    pub fn integrate(&self, angular_velocity: [f64; 3], dt: f64) -> Self {
+        let half = 0.5 * dt;
+        let [wx, wy, wz] = angular_velocity;
+        Self::exp_pure([wx * half, wy * half, wz * half])
+            .multiply(self)
+            .normalize()
+    }
+
+    /// Karcher (intrinsic) mean of a set of rotations, or `None` for an
+    /// empty slice.
+    ///
+    /// Starting from the first sample, each iteration maps every sample into
+    /// the tangent space at the current estimate with `ln`, averages there
+    /// and maps back with `exp`, until the step is negligible or
+    /// [`KARCHER_MAX_ITERATIONS`] is reached. Signs are canonicalized against
+    /// the estimate, so `q` and `-q` count as the same sample.
+    pub fn average(quats: &[Quaternion]) -> Option<Quaternion> {
+        let mut mean = quats.first()?.normalize().canonical();
+
+        for _ in 0..KARCHER_MAX_ITERATIONS {
+            let mut step = [0.0; 3];
+            for q in quats {
+                let mut delta = mean.conjugate().multiply(&q.normalize());
+                if delta.w < 0.0 {
+                    delta = Quaternion::new(-delta.w, -delta.x, -delta.y, -delta.z);
+                }
+                let [x, y, z] = delta.ln_unit();
+                step[0] += x;
+                step[1] += y;
+                step[2] += z;
+            }
+            let n = quats.len() as f64;
+            let step = step.map(|c| c / n);
+
+            mean = mean.multiply(&Self::exp_pure(step)).normalize();
+            if step.iter().map(|c| c * c).sum::<f64>().sqrt() < KARCHER_TOLERANCE {
+                break;
+            }
+        }
+
+        Some(mean.canonical())
+    }
+
+    /// Rotation vector (axis scaled by angle, in radians) of this rotation,
+    /// with the angle in `[0, π]`; the identity maps to zero. This is
+    /// `2 * ln(q)` taken on the `w >= 0` half of the double cover, so `q`
+    /// and `-q` give the same vector.
+    pub fn to_rotation_vector(&self) -> [f64; 3] {
+        let mut q = self.normalize();
+        if q.w < 0.0 {
+            q = Self::new(-q.w, -q.x, -q.y, -q.z);
+        }
+        q.ln_unit().map(|c| 2.0 * c)
+    }
+
+    /// Unit quaternion rotating by `|v|` radians about `v`, the inverse of
+    /// [`to_rotation_vector`](Self::to_rotation_vector): `exp(v / 2)`.
+    pub fn from_rotation_vector(v: [f64; 3]) -> Self {
+        Self::exp_pure(v.map(|c| 0.5 * c))
+    }
+
+    /// Vector part of the logarithm of a unit quaternion, the inverse of
+    /// [`exp_pure`](Self::exp_pure).
+    fn ln_unit(&self) -> [f64; 3] {
+        let sin_angle = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        if sin_angle < 1e-12 {
+            // angle / sin(angle) -> 1
+            return [self.x, self.y, self.z];
+        }
+
+        let s = sin_angle.atan2(self.w) / sin_angle;
+        [self.x * s, self.y * s, self.z * s]
+    }
+
+    /// Exponential of the pure quaternion `(0, v)`.
+    fn exp_pure(v: [f64; 3]) -> Self {
+        let [x, y, z] = v;
+        let angle = (x * x + y * y + z * z).sqrt();
+        if angle < 1e-12 {
+            // sin(a) / a -> 1, so use the first-order series
+            return Self::new(1.0, x, y, z).normalize();
+        }
+
+        let s = angle.sin() / angle;
+        Self::new(angle.cos(), x * s, y * s, z * s)
+    }
+}
+
+/// Scalar-first `[w, x, y, z]`, as [`Quaternion::to_array`].
+impl From<Quaternion> for [f64; 4] {
+    fn from(q: Quaternion) -> Self {
+        q.to_array()
+    }
+}
+
+/// Reads scalar-first `[w, x, y, z]`; any other length is an
+/// `Error::InvalidParameter`.
+impl TryFrom<&[f64]> for Quaternion {
+    type Error = Error;
+
+    fn try_from(components: &[f64]) -> Result<Self> {
+        let array: [f64; 4] = components.try_into().map_err(|_| {
+            Error::InvalidParameter(
+                "quaternion".into(),
+                format!(
+                    "expected 4 components (w, x, y, z), got {}",
+                    components.len()
+                ),
+            )
+        })?;
+        Ok(Self::from_array(array))
+    }
+}
+
+/// Wire form of [`Quaternion`], checked by its `TryFrom` before use
+#[derive(Deserialize)]
+pub struct QuaternionComponents {
+    w: f64,
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+/// Deserialization goes through here, so NaN or infinite components are
+/// rejected at the boundary instead of turning metrics into NaN later.
+impl TryFrom<QuaternionComponents> for Quaternion {
+    type Error = Error;
+
+    fn try_from(QuaternionComponents { w, x, y, z }: QuaternionComponents) -> Result<Self> {
+        for (name, value) in [("w", w), ("x", x), ("y", y), ("z", z)] {
+            if !value.is_finite() {
+                return Err(Error::InvalidParameter(
+                    "quaternion".into(),
+                    format!("component {} must be finite, got {}", name, value),
+                ));
+            }
+        }
+
+        let q = Self::new(w, x, y, z);
+        if q.norm() < 1e-10 {
+            warn!("Deserialized a zero quaternion; it will normalize to the identity");
+        }
+        Ok(q)
+    }
+}
+
+#[cfg(feature = "nalgebra")]
+impl From<nalgebra::UnitQuaternion<f64>> for Quaternion {
+    fn from(q: nalgebra::UnitQuaternion<f64>) -> Self {
+        Self::new(q.w, q.i, q.j, q.k)
+    }
+}
+
+/// Normalizes, since nalgebra's unit quaternion assumes unit length.
+#[cfg(feature = "nalgebra")]
+impl From<Quaternion> for nalgebra::UnitQuaternion<f64> {
+    fn from(q: Quaternion) -> Self {
+        nalgebra::UnitQuaternion::from_quaternion(nalgebra::Quaternion::new(q.w, q.x, q.y, q.z))
+    }
+}
+
+/// Timing curve applied to an interpolation parameter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Easing {
+    #[default]
+    Linear,
+    /// Slow start and finish (smoothstep)
+    EaseInOut,
+    /// Slow start (quadratic)
+    EaseIn,
+    /// Slow finish (quadratic)
+    EaseOut,
+}
+
+impl Easing {
+    /// Every curve, in declaration order
+    pub const ALL: [Easing; 4] = [
+        Easing::Linear,
+        Easing::EaseInOut,
+        Easing::EaseIn,
+        Easing::EaseOut,
+    ];
+
+    /// Map `t` in `[0, 1]` onto the curve; every curve fixes 0 and 1.
+    pub fn apply(self, t: f64) -> f64 {
+        match self {
+            Easing::Linear => t,
+            Easing::EaseInOut => t * t * (3.0 - 2.0 * t),
+            Easing::EaseIn => t * t,
+            Easing::EaseOut => t * (2.0 - t),
+        }
+    }
+}
+
+/// Slerp between fixed endpoints with the per-pair work done once.
+///
+/// Takes the shorter arc, and near-parallel endpoints fall back to normalized
+/// linear interpolation, exactly as [`Quaternion::slerp`], which delegates
+/// here.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlerpInterpolator {
+    from: Quaternion,
+    /// `to`, negated when that makes the arc shorter
+    to: Quaternion,
+    dot: f64,
+    /// `(theta_0, sin(theta_0))`, or `None` when interpolating linearly
+    arc: Option<(f64, f64)>,
+}
+
+impl SlerpInterpolator {
+    /// Dot product above which the endpoints are treated as parallel
+    const DOT_THRESHOLD: f64 = 0.9995;
+
+    pub fn new(from: Quaternion, to: Quaternion) -> Self {
+        let mut dot = from.w * to.w + from.x * to.x + from.y * to.y + from.z * to.z;
 
         // If the dot product is negative, the quaternions have opposite handedness
         // and slerp won't take the shorter path. Fix by reversing one quaternion.
-        let mut other = *other;
+        let mut to = to;
         if dot < 0.0 {
-            other = Quaternion::new(-other.w, -other.x, -other.y, -other.z);
+            to = Quaternion::new(-to.w, -to.x, -to.y, -to.z);
             dot = -dot;
         }
 
-        const DOT_THRESHOLD: f64 = 0.9995;
-        if dot > DOT_THRESHOLD {
+        // Since dot is in range [0, DOT_THRESHOLD] on the arc path, acos is safe
+        let arc = (dot <= Self::DOT_THRESHOLD).then(|| {
+            let theta_0 = dot.acos();
+            (theta_0, theta_0.sin())
+        });
+
+        Self { from, to, dot, arc }
+    }
+
+    /// The rotor a fraction `t` of the way from `from` to `to`
+    pub fn at(&self, t: f64) -> Quaternion {
+        let (from, to) = (self.from, self.to);
+        let Some((theta_0, sin_theta_0)) = self.arc else {
             // If the inputs are too close, linearly interpolate and normalize
-            let result = Quaternion {
-                w: self.w + t * (other.w - self.w),
-                x: self.x + t * (other.x - self.x),
-                y: self.y + t * (other.y - self.y),
-                z: self.z + t * (other.z - self.z),
-            };
-            return result.normalize();
-        }
+            return Quaternion {
+                w: from.w + t * (to.w - from.w),
+                x: from.x + t * (to.x - from.x),
+                y: from.y + t * (to.y - from.y),
+                z: from.z + t * (to.z - from.z),
+            }
+            .normalize();
+        };
 
-        // Since dot is in range [0, DOT_THRESHOLD], acos is safe
-        let theta_0 = dot.acos();
         let theta = theta_0 * t;
         let sin_theta = theta.sin();
-        let sin_theta_0 = theta_0.sin();
-
-        let s1 = (theta_0 - theta).cos() - dot * sin_theta / sin_theta_0;
+        let s1 = theta.cos() - self.dot * sin_theta / sin_theta_0;
         let s2 = sin_theta / sin_theta_0;
 
         Quaternion {
-            w: self.w * s1 + other.w * s2,
-            x: self.x * s1 + other.x * s2,
-            y: self.y * s1 + other.y * s2,
-            z: self.z * s1 + other.z * s2,
+            w: from.w * s1 + to.w * s2,
+            x: from.x * s1 + to.x * s2,
+            y: from.y * s1 + to.y * s2,
+            z: from.z * s1 + to.z * s2,
+        }
+    }
+}
+
+/// Iteration cap for [`Quaternion::average`]
+pub const KARCHER_MAX_ITERATIONS: usize = 32;
+
+/// Tangent-space step length below which [`Quaternion::average`] has converged
+const KARCHER_TOLERANCE: f64 = 1e-12;
+
+/// Exponential moving average of rotor states on SO(3).
+///
+/// Each measurement pulls the state `alpha` of the way towards it along the
+/// shorter slerp arc: `alpha` near 0 smooths heavily, `alpha == 1` tracks the
+/// measurements exactly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuaternionSmoother {
+    state: Quaternion,
+    alpha: f64,
+}
+
+impl QuaternionSmoother {
+    /// Start from `initial`; `alpha` must lie in `[0, 1]`.
+    pub fn new(initial: Quaternion, alpha: f64) -> Result<Self> {
+        if !(0.0..=1.0).contains(&alpha) {
+            return Err(Error::InvalidParameter(
+                "alpha".into(),
+                format!("must be within [0, 1], got {}", alpha),
+            ));
+        }
+        Ok(Self {
+            state: initial.normalize(),
+            alpha,
+        })
+    }
+
+    /// Blend `measurement` into the state and return the new state.
+    pub fn update(&mut self, measurement: Quaternion) -> Quaternion {
+        self.state = if self.alpha == 1.0 {
+            measurement
+        } else {
+            self.state
+                .slerp(&measurement.normalize(), self.alpha)
+                .normalize()
+        };
+        self.state
+    }
+
+    pub fn state(&self) -> Quaternion {
+        self.state
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+}
+
+/// Bytes before the first sample in [`OrientationLog::to_bytes`]: capacity
+/// and length as little-endian `u32`s
+const ORIENTATION_LOG_HEADER_BYTES: usize = 8;
+/// The first sample, as four little-endian `f64`s
+const ORIENTATION_LOG_KEY_BYTES: usize = 32;
+/// Each later sample: the vector part of its delta, as three `f32`s
+const ORIENTATION_LOG_DELTA_BYTES: usize = 12;
+
+/// Rolling buffer of the most recent `capacity` orientations.
+///
+/// Samples are stored normalized and canonical. [`to_bytes`](Self::to_bytes)
+/// delta-encodes them: the first sample in full, then each sample as the
+/// relative rotation `prev.conjugate() * cur` from the one before. Only the
+/// vector part of a delta is kept, in single precision, since `w` follows
+/// from it; deltas are taken against the decoded previous sample so rounding
+/// does not accumulate along the sequence. A smooth trajectory encodes in
+/// well under half its raw size.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrientationLog {
+    samples: VecDeque<Quaternion>,
+    capacity: usize,
+}
+
+impl OrientationLog {
+    /// An empty log keeping at most `capacity` samples; `capacity` must be
+    /// positive and fit in a `u32`.
+    pub fn new(capacity: usize) -> Result<Self> {
+        if capacity == 0 || u32::try_from(capacity).is_err() {
+            return Err(Error::InvalidParameter(
+                "capacity".into(),
+                format!("must be within [1, {}], got {}", u32::MAX, capacity),
+            ));
+        }
+        // Grown on demand: `capacity` may come from an untrusted header
+        Ok(Self {
+            samples: VecDeque::new(),
+            capacity,
+        })
+    }
+
+    /// Append an orientation, dropping the oldest once the log is full.
+    pub fn push(&mut self, orientation: Quaternion) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(orientation.normalize().canonical());
+    }
+
+    /// Samples, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &Quaternion> {
+        self.samples.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Delta-encoded form; see the type docs. Decoded samples differ from
+    /// the stored ones by single-precision rounding of each delta.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(
+            ORIENTATION_LOG_HEADER_BYTES
+                + ORIENTATION_LOG_KEY_BYTES
+                + self.len().saturating_sub(1) * ORIENTATION_LOG_DELTA_BYTES,
+        );
+        // Both fit in a u32, as checked by `new` and `from_bytes`
+        bytes.extend_from_slice(&(self.capacity as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.len() as u32).to_le_bytes());
+
+        let mut samples = self.samples.iter();
+        let Some(first) = samples.next() else {
+            return bytes;
+        };
+        for component in [first.w, first.x, first.y, first.z] {
+            bytes.extend_from_slice(&component.to_le_bytes());
+        }
+
+        let mut decoded = *first;
+        for sample in samples {
+            let delta = decoded.conjugate().multiply(sample).normalize().canonical();
+            let packed = [delta.x as f32, delta.y as f32, delta.z as f32];
+            for component in packed {
+                bytes.extend_from_slice(&component.to_le_bytes());
+            }
+            decoded = Self::apply_delta(decoded, packed);
+        }
+        bytes
+    }
+
+    /// Decode the output of [`to_bytes`](Self::to_bytes).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let invalid = |message: String| Error::InvalidParameter("orientation log".into(), message);
+
+        let header = bytes
+            .get(..ORIENTATION_LOG_HEADER_BYTES)
+            .ok_or_else(|| invalid("truncated header".into()))?;
+        let read_u32 = |at: usize| u32::from_le_bytes(header[at..at + 4].try_into().unwrap());
+        let capacity = read_u32(0) as usize;
+        let len = read_u32(4) as usize;
+
+        let expected = match len {
+            0 => Some(ORIENTATION_LOG_HEADER_BYTES),
+            _ => (len - 1)
+                .checked_mul(ORIENTATION_LOG_DELTA_BYTES)
+                .and_then(|deltas| {
+                    deltas.checked_add(ORIENTATION_LOG_HEADER_BYTES + ORIENTATION_LOG_KEY_BYTES)
+                }),
+        };
+        if expected != Some(bytes.len()) {
+            return Err(invalid(format!(
+                "{} bytes do not hold {} samples",
+                bytes.len(),
+                len
+            )));
+        }
+        if len > capacity {
+            return Err(invalid(format!(
+                "{} samples exceed the capacity of {}",
+                len, capacity
+            )));
+        }
+
+        // `len` is bounded by the input size checked above; `capacity` is not
+        let mut log = Self::new(capacity)?;
+        log.samples.reserve_exact(len);
+        if len == 0 {
+            return Ok(log);
+        }
+
+        let body = &bytes[ORIENTATION_LOG_HEADER_BYTES..];
+        let key: Vec<f64> = body[..ORIENTATION_LOG_KEY_BYTES]
+            .chunks_exact(8)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        if key.iter().any(|c| !c.is_finite()) {
+            return Err(invalid("non-finite key".into()));
+        }
+        let first = Quaternion::try_from(key.as_slice())?;
+        let mut decoded = first.normalize().canonical();
+        log.samples.push_back(decoded);
+
+        for delta in body[ORIENTATION_LOG_KEY_BYTES..].chunks_exact(ORIENTATION_LOG_DELTA_BYTES) {
+            let mut packed = [0.0f32; 3];
+            for (component, chunk) in packed.iter_mut().zip(delta.chunks_exact(4)) {
+                *component = f32::from_le_bytes(chunk.try_into().unwrap());
+            }
+            if packed.iter().any(|c| !c.is_finite()) {
+                return Err(invalid("non-finite delta".into()));
+            }
+            decoded = Self::apply_delta(decoded, packed);
+            log.samples.push_back(decoded);
         }
+        Ok(log)
+    }
+
+    /// `prev * delta`, with the delta's `w` recovered from its vector part
+    fn apply_delta(prev: Quaternion, packed: [f32; 3]) -> Quaternion {
+        let [x, y, z] = packed.map(f64::from);
+        let w = (1.0 - (x * x + y * y + z * z)).max(0.0).sqrt();
+        prev.multiply(&Quaternion::new(w, x, y, z))
+            .normalize()
+            .canonical()
     }
 }
 
@@ -171,6 +824,403 @@ mod tests {
         assert_eq!(q.z, 0.0);
     }
 
+    #[test]
+    fn test_orientation_log_round_trips_and_compresses() {
+        let mut log = OrientationLog::new(500).unwrap();
+        // A smooth tumble, longer than the log so the oldest samples roll off
+        for i in 0..600 {
+            let t = i as f64 * 0.01;
+            log.push(Quaternion::from_axis_angle(
+                [t.cos(), t.sin(), 0.5],
+                3.0 * t,
+            ));
+        }
+        assert_eq!(log.len(), 500);
+
+        let bytes = log.to_bytes();
+        let raw = log.len() * 4 * std::mem::size_of::<f64>();
+        assert!(
+            bytes.len() * 2 < raw,
+            "{} vs {} raw bytes",
+            bytes.len(),
+            raw
+        );
+
+        let decoded = OrientationLog::from_bytes(&bytes).unwrap();
+        assert_eq!(decoded.len(), log.len());
+        assert_eq!(decoded.capacity(), log.capacity());
+        for (a, b) in log.iter().zip(decoded.iter()) {
+            for (x, y) in a.to_array().into_iter().zip(b.to_array()) {
+                assert!((x - y).abs() < 1e-6, "{a:?} vs {b:?}");
+            }
+        }
+
+        assert!(OrientationLog::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+        let empty = OrientationLog::new(4).unwrap();
+        assert_eq!(
+            OrientationLog::from_bytes(&empty.to_bytes()).unwrap(),
+            empty
+        );
+    }
+
+    #[test]
+    fn test_orientation_log_hostile_header_does_not_preallocate() {
+        // Claims the largest capacity but holds nothing
+        let log = OrientationLog::from_bytes(&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0]).unwrap();
+        assert_eq!(log.capacity(), u32::MAX as usize);
+        assert!(log.is_empty());
+
+        // Claims the largest length with no samples behind it
+        assert!(OrientationLog::from_bytes(&[0xff; 8]).is_err());
+
+        // A well-formed buffer whose key is not a number
+        let mut log = OrientationLog::new(4).unwrap();
+        log.push(Quaternion::identity());
+        log.push(Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.1));
+        for bad in [f64::NAN, f64::INFINITY] {
+            let mut bytes = log.to_bytes();
+            bytes[ORIENTATION_LOG_HEADER_BYTES..ORIENTATION_LOG_HEADER_BYTES + 8]
+                .copy_from_slice(&bad.to_le_bytes());
+            assert!(matches!(
+                OrientationLog::from_bytes(&bytes),
+                Err(Error::InvalidParameter(_, message)) if message == "non-finite key"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_rotation_vector_round_trips_across_magnitudes() {
+        let axes: [[f64; 3]; 3] = [[1.0, 0.0, 0.0], [0.0, -1.0, 0.0], [1.0, 2.0, -2.0]];
+        let magnitudes = [
+            0.0,
+            1e-15,
+            1e-12,
+            1e-9,
+            1e-6,
+            1e-3,
+            0.1,
+            1.0,
+            2.5,
+            PI - 1e-6,
+        ];
+        for axis in axes {
+            let norm = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+            for magnitude in magnitudes {
+                let v = axis.map(|c| c / norm * magnitude);
+                let q = Quaternion::from_rotation_vector(v);
+                assert!((q.norm() - 1.0).abs() < 1e-15);
+                let expected = Quaternion::from_axis_angle(axis, magnitude);
+                assert_relative_eq!(q.w, expected.w, epsilon = 1e-15);
+                assert_relative_eq!(q.x, expected.x, epsilon = 1e-15);
+                assert_relative_eq!(q.y, expected.y, epsilon = 1e-15);
+                assert_relative_eq!(q.z, expected.z, epsilon = 1e-15);
+
+                let back = q.to_rotation_vector();
+                for (a, b) in back.iter().zip(&v) {
+                    // Relative to the angle, so tiny rotations keep their precision
+                    assert!((a - b).abs() <= 1e-12 * magnitude, "{v:?} -> {back:?}");
+                }
+            }
+        }
+
+        // -q is the same rotation and gives the same vector
+        let q = Quaternion::from_axis_angle([0.3, -0.4, 0.5], 2.2);
+        let flipped = Quaternion::new(-q.w, -q.x, -q.y, -q.z);
+        assert_eq!(flipped.to_rotation_vector(), q.to_rotation_vector());
+        assert_eq!(Quaternion::identity().to_rotation_vector(), [0.0; 3]);
+    }
+
+    #[test]
+    fn test_basis_vectors_are_orthonormal_matrix_columns() {
+        let (x, y, z) = Quaternion::identity().basis_vectors();
+        assert_eq!(
+            (x, y, z),
+            ([1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0])
+        );
+
+        let dot = |a: [f64; 3], b: [f64; 3]| a[0] * b[0] + a[1] * b[1] + a[2] * b[2];
+        for (axis, angle) in [
+            ([1.0, -2.0, 0.5], 1.1),
+            ([0.0, 3.0, 4.0], -2.7),
+            ([1.0, 1.0, 1.0], PI),
+        ] {
+            let q = Quaternion::from_axis_angle(axis, angle);
+            let (x, y, z) = q.basis_vectors();
+            for (a, b, expected) in [
+                (x, x, 1.0),
+                (y, y, 1.0),
+                (z, z, 1.0),
+                (x, y, 0.0),
+                (y, z, 0.0),
+                (z, x, 0.0),
+            ] {
+                assert!((dot(a, b) - expected).abs() < 1e-12);
+            }
+
+            let m = q.to_rotation_matrix();
+            for (col, axis) in [x, y, z].into_iter().enumerate() {
+                for row in 0..3 {
+                    assert!((axis[row] - m[row][col]).abs() < 1e-15);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotation_matrix_matches_rotate_vector() {
+        let q = Quaternion::from_axis_angle([1.0, -2.0, 0.5], 1.1);
+        let m = q.to_rotation_matrix();
+        let v = [0.3, -1.2, 2.0];
+
+        let rotated = q.rotate_vector(v);
+        for (row, expected) in m.iter().zip(rotated) {
+            let product: f64 = row.iter().zip(v).map(|(a, b)| a * b).sum();
+            assert_relative_eq!(product, expected, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_to_homogeneous_is_column_major() {
+        let q = Quaternion::from_axis_angle([0.0, 1.0, 1.0], -0.7);
+        let r = q.to_rotation_matrix();
+        let m = q.to_homogeneous([4.0, -5.0, 6.0]);
+
+        for row in 0..3 {
+            for col in 0..3 {
+                assert_eq!(m[col][row], r[row][col]);
+            }
+        }
+        assert_eq!(m[3], [4.0, -5.0, 6.0, 1.0]);
+        assert_eq!([m[0][3], m[1][3], m[2][3], m[3][3]], [0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_quaternion_average() {
+        assert_eq!(Quaternion::average(&[]), None);
+
+        let q = Quaternion::from_axis_angle([1.0, 2.0, -1.0], 0.9);
+        let single = Quaternion::average(&[q]).unwrap();
+        assert_relative_eq!(single.w, q.w, epsilon = 1e-12);
+        assert_relative_eq!(single.x, q.x, epsilon = 1e-12);
+        assert_relative_eq!(single.y, q.y, epsilon = 1e-12);
+        assert_relative_eq!(single.z, q.z, epsilon = 1e-12);
+
+        // The mean of two rotations is the midpoint of the arc between them,
+        // whichever sign the samples come in with
+        let a = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.1);
+        let b = Quaternion::from_axis_angle([0.0, 1.0, 1.0], 0.3);
+        let flipped_b = Quaternion::new(-b.w, -b.x, -b.y, -b.z);
+        let mean = Quaternion::average(&[a, flipped_b]).unwrap();
+        let midpoint = a.slerp(&b, 0.5);
+        assert_relative_eq!(mean.w, midpoint.w, epsilon = 1e-9);
+        assert_relative_eq!(mean.x, midpoint.x, epsilon = 1e-9);
+        assert_relative_eq!(mean.y, midpoint.y, epsilon = 1e-9);
+        assert_relative_eq!(mean.z, midpoint.z, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn test_slerp_eased() {
+        let a = Quaternion::from_axis_angle([1.0, 0.0, 0.0], 0.2);
+        let b = Quaternion::from_axis_angle([0.0, 1.0, 0.0], 1.4);
+
+        for t in [0.0, 0.1, 0.35, 0.5, 0.8, 1.0] {
+            assert_eq!(a.slerp_eased(&b, t, Easing::Linear), a.slerp(&b, t));
+        }
+        for easing in Easing::ALL {
+            assert_eq!(a.slerp_eased(&b, 0.0, easing), a.slerp(&b, 0.0));
+            assert_eq!(a.slerp_eased(&b, 1.0, easing), a.slerp(&b, 1.0));
+        }
+        assert_eq!(a.slerp_eased(&b, 0.5, Easing::EaseInOut), a.slerp(&b, 0.5));
+
+        // Easing in lags behind linear early on; easing out leads
+        let angle_from_a = |q: Quaternion| a.conjugate().multiply(&q).to_axis_angle().1;
+        let linear = angle_from_a(a.slerp(&b, 0.25));
+        assert!(angle_from_a(a.slerp_eased(&b, 0.25, Easing::EaseIn)) < linear);
+        assert!(angle_from_a(a.slerp_eased(&b, 0.25, Easing::EaseOut)) > linear);
+    }
+
+    #[test]
+    fn test_quaternion_deserialization_rejects_non_finite() {
+        let q = Quaternion::from_axis_angle([0.0, 1.0, 0.0], 0.7);
+        let json = serde_json::to_string(&q).unwrap();
+        assert_eq!(serde_json::from_str::<Quaternion>(&json).unwrap(), q);
+
+        // JSON has no NaN literal, but other formats can still produce one
+        let fields = [("w", 1.0), ("x", f64::NAN), ("y", 0.0), ("z", 0.0)];
+        let nan = serde::de::value::MapDeserializer::<_, serde::de::value::Error>::new(
+            fields.into_iter(),
+        );
+        let err = Quaternion::deserialize(nan).unwrap_err();
+        assert!(
+            err.to_string().contains("component x must be finite"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_quaternion_canonical() {
+        let q = Quaternion::from_axis_angle([0.3, -0.8, 0.5], 2.4);
+        let negated = Quaternion::new(-q.w, -q.x, -q.y, -q.z);
+        assert_eq!(q.canonical(), negated.canonical());
+        assert!(q.canonical().w >= 0.0);
+
+        let v = [1.0, -2.0, 0.5];
+        let expected = q.rotate_vector(v);
+        let [x, y, z] = negated.canonical().rotate_vector(v);
+        assert_relative_eq!(x, expected[0], epsilon = 1e-12);
+        assert_relative_eq!(y, expected[1], epsilon = 1e-12);
+        assert_relative_eq!(z, expected[2], epsilon = 1e-12);
+
+        // A half-turn has w == 0, so the first nonzero component decides
+        let half_turn = Quaternion::new(0.0, 0.0, -0.6, 0.8);
+        assert_eq!(half_turn.canonical(), Quaternion::new(0.0, 0.0, 0.6, -0.8));
+        assert_eq!(
+            Quaternion::new(0.0, 0.0, 0.6, -0.8).canonical(),
+            half_turn.canonical()
+        );
+    }
+
+    #[test]
+    fn test_smoother_converges_to_constant_input() {
+        let target = Quaternion::from_axis_angle([0.0, 1.0, 1.0], 2.0);
+        let mut smoother = QuaternionSmoother::new(Quaternion::identity(), 0.2).unwrap();
+
+        let first = smoother.update(target);
+        // One step only covers part of the way
+        assert!((first.w - target.w).abs() > 0.1);
+        for _ in 0..200 {
+            smoother.update(target);
+        }
+        let state = smoother.state();
+        assert_relative_eq!(state.w, target.w, epsilon = 1e-9);
+        assert_relative_eq!(state.x, target.x, epsilon = 1e-9);
+        assert_relative_eq!(state.y, target.y, epsilon = 1e-9);
+        assert_relative_eq!(state.z, target.z, epsilon = 1e-9);
+
+        assert!(QuaternionSmoother::new(target, 1.5).is_err());
+        assert!(QuaternionSmoother::new(target, f64::NAN).is_err());
+    }
+
+    #[test]
+    fn test_smoother_alpha_one_passes_measurements_through() {
+        let mut smoother = QuaternionSmoother::new(Quaternion::identity(), 1.0).unwrap();
+        for angle in [0.3, -2.5, 3.1] {
+            let measurement = Quaternion::from_axis_angle([1.0, 0.0, 0.0], angle);
+            assert_eq!(smoother.update(measurement), measurement);
+        }
+        // Even one on the far side of the double cover
+        let flipped = Quaternion::new(-0.6, 0.0, 0.8, 0.0);
+        assert_eq!(smoother.update(flipped), flipped);
+    }
+
+    #[test]
+    fn test_to_euler_checked_at_gimbal_lock() {
+        let from_euler = |roll: f64, pitch: f64, yaw: f64| {
+            Quaternion::from_axis_angle([0.0, 0.0, 1.0], yaw)
+                .multiply(&Quaternion::from_axis_angle([0.0, 1.0, 0.0], pitch))
+                .multiply(&Quaternion::from_axis_angle([1.0, 0.0, 0.0], roll))
+        };
+        let probes = [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.3, -0.4, 0.9]];
+
+        for pitch in [FRAC_PI_2, -FRAC_PI_2] {
+            let q = from_euler(0.3, pitch, 0.5);
+            let (roll, checked_pitch, yaw, locked) = q.to_euler_checked();
+            assert!(locked);
+            assert_eq!(roll, 0.0);
+            assert_relative_eq!(checked_pitch, pitch, epsilon = 1e-6);
+
+            let rebuilt = from_euler(roll, checked_pitch, yaw);
+            for v in probes {
+                let [x, y, z] = rebuilt.rotate_vector(v);
+                let expected = q.rotate_vector(v);
+                assert_relative_eq!(x, expected[0], epsilon = 1e-9);
+                assert_relative_eq!(y, expected[1], epsilon = 1e-9);
+                assert_relative_eq!(z, expected[2], epsilon = 1e-9);
+            }
+        }
+
+        // Away from the poles all three angles are recovered
+        let q = from_euler(0.3, 0.4, 0.5);
+        let (roll, pitch, yaw, locked) = q.to_euler_checked();
+        assert!(!locked);
+        assert_relative_eq!(roll, 0.3, epsilon = 1e-12);
+        assert_relative_eq!(pitch, 0.4, epsilon = 1e-12);
+        assert_relative_eq!(yaw, 0.5, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_slerp_interpolator_matches_slerp() {
+        let from = Quaternion::from_axis_angle([1.0, 0.0, 0.0], 0.4);
+        let pairs = [
+            (from, Quaternion::from_axis_angle([0.0, 1.0, 1.0], 2.2)),
+            // Opposite handedness: both must take the shorter arc
+            (from, Quaternion::new(-0.5, 0.5, -0.5, 0.5)),
+            // Close enough to fall back to nlerp
+            (from, Quaternion::from_axis_angle([1.0, 0.0, 0.01], 0.41)),
+            (from, from),
+        ];
+
+        for (a, b) in pairs {
+            let interpolator = SlerpInterpolator::new(a, b);
+            for i in 0..=100 {
+                let t = i as f64 / 100.0;
+                let (expected, actual) = (a.slerp(&b, t), interpolator.at(t));
+                assert_relative_eq!(actual.w, expected.w, epsilon = 1e-15);
+                assert_relative_eq!(actual.x, expected.x, epsilon = 1e-15);
+                assert_relative_eq!(actual.y, expected.y, epsilon = 1e-15);
+                assert_relative_eq!(actual.z, expected.z, epsilon = 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn test_quaternion_array_orderings() {
+        let q = Quaternion::new(0.5, -1.0, 2.0, 3.5);
+        assert_eq!(q.to_array(), [0.5, -1.0, 2.0, 3.5]);
+        assert_eq!(Quaternion::from_array(q.to_array()), q);
+        assert_eq!(q.to_xyzw(), [-1.0, 2.0, 3.5, 0.5]);
+        assert_eq!(Quaternion::from_xyzw(q.to_xyzw()), q);
+
+        let buffer = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(
+            Quaternion::from_wxyz(buffer),
+            Quaternion::new(1.0, 2.0, 3.0, 4.0)
+        );
+        assert_eq!(
+            Quaternion::from_xyzw(buffer),
+            Quaternion::new(4.0, 1.0, 2.0, 3.0)
+        );
+    }
+
+    #[test]
+    fn test_quaternion_slice_conversions() {
+        let q = Quaternion::new(0.5, -1.0, 2.0, 3.5);
+        let array: [f64; 4] = q.into();
+        assert_eq!(array, [0.5, -1.0, 2.0, 3.5]);
+        assert_eq!(Quaternion::try_from(&array[..]).unwrap(), q);
+
+        for len in [0, 3, 5] {
+            let components = vec![1.0; len];
+            match Quaternion::try_from(components.as_slice()) {
+                Err(Error::InvalidParameter(_, message)) => {
+                    assert!(message.ends_with(&format!("got {}", len)))
+                }
+                other => panic!("expected InvalidParameter, got {:?}", other),
+            }
+        }
+    }
+
+    #[cfg(feature = "nalgebra")]
+    #[test]
+    fn test_quaternion_nalgebra_round_trip() {
+        let q = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.7);
+        let unit: nalgebra::UnitQuaternion<f64> = q.into();
+        assert_relative_eq!(unit.angle(), 0.7, epsilon = 1e-12);
+        let back = Quaternion::from(unit);
+        assert_relative_eq!(back.w, q.w, epsilon = 1e-12);
+        assert_relative_eq!(back.z, q.z, epsilon = 1e-12);
+    }
+
     #[test]
     fn test_quaternion_multiplication() {
         let q1 = Quaternion::new(1.0, 2.0, 3.0, 4.0);
@@ -217,4 +1267,178 @@ mod tests {
         assert_relative_eq!(q_mid.y, expected.y, epsilon = 1e-10);
         assert_relative_eq!(q_mid.z, expected.z, epsilon = 1e-10);
     }
+
+    #[test]
+    fn test_quaternion_slerp_endpoints() {
+        let q1 = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.3);
+        let q2 = Quaternion::from_axis_angle([0.0, 1.0, 0.0], 1.2);
+
+        for (t, expected) in [(0.0, q1), (1.0, q2)] {
+            let q = q1.slerp(&q2, t);
+            assert_relative_eq!(q.w, expected.w, epsilon = 1e-10);
+            assert_relative_eq!(q.x, expected.x, epsilon = 1e-10);
+            assert_relative_eq!(q.y, expected.y, epsilon = 1e-10);
+            assert_relative_eq!(q.z, expected.z, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_nlerp_matches_slerp_at_midpoint() {
+        let q1 = Quaternion::identity();
+        let q2 = Quaternion::from_axis_angle([0.0, 1.0, 0.0], FRAC_PI_2);
+
+        // Halfway along the arc both interpolations agree exactly
+        let n = q1.nlerp(&q2, 0.5);
+        let s = q1.slerp(&q2, 0.5);
+        assert_relative_eq!(n.w, s.w, epsilon = 1e-10);
+        assert_relative_eq!(n.x, s.x, epsilon = 1e-10);
+        assert_relative_eq!(n.y, s.y, epsilon = 1e-10);
+        assert_relative_eq!(n.z, s.z, epsilon = 1e-10);
+        assert_relative_eq!(q1.nlerp(&q2, 0.3).norm(), 1.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_quaternion_slerp_path() {
+        let q1 = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 0.3);
+        let q2 = Quaternion::from_axis_angle([0.0, 1.0, 0.0], 1.2);
+
+        let path = q1.slerp_path(&q2, 8);
+        assert_eq!(path.len(), 9);
+        for (got, expected) in [(path[0], q1), (path[8], q2)] {
+            assert_relative_eq!(got.w, expected.w, epsilon = 1e-10);
+            assert_relative_eq!(got.x, expected.x, epsilon = 1e-10);
+            assert_relative_eq!(got.y, expected.y, epsilon = 1e-10);
+            assert_relative_eq!(got.z, expected.z, epsilon = 1e-10);
+        }
+        for q in &path {
+            assert_relative_eq!(q.norm(), 1.0, epsilon = 1e-12);
+        }
+
+        let mid = path[4];
+        let expected = q1.slerp(&q2, 0.5);
+        assert_relative_eq!(mid.w, expected.w, epsilon = 1e-10);
+        assert_relative_eq!(mid.y, expected.y, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_quaternion_slerp_path_zero_steps() {
+        let q1 = Quaternion::identity();
+        let q2 = Quaternion::new(0.0, 0.0, 3.0, 0.0);
+
+        let path = q1.slerp_path(&q2, 0);
+        assert_eq!(path.len(), 2);
+        for (got, expected) in path.iter().zip([q1, Quaternion::new(0.0, 0.0, 1.0, 0.0)]) {
+            assert_relative_eq!(got.w, expected.w, epsilon = 1e-12);
+            assert_relative_eq!(got.x, expected.x, epsilon = 1e-12);
+            assert_relative_eq!(got.y, expected.y, epsilon = 1e-12);
+            assert_relative_eq!(got.z, expected.z, epsilon = 1e-12);
+        }
+
+        // Against a negative dot product the endpoint is flipped onto the
+        // shorter arc whatever the number of steps
+        let opposite = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 3.0);
+        let target = Quaternion::new(-1.0, 0.0, 0.0, 0.0);
+        for steps in [0, 1, 4] {
+            let path = opposite.slerp_path(&target, steps);
+            assert_eq!(path.len(), steps.max(1) + 1);
+            let last = path[path.len() - 1];
+            assert_relative_eq!(last.w, 1.0, epsilon = 1e-10);
+            assert_relative_eq!(last.z, 0.0, epsilon = 1e-10);
+            assert_relative_eq!(path[0].w, opposite.w, epsilon = 1e-10);
+            assert_relative_eq!(path[0].z, opposite.z, epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_normalize_mut() {
+        let original = Quaternion::new(1.0, 2.0, 3.0, 4.0);
+        let mut q = original;
+        q.normalize_mut();
+        assert_eq!(q, original.normalize());
+
+        let mut zero = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+        zero.normalize_mut();
+        assert_eq!(zero, Quaternion::identity());
+
+        let step = Quaternion::from_axis_angle([0.3, -0.5, 0.8], 0.01);
+        for _ in 0..10_000 {
+            q = step.multiply(&q);
+            q.normalize_mut();
+            assert_relative_eq!(q.norm(), 1.0, epsilon = 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_quaternion_integrate_constant_omega() {
+        let theta = 1.3;
+        let steps = 1000;
+        let dt = 0.01;
+        let omega = [0.0, 0.0, theta / (steps as f64 * dt)];
+
+        let mut q = Quaternion::identity();
+        for _ in 0..steps {
+            q = q.integrate(omega, dt);
+        }
+
+        let expected = Quaternion::from_axis_angle([0.0, 0.0, 1.0], theta);
+        assert_relative_eq!(q.w, expected.w, epsilon = 1e-9);
+        assert_relative_eq!(q.x, expected.x, epsilon = 1e-9);
+        assert_relative_eq!(q.y, expected.y, epsilon = 1e-9);
+        assert_relative_eq!(q.z, expected.z, epsilon = 1e-9);
+        assert_relative_eq!(q.norm(), 1.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_quaternion_integrate_is_world_frame() {
+        // Spinning about world +Z after a quarter turn about X still turns
+        // +X towards +Y
+        let start = Quaternion::from_axis_angle([1.0, 0.0, 0.0], FRAC_PI_2);
+        let q = start.integrate([0.0, 0.0, FRAC_PI_2], 1.0);
+        let rotated = q.rotate_vector([1.0, 0.0, 0.0]);
+        assert_relative_eq!(rotated[1], 1.0, epsilon = 1e-10);
+
+        assert_eq!(start.integrate([0.0; 3], 0.5), start.normalize());
+    }
+
+    #[test]
+    fn test_quaternion_axis_angle_round_trip() {
+        let inv_sqrt3 = 1.0 / 3f64.sqrt();
+        let axes = [
+            [1.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [inv_sqrt3, -inv_sqrt3, inv_sqrt3],
+        ];
+        for axis in axes {
+            for angle in [0.1, FRAC_PI_2, 2.0, PI] {
+                let (got_axis, got_angle) =
+                    Quaternion::from_axis_angle(axis, angle).to_axis_angle();
+                assert_relative_eq!(got_angle, angle, epsilon = 1e-10);
+                for i in 0..3 {
+                    assert_relative_eq!(got_axis[i], axis[i], epsilon = 1e-10);
+                }
+            }
+        }
+
+        // Angles past π come back as the shorter rotation about the opposite axis
+        let (axis, angle) = Quaternion::from_axis_angle([0.0, 0.0, 1.0], 1.5 * PI).to_axis_angle();
+        assert_relative_eq!(angle, FRAC_PI_2, epsilon = 1e-10);
+        assert_relative_eq!(axis[2], -1.0, epsilon = 1e-10);
+
+        // Scaling does not change the rotation
+        let scaled = Quaternion::new(2.0, 2.0, 0.0, 0.0);
+        let (axis, angle) = scaled.to_axis_angle();
+        assert_relative_eq!(angle, FRAC_PI_2, epsilon = 1e-10);
+        assert_relative_eq!(axis[0], 1.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_quaternion_identity_axis_angle() {
+        assert_eq!(
+            Quaternion::identity().to_axis_angle(),
+            ([1.0, 0.0, 0.0], 0.0)
+        );
+        let negated = Quaternion::new(-1.0, 0.0, 0.0, 0.0);
+        assert_eq!(negated.to_axis_angle().1, 0.0);
+    }
 }
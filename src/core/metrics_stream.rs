@@ -0,0 +1,115 @@
+use serde::Serialize;
+use tokio::sync::broadcast::{self, error::RecvError};
+use utoipa::ToSchema;
+
+use crate::core::types::GeometricMetrics;
+
+/// Snapshots buffered per subscriber before the oldest are dropped
+pub const METRICS_BROADCAST_CAPACITY: usize = 64;
+
+/// Item delivered to a metrics subscriber.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricsEvent {
+    /// A new metrics snapshot
+    Metrics(GeometricMetrics),
+    /// The subscriber fell behind and this many older snapshots were dropped
+    Lagged { skipped: u64 },
+}
+
+/// Bounded fan-out of metrics snapshots.
+///
+/// Memory is capped at `capacity` snapshots regardless of how slowly
+/// subscribers read: once a subscriber is `capacity` snapshots behind, the
+/// oldest ones are dropped and it is told how many it missed.
+#[derive(Debug, Clone)]
+pub struct MetricsBroadcast {
+    sender: broadcast::Sender<(u64, GeometricMetrics)>,
+}
+
+impl Default for MetricsBroadcast {
+    fn default() -> Self {
+        Self::new(METRICS_BROADCAST_CAPACITY)
+    }
+}
+
+impl MetricsBroadcast {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    /// Send a snapshot, with its history ID, to every current subscriber; a
+    /// no-op when there are none.
+    pub fn publish(&self, id: u64, metrics: &GeometricMetrics) {
+        let _ = self.sender.send((id, metrics.clone()));
+    }
+
+    /// Receive snapshots published from now on.
+    pub fn subscribe(&self) -> MetricsSubscription {
+        MetricsSubscription {
+            receiver: self.sender.subscribe(),
+        }
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+/// One subscriber's view of a [`MetricsBroadcast`].
+#[derive(Debug)]
+pub struct MetricsSubscription {
+    receiver: broadcast::Receiver<(u64, GeometricMetrics)>,
+}
+
+impl MetricsSubscription {
+    /// Wait for the next event; `None` once the broadcast is gone.
+    pub async fn recv(&mut self) -> Option<MetricsEvent> {
+        self.recv_with_id().await.map(|(_, event)| event)
+    }
+
+    /// Like [`recv`](Self::recv), also returning the history ID of a metrics
+    /// event. `Lagged` events have no ID.
+    pub async fn recv_with_id(&mut self) -> Option<(Option<u64>, MetricsEvent)> {
+        match self.receiver.recv().await {
+            Ok((id, metrics)) => Some((Some(id), MetricsEvent::Metrics(metrics))),
+            Err(RecvError::Lagged(skipped)) => Some((None, MetricsEvent::Lagged { skipped })),
+            Err(RecvError::Closed) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(v: f64) -> GeometricMetrics {
+        GeometricMetrics {
+            v_geometric: v,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_is_told_it_lagged() {
+        let broadcast = MetricsBroadcast::new(4);
+        let mut slow = broadcast.subscribe();
+
+        for i in 0..10 {
+            broadcast.publish(i, &snapshot(i as f64));
+        }
+
+        // Only the newest `capacity` snapshots are kept
+        assert_eq!(slow.recv().await, Some(MetricsEvent::Lagged { skipped: 6 }));
+        for i in 6..10 {
+            assert_eq!(
+                slow.recv().await,
+                Some(MetricsEvent::Metrics(snapshot(i as f64)))
+            );
+        }
+
+        drop(broadcast);
+        assert_eq!(slow.recv().await, None);
+    }
+}
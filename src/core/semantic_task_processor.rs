@@ -1,17 +1,30 @@
-use crate::core::emergence_logic::EmergenceLogic;
+use crate::core::alerts::{Alert, AlertMonitor};
+use crate::core::clock::{has_elapsed, Clock, SystemClock};
+use crate::core::emergence_logic::{EmergenceLogic, RuleContribution, RuleSet};
 use crate::core::error::{Error, Result};
-use crate::core::types::{GeometricMetrics, GeometricTaskCommand, TaskExecutionResult};
+use crate::core::geometric_metrics::{detect_anomalies, Anomaly};
+use crate::core::metrics_stream::{MetricsBroadcast, MetricsSubscription};
+use crate::core::types::{
+    GeometricMetrics, GeometricOperator, GeometricTaskCommand, SemanticAnchor, TaskExecutionResult,
+};
+use crate::core::validation::validate_task_command;
 use crate::state::{
-    compute_electron_mass, compute_fine_structure, compute_quaternion_coherence, compute_zitter_entropy,
+    compute_electron_mass, compute_fine_structure, compute_quaternion_coherence,
+    compute_zitter_entropy,
 };
-use log::{error, info};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
 /// Represents the status of a task
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub enum TaskStatus {
     Pending,
     InProgress,
@@ -19,6 +32,45 @@ pub enum TaskStatus {
     Failed(String),
 }
 
+/// [`TaskStatus`] without its payload, for counting tasks by state
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+pub enum TaskStatusKind {
+    Pending,
+    InProgress,
+    Completed,
+    Failed,
+}
+
+impl TaskStatusKind {
+    pub const ALL: [TaskStatusKind; 4] = [
+        TaskStatusKind::Pending,
+        TaskStatusKind::InProgress,
+        TaskStatusKind::Completed,
+        TaskStatusKind::Failed,
+    ];
+}
+
+impl TaskStatus {
+    /// Status a task ends up in after producing `result`
+    pub fn from_result(result: &TaskExecutionResult) -> Self {
+        match &result.error {
+            None => TaskStatus::Completed(result.metrics.clone()),
+            Some(message) => TaskStatus::Failed(message.clone()),
+        }
+    }
+
+    pub fn kind(&self) -> TaskStatusKind {
+        match self {
+            TaskStatus::Pending => TaskStatusKind::Pending,
+            TaskStatus::InProgress => TaskStatusKind::InProgress,
+            TaskStatus::Completed(_) => TaskStatusKind::Completed,
+            TaskStatus::Failed(_) => TaskStatusKind::Failed,
+        }
+    }
+}
+
 impl SemanticTaskProcessor {
     fn baseline_metrics() -> GeometricMetrics {
         let coherence = compute_quaternion_coherence();
@@ -41,31 +93,261 @@ impl SemanticTaskProcessor {
     }
 }
 
+/// Number of metric snapshots retained in the history buffer
+pub const METRICS_HISTORY_CAPACITY: usize = 1024;
+
+/// Default for [`ProcessorConfig::max_completed_tasks`]
+pub const DEFAULT_MAX_COMPLETED_TASKS: usize = 100_000;
+
+/// A history entry: the metrics and when they were recorded
+type TimestampedMetrics = (DateTime<Utc>, GeometricMetrics);
+
+/// The bounded metrics history. Every entry gets an ID one higher than the
+/// entry recorded before it, and IDs are never reused, even across eviction
+/// and resets, so they can serve as resume points for streaming clients.
+struct MetricsHistory {
+    entries: VecDeque<TimestampedMetrics>,
+    /// ID of `entries[0]`, or of the next entry when empty
+    first_id: u64,
+}
+
+impl MetricsHistory {
+    fn new(baseline: TimestampedMetrics) -> Self {
+        Self {
+            entries: VecDeque::from([baseline]),
+            first_id: 0,
+        }
+    }
+
+    fn next_id(&self) -> u64 {
+        self.first_id + self.entries.len() as u64
+    }
+
+    /// Append an entry, evicting the oldest at capacity, and return its ID
+    fn push(&mut self, entry: TimestampedMetrics) -> u64 {
+        if self.entries.len() == METRICS_HISTORY_CAPACITY {
+            self.entries.pop_front();
+            self.first_id += 1;
+        }
+        let id = self.next_id();
+        self.entries.push_back(entry);
+        id
+    }
+
+    fn clear(&mut self) {
+        self.first_id = self.next_id();
+        self.entries.clear();
+    }
+
+    /// Entries with an ID greater than `id`, oldest first. `id` may come from
+    /// a client, so no ID is assumed to lie above it.
+    fn after(&self, id: u64) -> impl Iterator<Item = (u64, &TimestampedMetrics)> {
+        // Nothing follows u64::MAX; skip everything rather than wrap to 0
+        let skip = id.checked_add(1).map_or(usize::MAX, |next| {
+            usize::try_from(next.saturating_sub(self.first_id)).unwrap_or(usize::MAX)
+        });
+        (self.first_id..).zip(self.entries.iter()).skip(skip)
+    }
+}
+
+impl std::ops::Deref for MetricsHistory {
+    type Target = VecDeque<TimestampedMetrics>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entries
+    }
+}
+
+/// A command applied to the metrics and when it ran
+type LoggedCommand = (DateTime<Utc>, GeometricTaskCommand);
+
+/// Called with each command just before [`SemanticTaskProcessor::execute_task`]
+/// applies it
+pub type PreHook = Arc<dyn Fn(&GeometricTaskCommand) + Send + Sync>;
+
+/// Called with each result [`SemanticTaskProcessor::execute_task`] returns
+pub type PostHook = Arc<dyn Fn(&TaskExecutionResult) + Send + Sync>;
+
+/// A change to a task, reported to each [`TaskListener`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaskChange {
+    /// The task was accepted with this command, as `Pending`
+    Submitted(GeometricTaskCommand),
+    /// The task moved to this status
+    Status(TaskStatus),
+}
+
+/// Called with the ID of each submitted task and each status change
+pub type TaskListener = Arc<dyn Fn(Uuid, &TaskChange) + Send + Sync>;
+
+/// What applying one task's operator produced
+struct Simulation {
+    metrics: GeometricMetrics,
+    contributions: Vec<RuleContribution>,
+    /// The task's `expected_output_metric` before and after the update
+    before: Option<f64>,
+    after: f64,
+}
+
+/// Metrics as returned by [`SemanticTaskProcessor::metrics_snapshot`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricsSnapshot {
+    pub metrics: GeometricMetrics,
+    /// The live metrics were unavailable and these are the last values
+    /// successfully written
+    pub stale: bool,
+}
+
+/// Scheduled tasks ordered by due time, then submission order
+type ScheduleQueue = BTreeMap<(DateTime<Utc>, u64), Uuid>;
+
+/// Completed tasks ordered by completion time, then submission order
+type CompletedQueue = BTreeMap<(DateTime<Utc>, u64), Uuid>;
+
 struct TaskInfo {
     command: GeometricTaskCommand,
     status: TaskStatus,
+    /// Submission order, used to pick the next pending task
+    sequence: u64,
+    /// When the task last moved to `InProgress`
+    started_at: Option<DateTime<Utc>>,
+    /// When the task completed or failed
+    finished_at: Option<DateTime<Utc>>,
+}
+
+/// Tunable behaviour of a [`SemanticTaskProcessor`]
+#[derive(Debug, Clone)]
+pub struct ProcessorConfig {
+    /// Simulated work time spent inside `execute_task`
+    pub exec_delay: Duration,
+    /// Emergence coefficients; `None` uses the built-in defaults
+    pub rule_set: Option<RuleSet>,
+    /// Time source for history and completion timestamps
+    pub clock: Arc<dyn Clock>,
+    /// Fail a task whose `expected_output_metric` has the same value after
+    /// execution as before
+    pub require_metric_change: bool,
+    /// Completed tasks kept before the earliest completed are evicted; each
+    /// holds a full metrics snapshot
+    pub max_completed_tasks: usize,
+    /// Metrics to start from, such as those recovered from disk; `None`
+    /// starts from the baseline
+    pub initial_metrics: Option<GeometricMetrics>,
+}
+
+impl Default for ProcessorConfig {
+    fn default() -> Self {
+        Self {
+            exec_delay: Duration::from_millis(100),
+            rule_set: None,
+            clock: Arc::new(SystemClock),
+            require_metric_change: false,
+            max_completed_tasks: DEFAULT_MAX_COMPLETED_TASKS,
+            initial_metrics: None,
+        }
+    }
 }
 
 /// Manages the execution of geometric tasks
 pub struct SemanticTaskProcessor {
+    config: ProcessorConfig,
     tasks: Arc<Mutex<HashMap<Uuid, TaskInfo>>>,
     metrics: Arc<Mutex<GeometricMetrics>>,
+    /// Copy of `metrics` taken after each complete write, served when the
+    /// live metrics lock is poisoned
+    last_good_metrics: Arc<Mutex<GeometricMetrics>>,
     emergence: Arc<Mutex<EmergenceLogic>>,
+    operator_counts: Arc<Mutex<HashMap<GeometricOperator, u64>>>,
+    history: Arc<Mutex<MetricsHistory>>,
+    /// Every command applied to the metrics since the last reset, in order,
+    /// with the time it ran
+    command_log: Arc<Mutex<Vec<LoggedCommand>>>,
+    /// Metrics the commands in `command_log` were applied to: the initial
+    /// metrics, or the baseline after a reset. Only locked while
+    /// `command_log` is held.
+    origin: Arc<Mutex<GeometricMetrics>>,
+    scheduled: Arc<Mutex<ScheduleQueue>>,
+    /// Every task in `tasks` whose status is `Completed`, oldest first. Only
+    /// locked while `tasks` is held.
+    completed: Arc<Mutex<CompletedQueue>>,
+    /// Registered anchors in registration order
+    anchors: Arc<Mutex<Vec<SemanticAnchor>>>,
+    /// Commands registered by name for [`instantiate`](Self::instantiate)
+    templates: Arc<Mutex<HashMap<String, GeometricTaskCommand>>>,
+    pre_hooks: Arc<Mutex<Vec<PreHook>>>,
+    post_hooks: Arc<Mutex<Vec<PostHook>>>,
+    task_listeners: Arc<Mutex<Vec<TaskListener>>>,
+    /// Metric thresholds, checked after every metrics update
+    alerts: Arc<Mutex<AlertMonitor>>,
+    broadcast: MetricsBroadcast,
+    next_sequence: Arc<AtomicU64>,
+}
+
+impl Default for SemanticTaskProcessor {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl SemanticTaskProcessor {
     /// Create a new SemanticTaskProcessor
     pub fn new() -> Self {
+        Self::new_with_config(ProcessorConfig::default())
+    }
+
+    /// Create a new SemanticTaskProcessor with the given configuration
+    pub fn new_with_config(config: ProcessorConfig) -> Self {
+        let initial = config
+            .initial_metrics
+            .clone()
+            .unwrap_or_else(Self::baseline_metrics);
+        let emergence = EmergenceLogic::new(config.rule_set.clone()).with_metrics(initial.clone());
+        let started_at = config.clock.now();
         Self {
+            config,
             tasks: Arc::new(Mutex::new(HashMap::new())),
-            metrics: Arc::new(Mutex::new(Self::baseline_metrics())),
-            emergence: Arc::new(Mutex::new(EmergenceLogic::new(None))),
+            metrics: Arc::new(Mutex::new(initial.clone())),
+            last_good_metrics: Arc::new(Mutex::new(initial.clone())),
+            emergence: Arc::new(Mutex::new(emergence)),
+            operator_counts: Arc::new(Mutex::new(HashMap::new())),
+            history: Arc::new(Mutex::new(MetricsHistory::new((
+                started_at,
+                initial.clone(),
+            )))),
+            command_log: Arc::new(Mutex::new(Vec::new())),
+            origin: Arc::new(Mutex::new(initial)),
+            scheduled: Arc::new(Mutex::new(BTreeMap::new())),
+            completed: Arc::new(Mutex::new(BTreeMap::new())),
+            anchors: Arc::new(Mutex::new(Vec::new())),
+            templates: Arc::new(Mutex::new(HashMap::new())),
+            pre_hooks: Arc::new(Mutex::new(Vec::new())),
+            post_hooks: Arc::new(Mutex::new(Vec::new())),
+            task_listeners: Arc::new(Mutex::new(Vec::new())),
+            alerts: Arc::new(Mutex::new(AlertMonitor::default())),
+            broadcast: MetricsBroadcast::default(),
+            next_sequence: Arc::new(AtomicU64::new(0)),
         }
     }
 
     /// Submit a new geometric task for execution
     pub fn submit_task(&self, task: GeometricTaskCommand) -> Result<Uuid> {
-        let task_id = task.task_id.unwrap_or_else(Uuid::new_v4);
+        self.submit_task_with(task, false)
+    }
+
+    /// Submit a task; when it has no `task_id` and `deterministic` is set, the
+    /// ID is derived from its content so an identical resubmission is rejected
+    /// as a duplicate instead of creating a second task
+    pub fn submit_task_with(
+        &self,
+        task: GeometricTaskCommand,
+        deterministic: bool,
+    ) -> Result<Uuid> {
+        validate_task_command(&task)?;
+        let task_id = match task.task_id {
+            Some(id) => id,
+            None if deterministic => task.deterministic_id(),
+            None => Uuid::new_v4(),
+        };
 
         let mut tasks = self.tasks.lock().map_err(|e| {
             error!("Failed to lock tasks: {}", e);
@@ -79,54 +361,343 @@ impl SemanticTaskProcessor {
             )));
         }
 
+        let sequence = self.next_sequence.fetch_add(1, Ordering::Relaxed);
+        if let Some(execute_at) = task.execute_at {
+            let mut scheduled = self.scheduled.lock().map_err(|e| {
+                error!("Failed to lock task schedule: {}", e);
+                Error::TaskExecution("Failed to access task schedule".to_string())
+            })?;
+            scheduled.insert((execute_at, sequence), task_id);
+        }
+
         tasks.insert(
             task_id,
             TaskInfo {
                 command: task.clone(),
                 status: TaskStatus::Pending,
+                sequence,
+                started_at: None,
+                finished_at: None,
             },
         );
         info!("Submitted task {}: {}", task_id, task.task_name);
+        drop(tasks);
+        self.notify(task_id, TaskChange::Submitted(task));
 
         Ok(task_id)
     }
 
-    /// Execute a pending task
+    /// Execute a pending task and check its `expected_output_metric`.
+    ///
+    /// A metric name that is neither a scalar field nor a custom metric of the
+    /// result fails the task with `Error::InvalidParameter`, leaving the
+    /// metrics untouched. With `require_metric_change` set, a metric that kept
+    /// its value yields an unsuccessful result and a failed task.
     pub fn execute_task(&self, task_id: Uuid) -> Result<TaskExecutionResult> {
         // In a real implementation, this would execute the actual task
         // For now, we'll simulate task execution
-        let mut tasks = self.tasks.lock().map_err(|e| {
-            error!("Failed to lock tasks: {}", e);
-            Error::TaskExecution("Failed to access task storage".to_string())
-        })?;
+        let command = {
+            let mut tasks = self.tasks.lock().map_err(|e| {
+                error!("Failed to lock tasks: {}", e);
+                Error::TaskExecution("Failed to access task storage".to_string())
+            })?;
 
-        let info = tasks
-            .get_mut(&task_id)
-            .ok_or_else(|| Error::TaskExecution(format!("Task with ID {} not found", task_id)))?;
+            let info = tasks.get_mut(&task_id).ok_or_else(|| {
+                Error::TaskExecution(format!("Task with ID {} not found", task_id))
+            })?;
 
-        // Update status to in progress
-        info.status = TaskStatus::InProgress;
+            // Claim the task while still holding the lock, so neither a
+            // concurrent call nor a repeated one can apply the operator twice
+            match info.status {
+                TaskStatus::Pending => {}
+                TaskStatus::InProgress => {
+                    return Err(Error::TaskExecution("already running".to_string()));
+                }
+                TaskStatus::Completed(_) | TaskStatus::Failed(_) => {
+                    return Err(Error::TaskExecution("already executed".to_string()));
+                }
+            }
+            info.status = TaskStatus::InProgress;
+            info.started_at = Some(self.config.clock.now());
+            info.command.clone()
+        };
+        self.notify(task_id, TaskChange::Status(TaskStatus::InProgress));
+
+        let pre_hooks = match self.hooks(&self.pre_hooks) {
+            Ok(hooks) => hooks,
+            Err(err) => {
+                // Nothing has run yet, so hand the task back
+                self.release_claim(task_id);
+                return Err(err);
+            }
+        };
+        for (index, hook) in pre_hooks.iter().enumerate() {
+            if catch_unwind(AssertUnwindSafe(|| hook(&command))).is_err() {
+                error!("Pre-execution hook {} panicked on task {}", index, task_id);
+            }
+        }
 
         // Simulate some work
-        std::thread::sleep(std::time::Duration::from_millis(100));
+        if !self.config.exec_delay.is_zero() {
+            std::thread::sleep(self.config.exec_delay);
+        }
+
+        let outcome = self.simulate_task_execution(&command);
+        if let Ok(simulation) = &outcome {
+            self.check_alerts(&simulation.metrics);
+        }
+
+        let mut tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+        // The task may have been removed while it ran; the result still stands
+        let mut info = tasks.get_mut(&task_id);
+        if let Some(info) = info.as_deref_mut() {
+            info.finished_at = Some(self.config.clock.now());
+        }
 
-        let metrics = self.simulate_task_execution(&info.command)?;
+        let Simulation {
+            metrics,
+            contributions,
+            before,
+            after,
+        } = match outcome {
+            Ok(simulation) => simulation,
+            Err(err) => {
+                if let Some(info) = info {
+                    let status = TaskStatus::Failed(match &err {
+                        Error::InvalidParameter(_, message) => message.clone(),
+                        other => other.to_string(),
+                    });
+                    info.status = status.clone();
+                    drop(tasks);
+                    self.notify(task_id, TaskChange::Status(status));
+                }
+                return Err(err);
+            }
+        };
 
-        // Update the task status
-        info.status = TaskStatus::Completed(metrics.clone());
+        let expected = &command.expected_output_metric;
+        let error = (self.config.require_metric_change && before == Some(after))
+            .then(|| format!("expected metric '{}' did not change", expected));
 
         // Create the result
-        Ok(TaskExecutionResult {
+        let result = TaskExecutionResult {
             task_id,
-            success: true,
+            success: error.is_none(),
             metrics,
-            output: serde_json::json!({ "status": "completed" }),
-            error: None,
-        })
+            output: serde_json::json!({
+                "status": if error.is_none() { "completed" } else { "failed" },
+                "contributions": contributions,
+            }),
+            error,
+            replayed_from: None,
+        };
+        let status = TaskStatus::from_result(&result);
+        let tracked = info.is_some();
+        if let Some(info) = info {
+            info.status = status.clone();
+            if result.success {
+                let key = (info.finished_at.unwrap_or_default(), info.sequence);
+                self.evict_completed(&mut tasks, key, task_id);
+            }
+        }
+        drop(tasks);
+        if tracked {
+            self.notify(task_id, TaskChange::Status(status));
+        }
+
+        for (index, hook) in self.hooks(&self.post_hooks)?.iter().enumerate() {
+            if catch_unwind(AssertUnwindSafe(|| hook(&result))).is_err() {
+                error!("Post-execution hook {} panicked on task {}", index, task_id);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Return a task claimed by [`execute_task`](Self::execute_task) to
+    /// `Pending` when execution stops before its operator is applied.
+    fn release_claim(&self, task_id: Uuid) {
+        let released = match self.tasks.lock() {
+            Ok(mut tasks) => match tasks.get_mut(&task_id) {
+                Some(info) if info.status == TaskStatus::InProgress => {
+                    info.status = TaskStatus::Pending;
+                    info.started_at = None;
+                    true
+                }
+                _ => false,
+            },
+            Err(e) => {
+                error!("Failed to lock tasks: {}", e);
+                false
+            }
+        };
+        if released {
+            self.notify(task_id, TaskChange::Status(TaskStatus::Pending));
+        }
+    }
+
+    /// Run `hook` with every command [`execute_task`](Self::execute_task)
+    /// is about to apply, after the hooks registered before it. A panicking
+    /// hook is logged and skipped; execution carries on.
+    pub fn add_pre_hook(&self, hook: PreHook) -> Result<()> {
+        let mut hooks = self.pre_hooks.lock().map_err(|e| {
+            error!("Failed to lock hooks: {}", e);
+            Error::TaskExecution("Failed to access hooks".to_string())
+        })?;
+        hooks.push(hook);
+        Ok(())
+    }
+
+    /// Run `hook` with every result [`execute_task`](Self::execute_task)
+    /// returns, including unsuccessful ones, after the hooks registered
+    /// before it. A panicking hook is logged and skipped.
+    pub fn add_post_hook(&self, hook: PostHook) -> Result<()> {
+        let mut hooks = self.post_hooks.lock().map_err(|e| {
+            error!("Failed to lock hooks: {}", e);
+            Error::TaskExecution("Failed to access hooks".to_string())
+        })?;
+        hooks.push(hook);
+        Ok(())
+    }
+
+    /// Run `listener` with every task submitted and every status change from
+    /// now on, after the listeners registered before it. It is called without
+    /// the task lock held, once the change is visible; a panicking listener
+    /// is logged and skipped.
+    pub fn add_task_listener(&self, listener: TaskListener) -> Result<()> {
+        let mut listeners = self.task_listeners.lock().map_err(|e| {
+            error!("Failed to lock hooks: {}", e);
+            Error::TaskExecution("Failed to access hooks".to_string())
+        })?;
+        listeners.push(listener);
+        Ok(())
+    }
+
+    fn notify(&self, task_id: Uuid, change: TaskChange) {
+        let listeners = match self.hooks(&self.task_listeners) {
+            Ok(listeners) => listeners,
+            Err(err) => {
+                error!("Task {} change not reported: {}", task_id, err);
+                return;
+            }
+        };
+        for (index, listener) in listeners.iter().enumerate() {
+            if catch_unwind(AssertUnwindSafe(|| listener(task_id, &change))).is_err() {
+                error!("Task listener {} panicked on task {}", index, task_id);
+            }
+        }
+    }
+
+    /// Snapshot of registered hooks, so they run without the lock held and
+    /// may register further hooks
+    fn hooks<H: Clone>(&self, hooks: &Mutex<Vec<H>>) -> Result<Vec<H>> {
+        let hooks = hooks.lock().map_err(|e| {
+            error!("Failed to lock hooks: {}", e);
+            Error::TaskExecution("Failed to access hooks".to_string())
+        })?;
+        Ok(hooks.clone())
+    }
+
+    /// Execute the earliest-submitted pending task, if there is one. Tasks
+    /// scheduled for a later time are skipped.
+    pub fn execute_next(&self) -> Result<Option<TaskExecutionResult>> {
+        let next = {
+            let tasks = self.tasks.lock().map_err(|e| {
+                error!("Failed to lock tasks: {}", e);
+                Error::TaskExecution("Failed to access task storage".to_string())
+            })?;
+
+            let now = self.config.clock.now();
+            tasks
+                .iter()
+                .filter(|(_, info)| info.status == TaskStatus::Pending)
+                .filter(|(_, info)| info.command.execute_at.is_none_or(|at| at <= now))
+                .min_by_key(|(_, info)| info.sequence)
+                .map(|(id, _)| *id)
+        };
+
+        next.map(|task_id| self.execute_task(task_id)).transpose()
+    }
+
+    /// Execute every pending task in submission order
+    pub fn execute_ready(&self) -> Result<Vec<TaskExecutionResult>> {
+        let mut results = Vec::new();
+        while let Some(result) = self.execute_next()? {
+            results.push(result);
+        }
+        Ok(results)
+    }
+
+    /// Execute scheduled tasks whose time has arrived, earliest first. Tasks
+    /// that were removed or already run in the meantime are skipped, and a
+    /// failing task does not hold back the rest.
+    pub fn execute_due(&self) -> Result<Vec<TaskExecutionResult>> {
+        let now = self.config.clock.now();
+        let due: Vec<Uuid> = {
+            let mut scheduled = self.scheduled.lock().map_err(|e| {
+                error!("Failed to lock task schedule: {}", e);
+                Error::TaskExecution("Failed to access task schedule".to_string())
+            })?;
+
+            let mut due = Vec::new();
+            while let Some(entry) = scheduled.first_entry() {
+                if entry.key().0 > now {
+                    break;
+                }
+                due.push(entry.remove());
+            }
+            due
+        };
+
+        let mut results = Vec::new();
+        for task_id in due {
+            if !matches!(self.get_task(task_id), Ok((_, TaskStatus::Pending))) {
+                continue;
+            }
+            match self.execute_task(task_id) {
+                Ok(result) => results.push(result),
+                Err(err) => error!("Scheduled task {} failed: {}", task_id, err),
+            }
+        }
+        Ok(results)
     }
 
-    /// Simulate task execution (placeholder for actual implementation)
-    fn simulate_task_execution(&self, task: &GeometricTaskCommand) -> Result<GeometricMetrics> {
+    /// Re-run a known task's command under a fresh task ID
+    pub fn replay_task(&self, task_id: Uuid) -> Result<TaskExecutionResult> {
+        let mut command = {
+            let tasks = self.tasks.lock().map_err(|e| {
+                error!("Failed to lock tasks: {}", e);
+                Error::TaskExecution("Failed to access task storage".to_string())
+            })?;
+
+            tasks
+                .get(&task_id)
+                .map(|info| info.command.clone())
+                .ok_or(Error::TaskNotFound(task_id))?
+        };
+        command.task_id = None;
+        command.execute_at = None;
+
+        let replay_id = self.submit_task(command)?;
+        info!("Replaying task {} as {}", task_id, replay_id);
+
+        let mut result = self.execute_task(replay_id)?;
+        result.replayed_from = Some(task_id);
+        Ok(result)
+    }
+
+    /// Simulate task execution: apply the task's operator and record the new
+    /// metrics.
+    ///
+    /// The operator runs on a copy of the emergence state first, so a task
+    /// whose `expected_output_metric` is neither in the current metrics nor
+    /// produced by the operator is rejected before anything is applied or
+    /// recorded. The metric's value before the update is read under the same
+    /// locks as the update itself.
+    fn simulate_task_execution(&self, task: &GeometricTaskCommand) -> Result<Simulation> {
         let mut metrics = self.metrics.lock().map_err(|e| {
             error!("Failed to lock metrics: {}", e);
             Error::TaskExecution("Failed to access metrics".to_string())
@@ -137,10 +708,41 @@ impl SemanticTaskProcessor {
             Error::TaskExecution("Failed to access emergence logic".to_string())
         })?;
 
-        let updated = emergence.apply_operator(task.geometric_operator, &task.parameters);
-        *metrics = updated.clone();
+        let expected = &task.expected_output_metric;
+        let before = metrics.field(expected);
+        let mut next = emergence.clone();
+        let (updated, contributions) =
+            next.apply_operator(task.geometric_operator, &task.parameters);
+        let Some(after) = updated.field(expected) else {
+            return Err(Error::InvalidParameter(
+                "expected_output_metric".into(),
+                format!("unknown metric '{}'", expected),
+            ));
+        };
+        *emergence = next;
+        *metrics = updated;
+        self.store_last_good_metrics(&metrics);
+
+        let mut counts = self.operator_counts.lock().map_err(|e| {
+            error!("Failed to lock operator counts: {}", e);
+            Error::TaskExecution("Failed to access operator statistics".to_string())
+        })?;
+        *counts.entry(task.geometric_operator).or_insert(0) += 1;
+
+        let mut command_log = self.command_log.lock().map_err(|e| {
+            error!("Failed to lock command log: {}", e);
+            Error::TaskExecution("Failed to access command log".to_string())
+        })?;
+        command_log.push((self.config.clock.now(), task.clone()));
+
+        self.record_history(&metrics)?;
 
-        Ok(metrics.clone())
+        Ok(Simulation {
+            metrics: metrics.clone(),
+            contributions,
+            before,
+            after,
+        })
     }
 
     /// Get the status of a task
@@ -156,96 +758,1670 @@ impl SemanticTaskProcessor {
             .ok_or_else(|| Error::TaskExecution(format!("Task with ID {} not found", task_id)))
     }
 
-    /// Get the current metrics
+    /// Get a task's submitted command together with its status
+    pub fn get_task(&self, task_id: Uuid) -> Result<(GeometricTaskCommand, TaskStatus)> {
+        let tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        tasks
+            .get(&task_id)
+            .map(|info| (info.command.clone(), info.status.clone()))
+            .ok_or(Error::TaskNotFound(task_id))
+    }
+
+    /// Get the current metrics, or the last known good ones when the live
+    /// metrics are unavailable; see [`metrics_snapshot`](Self::metrics_snapshot)
     pub fn get_metrics(&self) -> Result<GeometricMetrics> {
-        let metrics = self.metrics.lock().map_err(|e| {
+        Ok(self.metrics_snapshot()?.metrics)
+    }
+
+    /// The current metrics. If a panic mid-update poisoned the live metrics,
+    /// the last completely written snapshot is returned instead, marked
+    /// stale, so reads keep working.
+    pub fn metrics_snapshot(&self) -> Result<MetricsSnapshot> {
+        match self.metrics.lock() {
+            Ok(metrics) => Ok(MetricsSnapshot {
+                metrics: metrics.clone(),
+                stale: false,
+            }),
+            Err(e) => {
+                warn!("Failed to lock metrics, serving last known good: {}", e);
+                let metrics = self
+                    .last_good_metrics
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner);
+                Ok(MetricsSnapshot {
+                    metrics: metrics.clone(),
+                    stale: true,
+                })
+            }
+        }
+    }
+
+    fn store_last_good_metrics(&self, metrics: &GeometricMetrics) {
+        let mut last_good = self
+            .last_good_metrics
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        *last_good = metrics.clone();
+    }
+
+    /// Restore the baseline metrics and reinitialize the emergence cascade
+    pub fn reset_metrics(&self) -> Result<()> {
+        let mut metrics = self.metrics.lock().map_err(|e| {
             error!("Failed to lock metrics: {}", e);
             Error::TaskExecution("Failed to access metrics".to_string())
         })?;
 
-        Ok(metrics.clone())
+        let mut emergence = self.emergence.lock().map_err(|e| {
+            error!("Failed to lock emergence logic: {}", e);
+            Error::TaskExecution("Failed to access emergence logic".to_string())
+        })?;
+
+        let mut command_log = self.command_log.lock().map_err(|e| {
+            error!("Failed to lock command log: {}", e);
+            Error::TaskExecution("Failed to access command log".to_string())
+        })?;
+
+        *metrics = Self::baseline_metrics();
+        self.store_last_good_metrics(&metrics);
+        // Keep any rule set installed since startup
+        *emergence = EmergenceLogic::new(Some(emergence.rules().clone()));
+        command_log.clear();
+        *self.origin.lock().unwrap_or_else(PoisonError::into_inner) = metrics.clone();
+
+        let mut history = self.history.lock().map_err(|e| {
+            error!("Failed to lock metrics history: {}", e);
+            Error::TaskExecution("Failed to access metrics history".to_string())
+        })?;
+        history.clear();
+        let id = history.push((self.config.clock.now(), metrics.clone()));
+        self.broadcast.publish(id, &metrics);
+        info!("Metrics reset to baseline");
+
+        let baseline = metrics.clone();
+        drop((metrics, emergence, command_log, history));
+        self.check_alerts(&baseline);
+        Ok(())
     }
 
-    /// List all known tasks with their statuses
-    pub fn list_tasks(&self) -> Result<Vec<(Uuid, TaskStatus)>> {
-        let tasks = self.tasks.lock().map_err(|e| {
-            error!("Failed to lock tasks: {}", e);
-            Error::TaskExecution("Failed to access task storage".to_string())
+    /// Use `rules` for every execution from now on. Metrics already recorded
+    /// keep their values until [`recompute_metrics`](Self::recompute_metrics).
+    pub fn set_rule_set(&self, rules: RuleSet) -> Result<()> {
+        let mut emergence = self.emergence.lock().map_err(|e| {
+            error!("Failed to lock emergence logic: {}", e);
+            Error::TaskExecution("Failed to access emergence logic".to_string())
         })?;
 
-        Ok(tasks
-            .iter()
-            .map(|(id, info)| (*id, info.status.clone()))
-            .collect())
+        emergence.set_rules(rules);
+        Ok(())
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::types::GeometricOperator;
+    /// Re-run every command applied since the last reset from the metrics
+    /// they started from, under the current rule set, and replace the metrics and their history
+    /// with the fresh trajectory. Returns the new final metrics.
+    ///
+    /// Tasks, their commands and operator counts are left untouched.
+    pub fn recompute_metrics(&self) -> Result<GeometricMetrics> {
+        let mut metrics = self.metrics.lock().map_err(|e| {
+            error!("Failed to lock metrics: {}", e);
+            Error::TaskExecution("Failed to access metrics".to_string())
+        })?;
 
-    #[test]
-    fn test_task_submission() {
-        let processor = SemanticTaskProcessor::new();
-        let task = GeometricTaskCommand {
-            task_name: "Test Task".to_string(),
-            geometric_operator: GeometricOperator::QuaternionRotation,
-            target_module: "test_module".to_string(),
-            parameters: serde_json::json!({}),
-            expected_output_metric: "v_geometric".to_string(),
-            task_id: None,
-        };
+        let mut emergence = self.emergence.lock().map_err(|e| {
+            error!("Failed to lock emergence logic: {}", e);
+            Error::TaskExecution("Failed to access emergence logic".to_string())
+        })?;
 
-        let task_id = processor.submit_task(task).unwrap();
-        let status = processor.get_task_status(task_id).unwrap();
+        let command_log = self.command_log.lock().map_err(|e| {
+            error!("Failed to lock command log: {}", e);
+            Error::TaskExecution("Failed to access command log".to_string())
+        })?;
 
-        assert!(matches!(status, TaskStatus::Pending));
+        let mut history = self.history.lock().map_err(|e| {
+            error!("Failed to lock metrics history: {}", e);
+            Error::TaskExecution("Failed to access metrics history".to_string())
+        })?;
+
+        let origin = self
+            .origin
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        let mut fresh =
+            EmergenceLogic::new(Some(emergence.rules().clone())).with_metrics(origin.clone());
+        let origin_at = history
+            .front()
+            .map_or_else(|| self.config.clock.now(), |(at, _)| *at);
+        // The fresh trajectory gets new IDs; subscribers only see its end
+        history.clear();
+        let mut last_id = history.push((origin_at, origin.clone()));
+        for (at, command) in command_log.iter() {
+            let (updated, _) =
+                fresh.apply_operator(command.geometric_operator, &command.parameters);
+            last_id = history.push((*at, updated));
+        }
+
+        *metrics = history.back().map_or(origin, |(_, last)| last.clone());
+        self.store_last_good_metrics(&metrics);
+        *emergence = fresh;
+        self.broadcast.publish(last_id, &metrics);
+        info!("Recomputed metrics over {} commands", command_log.len());
+
+        let recomputed = metrics.clone();
+        drop((metrics, emergence, command_log, history));
+        self.check_alerts(&recomputed);
+        Ok(recomputed)
     }
 
-    #[test]
-    fn test_task_execution() {
-        let processor = SemanticTaskProcessor::new();
-        let task = GeometricTaskCommand {
-            task_name: "Test Task".to_string(),
-            geometric_operator: GeometricOperator::QuaternionRotation,
-            target_module: "test_module".to_string(),
-            parameters: serde_json::json!({}),
-            expected_output_metric: "v_geometric".to_string(),
-            task_id: None,
+    /// Alert when `field` goes below `min` or above `max`, replacing any
+    /// existing alert on that field; passing neither bound removes it.
+    ///
+    /// Alerts are edge-triggered: one is raised when the metric leaves its
+    /// range and no more until it has come back.
+    pub fn set_alert(&self, field: String, min: Option<f64>, max: Option<f64>) -> Result<()> {
+        if field.trim().is_empty() {
+            return Err(Error::InvalidParameter(
+                "field".into(),
+                "must not be empty".into(),
+            ));
+        }
+
+        let mut alerts = self.alerts.lock().map_err(|e| {
+            error!("Failed to lock alerts: {}", e);
+            Error::TaskExecution("Failed to access alerts".to_string())
+        })?;
+        alerts.set(field, min, max)
+    }
+
+    /// Call `callback` with every alert raised from now on, replacing any
+    /// previous callback. It runs on the thread that updated the metrics,
+    /// with no processor locks held.
+    pub fn on_alert<F>(&self, callback: F) -> Result<()>
+    where
+        F: Fn(Alert) + Send + Sync + 'static,
+    {
+        let mut alerts = self.alerts.lock().map_err(|e| {
+            error!("Failed to lock alerts: {}", e);
+            Error::TaskExecution("Failed to access alerts".to_string())
+        })?;
+        alerts.set_callback(Arc::new(callback));
+        Ok(())
+    }
+
+    /// Must be called without holding any other processor lock, as the
+    /// callback may read the processor
+    fn check_alerts(&self, metrics: &GeometricMetrics) {
+        let (raised, callback) = {
+            let mut alerts = self.alerts.lock().unwrap_or_else(PoisonError::into_inner);
+            (alerts.evaluate(metrics), alerts.callback())
         };
 
-        let task_id = processor.submit_task(task).unwrap();
-        let result = processor.execute_task(task_id).unwrap();
+        for alert in raised {
+            warn!(
+                "Metric {} = {} crossed its {:?} threshold {}",
+                alert.field, alert.value, alert.bound, alert.threshold
+            );
+            if let Some(callback) = &callback {
+                callback(alert);
+            }
+        }
+    }
 
-        assert!(result.success);
-        assert!(result.metrics.v_geometric > 1.0);
+    /// Metric snapshots, oldest first; index 0 is the baseline until the
+    /// buffer wraps after `METRICS_HISTORY_CAPACITY` entries
+    pub fn metrics_history(&self) -> Result<Vec<GeometricMetrics>> {
+        let history = self.history.lock().map_err(|e| {
+            error!("Failed to lock metrics history: {}", e);
+            Error::TaskExecution("Failed to access metrics history".to_string())
+        })?;
 
-        let status = processor.get_task_status(task_id).unwrap();
-        assert!(matches!(status, TaskStatus::Completed(_)));
+        Ok(history.iter().map(|(_, metrics)| metrics.clone()).collect())
     }
 
-    #[test]
-    fn test_metrics_consistency() {
-        let processor = SemanticTaskProcessor::new();
-        let initial_metrics = processor.get_metrics().unwrap();
+    /// Metric snapshots paired with the time they were recorded, oldest first
+    pub fn timestamped_history(&self) -> Result<Vec<TimestampedMetrics>> {
+        let history = self.history.lock().map_err(|e| {
+            error!("Failed to lock metrics history: {}", e);
+            Error::TaskExecution("Failed to access metrics history".to_string())
+        })?;
 
-        let task = GeometricTaskCommand {
-            task_name: "Test Task".to_string(),
-            geometric_operator: GeometricOperator::QuaternionRotation,
-            target_module: "test_module".to_string(),
-            parameters: serde_json::json!({}),
-            expected_output_metric: "v_geometric".to_string(),
-            task_id: None,
-        };
+        Ok(history.iter().cloned().collect())
+    }
 
-        let task_id = processor.submit_task(task).unwrap();
-        let _ = processor.execute_task(task_id).unwrap();
+    /// History snapshots recorded after the one with ID `id`, oldest first,
+    /// each with its ID. IDs increase by one per snapshot and match those
+    /// delivered by [`subscribe_metrics`](Self::subscribe_metrics); a gap
+    /// before the first returned ID means those snapshots were evicted.
+    pub fn history_after(&self, id: u64) -> Result<Vec<(u64, GeometricMetrics)>> {
+        let history = self.history.lock().map_err(|e| {
+            error!("Failed to lock metrics history: {}", e);
+            Error::TaskExecution("Failed to access metrics history".to_string())
+        })?;
 
-        let updated_metrics = processor.get_metrics().unwrap();
+        Ok(history
+            .after(id)
+            .map(|(id, (_, metrics))| (id, metrics.clone()))
+            .collect())
+    }
 
-        assert!(updated_metrics.v_geometric > initial_metrics.v_geometric);
-        assert!(updated_metrics.s_geometric >= initial_metrics.s_geometric);
+    /// Number of snapshots currently held in the history buffer
+    pub fn history_len(&self) -> Result<usize> {
+        let history = self.history.lock().map_err(|e| {
+            error!("Failed to lock metrics history: {}", e);
+            Error::TaskExecution("Failed to access metrics history".to_string())
+        })?;
+
+        Ok(history.len())
+    }
+
+    /// Scalar fields of the latest snapshot whose z-score against the rolling
+    /// history exceeds `z_threshold`
+    pub fn detect_anomalies(&self, z_threshold: f64) -> Result<Vec<Anomaly>> {
+        let history = self.metrics_history()?;
+        Ok(detect_anomalies(&history, z_threshold))
+    }
+
+    fn record_history(&self, metrics: &GeometricMetrics) -> Result<()> {
+        let mut history = self.history.lock().map_err(|e| {
+            error!("Failed to lock metrics history: {}", e);
+            Error::TaskExecution("Failed to access metrics history".to_string())
+        })?;
+
+        let id = history.push((self.config.clock.now(), metrics.clone()));
+        self.broadcast.publish(id, metrics);
+        Ok(())
+    }
+
+    /// Receive every metrics snapshot recorded from now on. Subscribers that
+    /// fall behind lose the oldest snapshots rather than growing a buffer.
+    pub fn subscribe_metrics(&self) -> MetricsSubscription {
+        self.broadcast.subscribe()
+    }
+
+    /// Number of executions per operator, including operators never used
+    pub fn operator_stats(&self) -> Result<HashMap<GeometricOperator, u64>> {
+        let counts = self.operator_counts.lock().map_err(|e| {
+            error!("Failed to lock operator counts: {}", e);
+            Error::TaskExecution("Failed to access operator statistics".to_string())
+        })?;
+
+        Ok(GeometricOperator::ALL
+            .iter()
+            .map(|op| (*op, counts.get(op).copied().unwrap_or(0)))
+            .collect())
+    }
+
+    /// Remove a task regardless of its status; returns false if it was unknown
+    pub fn remove_task(&self, task_id: Uuid) -> Result<bool> {
+        let mut tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let Some(info) = tasks.remove(&task_id) else {
+            return Ok(false);
+        };
+        if let (TaskStatus::Completed(_), Some(finished_at)) = (&info.status, info.finished_at) {
+            match self.completed.lock() {
+                Ok(mut completed) => {
+                    completed.remove(&(finished_at, info.sequence));
+                }
+                Err(e) => error!("Failed to lock completed tasks: {}", e),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Drop completed and failed tasks, returning how many were removed
+    pub fn prune_finished(&self) -> Result<usize> {
+        let mut tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let before = tasks.len();
+        tasks.retain(|_, info| {
+            !matches!(
+                info.status,
+                TaskStatus::Completed(_) | TaskStatus::Failed(_)
+            )
+        });
+        let pruned = before - tasks.len();
+        if pruned > 0 {
+            self.sync_completed(&tasks);
+            info!("Pruned {} finished tasks", pruned);
+        }
+
+        Ok(pruned)
+    }
+
+    /// Drop tasks that finished at least `max_age` ago by the processor's
+    /// clock, returning how many were removed
+    pub fn prune_finished_older_than(&self, max_age: Duration) -> Result<usize> {
+        let mut tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let now = self.config.clock.now();
+        let before = tasks.len();
+        tasks.retain(|_, info| {
+            !info
+                .finished_at
+                .is_some_and(|finished_at| has_elapsed(finished_at, now, max_age))
+        });
+        let pruned = before - tasks.len();
+        if pruned > 0 {
+            self.sync_completed(&tasks);
+            info!("Pruned {} tasks finished over {:?} ago", pruned, max_age);
+        }
+
+        Ok(pruned)
+    }
+
+    /// Record a newly completed task under `key` and drop the earliest
+    /// completed tasks beyond `max_completed_tasks`, by completion time and
+    /// then submission order. Failed and unfinished tasks are never evicted.
+    fn evict_completed(
+        &self,
+        tasks: &mut HashMap<Uuid, TaskInfo>,
+        key: (DateTime<Utc>, u64),
+        task_id: Uuid,
+    ) {
+        let mut completed = match self.completed.lock() {
+            Ok(completed) => completed,
+            Err(e) => {
+                error!("Failed to lock completed tasks: {}", e);
+                return;
+            }
+        };
+        completed.insert(key, task_id);
+
+        let mut evicted = 0;
+        while completed.len() > self.config.max_completed_tasks {
+            let Some((_, id)) = completed.pop_first() else {
+                break;
+            };
+            tasks.remove(&id);
+            evicted += 1;
+        }
+        if evicted > 0 {
+            info!("Evicted {} completed tasks over the retention cap", evicted);
+        }
+    }
+
+    /// Drop entries for tasks no longer completed in `tasks` from the
+    /// completed queue, after tasks were removed in bulk
+    fn sync_completed(&self, tasks: &HashMap<Uuid, TaskInfo>) {
+        match self.completed.lock() {
+            Ok(mut completed) => completed.retain(|_, id| {
+                tasks
+                    .get(id)
+                    .is_some_and(|info| matches!(info.status, TaskStatus::Completed(_)))
+            }),
+            Err(e) => error!("Failed to lock completed tasks: {}", e),
+        }
+    }
+
+    /// Mark tasks that have been `InProgress` for at least `max_age` by the
+    /// processor's clock as `Failed("stale")`, returning their IDs. Such a
+    /// task's executor is assumed to have died.
+    pub fn fail_stale(&self, max_age: Duration) -> Result<Vec<Uuid>> {
+        let mut tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let now = self.config.clock.now();
+        let mut reaped = Vec::new();
+        for (id, info) in tasks.iter_mut() {
+            let stale = info.status == TaskStatus::InProgress
+                && info
+                    .started_at
+                    .is_some_and(|started_at| has_elapsed(started_at, now, max_age));
+            if stale {
+                info.status = TaskStatus::Failed("stale".to_string());
+                info.finished_at = Some(now);
+                reaped.push(*id);
+            }
+        }
+        drop(tasks);
+        for id in &reaped {
+            self.notify(
+                *id,
+                TaskChange::Status(TaskStatus::Failed("stale".to_string())),
+            );
+        }
+        if !reaped.is_empty() {
+            warn!(
+                "Failed {} tasks in progress for over {:?}",
+                reaped.len(),
+                max_age
+            );
+        }
+
+        Ok(reaped)
+    }
+
+    /// Put a task into `InProgress` as an executor that then dies would
+    #[cfg(test)]
+    pub(crate) fn mark_in_progress(&self, task_id: Uuid) {
+        let mut tasks = self.tasks.lock().unwrap();
+        let info = tasks.get_mut(&task_id).unwrap();
+        info.status = TaskStatus::InProgress;
+        info.started_at = Some(self.config.clock.now());
+    }
+
+    /// Overwrite the current metrics, bypassing execution and history
+    #[cfg(test)]
+    pub(crate) fn set_metrics(&self, metrics: GeometricMetrics) {
+        self.store_last_good_metrics(&metrics);
+        *self.metrics.lock().unwrap() = metrics;
+    }
+
+    /// Number of tracked tasks
+    pub fn len(&self) -> Result<usize> {
+        let tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        Ok(tasks.len())
+    }
+
+    /// Returns true if no tasks are tracked
+    pub fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Number of tasks in each state, including states with no tasks
+    pub fn status_counts(&self) -> Result<HashMap<TaskStatusKind, usize>> {
+        let tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let mut counts: HashMap<TaskStatusKind, usize> =
+            TaskStatusKind::ALL.iter().map(|kind| (*kind, 0)).collect();
+        for info in tasks.values() {
+            *counts.entry(info.status.kind()).or_default() += 1;
+        }
+        Ok(counts)
+    }
+
+    /// List all known tasks with their statuses, oldest submission first.
+    /// The order is stable across calls.
+    pub fn list_tasks(&self) -> Result<Vec<(Uuid, TaskStatus)>> {
+        let tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let mut listed: Vec<_> = tasks.iter().collect();
+        listed.sort_by_key(|(_, info)| info.sequence);
+        Ok(listed
+            .into_iter()
+            .map(|(id, info)| (*id, info.status.clone()))
+            .collect())
+    }
+
+    /// Tasks carrying `tag`, in submission order; untagged tasks never match
+    pub fn list_tasks_by_tag(&self, tag: &str) -> Result<Vec<(Uuid, TaskStatus)>> {
+        let tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let mut listed: Vec<_> = tasks
+            .iter()
+            .filter(|(_, info)| info.command.tags.iter().any(|t| t == tag))
+            .collect();
+        listed.sort_by_key(|(_, info)| info.sequence);
+        Ok(listed
+            .into_iter()
+            .map(|(id, info)| (*id, info.status.clone()))
+            .collect())
+    }
+
+    /// IDs of all tasks in submission order, without cloning their statuses
+    pub fn task_ids(&self) -> Result<Vec<Uuid>> {
+        let tasks = self.tasks.lock().map_err(|e| {
+            error!("Failed to lock tasks: {}", e);
+            Error::TaskExecution("Failed to access task storage".to_string())
+        })?;
+
+        let mut listed: Vec<_> = tasks.iter().collect();
+        listed.sort_by_key(|(_, info)| info.sequence);
+        Ok(listed.into_iter().map(|(id, _)| *id).collect())
+    }
+
+    /// Register a semantic anchor; its ID must not already be registered
+    pub fn add_anchor(&self, anchor: SemanticAnchor) -> Result<Uuid> {
+        let mut anchors = self.anchors.lock().map_err(|e| {
+            error!("Failed to lock anchors: {}", e);
+            Error::TaskExecution("Failed to access anchor storage".to_string())
+        })?;
+
+        if anchors.iter().any(|existing| existing.id == anchor.id) {
+            return Err(Error::InvalidParameter(
+                "id".into(),
+                format!("anchor {} already exists", anchor.id),
+            ));
+        }
+
+        let id = anchor.id;
+        info!("Registered anchor {}: {}", id, anchor.name);
+        anchors.push(anchor);
+        Ok(id)
+    }
+
+    /// Registered anchors in registration order
+    pub fn list_anchors(&self) -> Result<Vec<SemanticAnchor>> {
+        let anchors = self.anchors.lock().map_err(|e| {
+            error!("Failed to lock anchors: {}", e);
+            Error::TaskExecution("Failed to access anchor storage".to_string())
+        })?;
+
+        Ok(anchors.clone())
+    }
+
+    /// Unregister an anchor, returning it; `None` if it was unknown
+    pub fn remove_anchor(&self, id: Uuid) -> Result<Option<SemanticAnchor>> {
+        let mut anchors = self.anchors.lock().map_err(|e| {
+            error!("Failed to lock anchors: {}", e);
+            Error::TaskExecution("Failed to access anchor storage".to_string())
+        })?;
+
+        Ok(anchors
+            .iter()
+            .position(|anchor| anchor.id == id)
+            .map(|index| anchors.remove(index)))
+    }
+
+    /// Register `template` under `name`, replacing any template already
+    /// registered with that name
+    pub fn register_template(&self, name: String, template: GeometricTaskCommand) -> Result<()> {
+        if name.trim().is_empty() {
+            return Err(Error::InvalidParameter(
+                "name".into(),
+                "must not be empty".into(),
+            ));
+        }
+
+        let mut templates = self.templates.lock().map_err(|e| {
+            error!("Failed to lock templates: {}", e);
+            Error::TaskExecution("Failed to access template storage".to_string())
+        })?;
+        info!("Registered template {}", name);
+        templates.insert(name, template);
+        Ok(())
+    }
+
+    /// A copy of the template registered as `name` with `overrides` merged
+    /// into its parameters, or `None` if there is no such template.
+    ///
+    /// Objects are merged key by key, recursively; any other override value
+    /// replaces the template's. `null` overrides nothing. The copy never
+    /// carries the template's explicit `task_id`, so instances don't collide.
+    pub fn instantiate(
+        &self,
+        name: &str,
+        overrides: &serde_json::Value,
+    ) -> Result<Option<GeometricTaskCommand>> {
+        if !(overrides.is_object() || overrides.is_null()) {
+            return Err(Error::InvalidParameter(
+                "overrides".into(),
+                "must be a JSON object".into(),
+            ));
+        }
+
+        let templates = self.templates.lock().map_err(|e| {
+            error!("Failed to lock templates: {}", e);
+            Error::TaskExecution("Failed to access template storage".to_string())
+        })?;
+        let Some(template) = templates.get(name) else {
+            return Ok(None);
+        };
+
+        let mut command = template.clone();
+        command.task_id = None;
+        merge_json(&mut command.parameters, overrides);
+        Ok(Some(command))
+    }
+}
+
+/// Merge `overrides` into `target`: objects key by key, recursively, and
+/// anything else by replacement. A `null` override leaves `target` alone.
+fn merge_json(target: &mut serde_json::Value, overrides: &serde_json::Value) {
+    match (target, overrides) {
+        (_, serde_json::Value::Null) => {}
+        (serde_json::Value::Object(target), serde_json::Value::Object(overrides)) => {
+            for (key, value) in overrides {
+                match target.get_mut(key) {
+                    Some(existing) => merge_json(existing, value),
+                    None => {
+                        target.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (target, overrides) => *target = overrides.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::MockClock;
+    use crate::core::types::GeometricOperator;
+
+    fn fast_processor() -> SemanticTaskProcessor {
+        SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_task_submission() {
+        let processor = fast_processor();
+        let task = GeometricTaskCommand {
+            task_name: "Test Task".to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({}),
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let task_id = processor.submit_task(task).unwrap();
+        let status = processor.get_task_status(task_id).unwrap();
+
+        assert!(matches!(status, TaskStatus::Pending));
+    }
+
+    #[test]
+    fn test_task_execution() {
+        let processor = fast_processor();
+        let task = GeometricTaskCommand {
+            task_name: "Test Task".to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({}),
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let initial_metrics = processor.get_metrics().unwrap();
+        let task_id = processor.submit_task(task).unwrap();
+        let result = processor.execute_task(task_id).unwrap();
+
+        assert!(result.success);
+        // v_geometric tracks quaternion coherence, which is bounded below 1.0
+        assert!(result.metrics.v_geometric > initial_metrics.v_geometric);
+        assert_eq!(
+            result.metrics.v_geometric,
+            result.metrics.quaternion_coherence
+        );
+
+        let status = processor.get_task_status(task_id).unwrap();
+        assert!(matches!(status, TaskStatus::Completed(_)));
+    }
+
+    fn derivation_expecting(metric: &str) -> GeometricTaskCommand {
+        GeometricTaskCommand {
+            task_name: "Derivation".to_string(),
+            geometric_operator: GeometricOperator::GeometricDerivation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({ "delta": 0.5 }),
+            expected_output_metric: metric.to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_anchor_round_trip() {
+        let processor = fast_processor();
+        let anchor = |name: &str| SemanticAnchor {
+            id: Uuid::new_v4(),
+            name: name.to_string(),
+            description: String::new(),
+            position: [0.0, 0.0, 0.0, 1.0],
+            metadata: serde_json::json!({}),
+        };
+
+        let first = anchor("first");
+        let second = anchor("second");
+        processor.add_anchor(first.clone()).unwrap();
+        processor.add_anchor(second.clone()).unwrap();
+        assert!(matches!(
+            processor.add_anchor(first.clone()),
+            Err(Error::InvalidParameter(..))
+        ));
+        assert_eq!(
+            processor.list_anchors().unwrap(),
+            vec![first.clone(), second.clone()]
+        );
+
+        assert_eq!(
+            processor.remove_anchor(first.id).unwrap(),
+            Some(first.clone())
+        );
+        assert_eq!(processor.remove_anchor(first.id).unwrap(), None);
+        assert_eq!(processor.list_anchors().unwrap(), vec![second]);
+    }
+
+    #[test]
+    fn test_list_tasks_in_submission_order() {
+        let processor = fast_processor();
+        let ids: Vec<Uuid> = (0..8)
+            .map(|_| {
+                processor
+                    .submit_task(derivation_expecting("s_geometric"))
+                    .unwrap()
+            })
+            .collect();
+        processor.execute_task(ids[3]).unwrap();
+
+        for _ in 0..3 {
+            let listed: Vec<Uuid> = processor
+                .list_tasks()
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect();
+            assert_eq!(listed, ids);
+        }
+    }
+
+    #[test]
+    fn test_list_tasks_by_tag() {
+        let processor = fast_processor();
+        let tagged = |name: &str, tags: &[&str]| GeometricTaskCommand {
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..command(name, serde_json::json!({}))
+        };
+        let a = processor.submit_task(tagged("a", &["exp-1"])).unwrap();
+        processor.submit_task(tagged("b", &["exp-2"])).unwrap();
+        let c = processor
+            .submit_task(tagged("c", &["exp-2", "exp-1"]))
+            .unwrap();
+        processor.submit_task(tagged("untagged", &[])).unwrap();
+
+        let ids = |tag| -> Vec<Uuid> {
+            processor
+                .list_tasks_by_tag(tag)
+                .unwrap()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect()
+        };
+        assert_eq!(ids("exp-1"), vec![a, c]);
+        assert_eq!(ids("exp-2").len(), 2);
+        assert!(ids("").is_empty());
+        assert!(ids("exp").is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_concurrent_execute_task_runs_once() {
+        let processor = Arc::new(SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::from_millis(50),
+            ..Default::default()
+        }));
+        let task_id = processor
+            .submit_task(derivation_expecting("s_geometric"))
+            .unwrap();
+        let before = processor.history_len().unwrap();
+
+        let run = || {
+            let processor = processor.clone();
+            tokio::task::spawn_blocking(move || processor.execute_task(task_id))
+        };
+        let (first, second) = tokio::join!(run(), run());
+        let outcomes = [first.unwrap(), second.unwrap()];
+
+        assert_eq!(outcomes.iter().filter(|r| r.is_ok()).count(), 1);
+        let rejected = outcomes.iter().find_map(|r| r.as_ref().err()).unwrap();
+        assert!(
+            matches!(rejected, Error::TaskExecution(message) if message == "already running"),
+            "unexpected error: {rejected}"
+        );
+        assert_eq!(processor.history_len().unwrap(), before + 1);
+        assert!(matches!(
+            processor.get_task_status(task_id).unwrap(),
+            TaskStatus::Completed(_)
+        ));
+
+        // Running a finished task again is refused as well
+        assert!(matches!(
+            processor.execute_task(task_id),
+            Err(Error::TaskExecution(message)) if message == "already executed"
+        ));
+        assert_eq!(processor.history_len().unwrap(), before + 1);
+    }
+
+    #[test]
+    fn test_recompute_metrics_with_unchanged_rules_is_a_no_op() {
+        let processor = fast_processor();
+        for theta in [0.3, 0.9, 1.7] {
+            let id = processor
+                .submit_task(command("rotation", serde_json::json!({ "theta": theta })))
+                .unwrap();
+            processor.execute_task(id).unwrap();
+        }
+        let history = processor.metrics_history().unwrap();
+
+        let recomputed = processor.recompute_metrics().unwrap();
+        assert_eq!(&recomputed, history.last().unwrap());
+        assert_eq!(processor.metrics_history().unwrap(), history);
+
+        // Nothing left to replay after a reset
+        processor.reset_metrics().unwrap();
+        assert_eq!(
+            processor.recompute_metrics().unwrap(),
+            processor.get_metrics().unwrap()
+        );
+        assert_eq!(processor.history_len().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_initial_metrics_are_the_starting_point() {
+        let initial = GeometricMetrics {
+            v_geometric: 0.5,
+            ..SemanticTaskProcessor::baseline_metrics()
+        };
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            initial_metrics: Some(initial.clone()),
+            ..Default::default()
+        });
+        assert_eq!(processor.get_metrics().unwrap(), initial);
+        assert_eq!(processor.metrics_history().unwrap(), vec![initial.clone()]);
+
+        let id = processor
+            .submit_task(command("rotation", serde_json::json!({ "theta": 0.9 })))
+            .unwrap();
+        let executed = processor.execute_task(id).unwrap().metrics;
+        assert_ne!(executed, initial);
+
+        // Recomputing replays from the initial metrics, not the baseline
+        assert_eq!(processor.recompute_metrics().unwrap(), executed);
+        assert_eq!(
+            processor.metrics_history().unwrap(),
+            vec![initial, executed]
+        );
+
+        processor.reset_metrics().unwrap();
+        assert_eq!(
+            processor.recompute_metrics().unwrap(),
+            SemanticTaskProcessor::baseline_metrics()
+        );
+    }
+
+    #[test]
+    fn test_completed_tasks_beyond_cap_are_evicted_oldest_first() {
+        let clock = MockClock::default();
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            clock: Arc::new(clock.clone()),
+            max_completed_tasks: 3,
+            ..Default::default()
+        });
+        let failing = processor
+            .submit_task(derivation_expecting("no_such_metric"))
+            .unwrap();
+        assert!(processor.execute_task(failing).is_err());
+
+        let mut completed = Vec::new();
+        for i in 0..5 {
+            let task_id = processor
+                .submit_task(command(&format!("Task {}", i), serde_json::json!({})))
+                .unwrap();
+            processor.execute_task(task_id).unwrap();
+            completed.push(task_id);
+            clock.advance(Duration::from_secs(1));
+        }
+        let pending = processor
+            .submit_task(command("Pending", serde_json::json!({})))
+            .unwrap();
+
+        let counts = processor.status_counts().unwrap();
+        assert_eq!(counts[&TaskStatusKind::Completed], 3);
+        for evicted in &completed[..2] {
+            assert!(processor.get_task_status(*evicted).is_err());
+        }
+        for kept in completed[2..].iter().chain([&failing, &pending]) {
+            assert!(processor.get_task_status(*kept).is_ok());
+        }
+
+        // A removed task frees its slot instead of counting towards the cap
+        assert!(processor.remove_task(completed[2]).unwrap());
+        let task_id = processor
+            .submit_task(command("Task 5", serde_json::json!({})))
+            .unwrap();
+        processor.execute_task(task_id).unwrap();
+        for kept in [completed[3], completed[4], task_id] {
+            assert!(processor.get_task_status(kept).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_hooks_run_in_order_and_survive_panics() {
+        let processor = fast_processor();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+
+        let log = calls.clone();
+        processor
+            .add_pre_hook(Arc::new(move |command: &GeometricTaskCommand| {
+                log.lock()
+                    .unwrap()
+                    .push(format!("pre 1: {}", command.task_name));
+            }))
+            .unwrap();
+        processor
+            .add_pre_hook(Arc::new(|_: &GeometricTaskCommand| panic!("broken hook")))
+            .unwrap();
+        let log = calls.clone();
+        processor
+            .add_pre_hook(Arc::new(move |command: &GeometricTaskCommand| {
+                log.lock()
+                    .unwrap()
+                    .push(format!("pre 3: {}", command.task_name));
+            }))
+            .unwrap();
+        let log = calls.clone();
+        processor
+            .add_post_hook(Arc::new(move |result: &TaskExecutionResult| {
+                log.lock()
+                    .unwrap()
+                    .push(format!("post: {}", result.task_id));
+            }))
+            .unwrap();
+
+        let task_id = processor
+            .submit_task(derivation_expecting("s_geometric"))
+            .unwrap();
+        let result = processor.execute_task(task_id).unwrap();
+        assert!(result.success);
+
+        let name = derivation_expecting("s_geometric").task_name;
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec![
+                format!("pre 1: {}", name),
+                format!("pre 3: {}", name),
+                format!("post: {}", task_id),
+            ]
+        );
+        // The processor is still usable after the panic
+        assert!(matches!(
+            processor.get_task_status(task_id).unwrap(),
+            TaskStatus::Completed(_)
+        ));
+        processor.get_metrics().unwrap();
+    }
+
+    #[test]
+    fn test_instantiate_template_merges_overrides() {
+        let processor = fast_processor();
+        let mut template = command(
+            "Sweep",
+            serde_json::json!({ "theta": 0.1, "axis": { "x": 0.0, "z": 1.0 } }),
+        );
+        template.task_id = Some(Uuid::new_v4());
+        processor
+            .register_template("sweep".into(), template.clone())
+            .unwrap();
+
+        let instance = processor
+            .instantiate(
+                "sweep",
+                &serde_json::json!({ "theta": 0.7, "axis": { "x": 1.0 } }),
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            instance.parameters,
+            serde_json::json!({ "theta": 0.7, "axis": { "x": 1.0, "z": 1.0 } })
+        );
+        assert_eq!(instance.task_id, None);
+        assert_eq!(instance.task_name, template.task_name);
+
+        assert!(processor
+            .instantiate("missing", &serde_json::Value::Null)
+            .unwrap()
+            .is_none());
+        assert!(processor
+            .instantiate("sweep", &serde_json::json!([1, 2]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_alert_fires_once_while_metric_stays_above_max() {
+        let processor = fast_processor();
+        let baseline = processor.get_metrics().unwrap().v_geometric;
+        let raised = Arc::new(Mutex::new(Vec::new()));
+        let sink = raised.clone();
+        processor
+            .on_alert(move |alert| sink.lock().unwrap().push(alert))
+            .unwrap();
+        processor
+            .set_alert("v_geometric".into(), None, Some(baseline - 1.0))
+            .unwrap();
+
+        for _ in 0..3 {
+            let task_id = processor
+                .submit_task(derivation_expecting("v_geometric"))
+                .unwrap();
+            processor.execute_task(task_id).unwrap();
+        }
+        processor.reset_metrics().unwrap();
+
+        let raised = raised.lock().unwrap();
+        assert_eq!(raised.len(), 1, "{raised:?}");
+        assert_eq!(raised[0].field, "v_geometric");
+        assert_eq!(raised[0].threshold, baseline - 1.0);
+    }
+
+    #[test]
+    fn test_poisoned_metrics_serve_last_known_good() {
+        let processor = fast_processor();
+        let task_id = processor
+            .submit_task(derivation_expecting("s_geometric"))
+            .unwrap();
+        let executed = processor.execute_task(task_id).unwrap().metrics;
+
+        // Panic while holding the lock, as a failed update would
+        let metrics = processor.metrics.clone();
+        let _ = std::thread::spawn(move || {
+            let mut metrics = metrics.lock().unwrap();
+            metrics.v_geometric = f64::NAN;
+            panic!("update failed halfway");
+        })
+        .join();
+
+        let snapshot = processor.metrics_snapshot().unwrap();
+        assert!(snapshot.stale);
+        assert_eq!(snapshot.metrics, executed);
+        assert_eq!(processor.get_metrics().unwrap(), executed);
+    }
+
+    #[test]
+    fn test_history_ids_survive_eviction_and_reset() {
+        let mut history = MetricsHistory::new((DateTime::UNIX_EPOCH, GeometricMetrics::default()));
+        for _ in 0..METRICS_HISTORY_CAPACITY + 5 {
+            history.push((DateTime::UNIX_EPOCH, GeometricMetrics::default()));
+        }
+        assert_eq!(history.len(), METRICS_HISTORY_CAPACITY);
+        assert_eq!(history.first_id, 6);
+
+        let ids: Vec<u64> = history.after(1027).map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![1028, 1029]);
+        assert_eq!(history.after(0).next().unwrap().0, 6);
+        assert_eq!(history.after(1029).count(), 0);
+        assert_eq!(history.after(u64::MAX).count(), 0);
+
+        history.clear();
+        let id = history.push((DateTime::UNIX_EPOCH, GeometricMetrics::default()));
+        assert_eq!(id, 1030);
+    }
+
+    #[test]
+    fn test_expected_metric_is_checked() {
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            require_metric_change: true,
+            ..Default::default()
+        });
+
+        let task_id = processor
+            .submit_task(derivation_expecting("s_geometric"))
+            .unwrap();
+        let result = processor.execute_task(task_id).unwrap();
+        assert!(result.success);
+        assert_eq!(result.error, None);
+
+        // Derivation leaves v_geometric alone
+        let task_id = processor
+            .submit_task(derivation_expecting("v_geometric"))
+            .unwrap();
+        let result = processor.execute_task(task_id).unwrap();
+        assert!(!result.success);
+        assert_eq!(
+            result.error.as_deref(),
+            Some("expected metric 'v_geometric' did not change")
+        );
+        assert!(matches!(
+            processor.get_task_status(task_id).unwrap(),
+            TaskStatus::Failed(_)
+        ));
+    }
+
+    #[test]
+    fn test_unknown_expected_metric_fails_task() {
+        let processor = fast_processor();
+        let task_id = processor
+            .submit_task(derivation_expecting("warp_factor"))
+            .unwrap();
+
+        match processor.execute_task(task_id) {
+            Err(Error::InvalidParameter(name, message)) => {
+                assert_eq!(name, "expected_output_metric");
+                assert_eq!(message, "unknown metric 'warp_factor'");
+            }
+            other => panic!("expected InvalidParameter, got {:?}", other),
+        }
+        assert_eq!(
+            processor.get_task_status(task_id).unwrap(),
+            TaskStatus::Failed("unknown metric 'warp_factor'".to_string())
+        );
+    }
+
+    #[test]
+    fn test_unknown_expected_metric_applies_nothing() {
+        let processor = fast_processor();
+        let metrics = processor.get_metrics().unwrap();
+        let history = processor.history_len().unwrap();
+
+        let task_id = processor
+            .submit_task(derivation_expecting("warp_factor"))
+            .unwrap();
+        assert!(processor.execute_task(task_id).is_err());
+
+        assert_eq!(processor.get_metrics().unwrap(), metrics);
+        assert_eq!(processor.history_len().unwrap(), history);
+        assert!(processor
+            .operator_stats()
+            .unwrap()
+            .values()
+            .all(|count| *count == 0));
+
+        // A custom metric the operator creates is known once it has run
+        let task_id = processor
+            .submit_task(GeometricTaskCommand::semantic_synthesis(
+                "Synthesis",
+                "probe",
+                0.5,
+            ))
+            .unwrap();
+        assert!(processor.execute_task(task_id).unwrap().success);
+    }
+
+    #[test]
+    fn test_execution_output_lists_rule_contributions() {
+        let processor = fast_processor();
+        let task = GeometricTaskCommand {
+            task_name: "Derivation".to_string(),
+            geometric_operator: GeometricOperator::GeometricDerivation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({ "delta": 0.5 }),
+            expected_output_metric: "s_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let task_id = processor.submit_task(task).unwrap();
+        let result = processor.execute_task(task_id).unwrap();
+
+        let contributions: Vec<RuleContribution> =
+            serde_json::from_value(result.output["contributions"].clone()).unwrap();
+        assert!(contributions
+            .iter()
+            .any(|c| c.rule == "geometric_derivation" && c.field == "s_geometric"));
+    }
+
+    #[test]
+    fn test_get_task_returns_submitted_command() {
+        let processor = fast_processor();
+        let task = GeometricTaskCommand {
+            task_name: "Audit".to_string(),
+            geometric_operator: GeometricOperator::Zitterbewegung,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({ "frequency_scale": 2.0 }),
+            expected_output_metric: "q_oscillator".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let task_id = processor.submit_task(task.clone()).unwrap();
+        let (command, status) = processor.get_task(task_id).unwrap();
+        assert_eq!(command, task);
+        assert_eq!(status, TaskStatus::Pending);
+
+        let unknown = Uuid::new_v4();
+        assert!(matches!(
+            processor.get_task(unknown),
+            Err(Error::TaskNotFound(id)) if id == unknown
+        ));
+    }
+
+    #[test]
+    fn test_metrics_consistency() {
+        let processor = fast_processor();
+        let initial_metrics = processor.get_metrics().unwrap();
+
+        let task = GeometricTaskCommand {
+            task_name: "Test Task".to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({}),
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let task_id = processor.submit_task(task).unwrap();
+        let _ = processor.execute_task(task_id).unwrap();
+
+        let updated_metrics = processor.get_metrics().unwrap();
+
+        assert!(updated_metrics.v_geometric > initial_metrics.v_geometric);
+        assert!(updated_metrics.s_geometric >= initial_metrics.s_geometric);
         assert!(updated_metrics.q_oscillator >= initial_metrics.q_oscillator);
     }
+
+    #[test]
+    fn test_reset_metrics_restores_baseline() {
+        let processor = fast_processor();
+        let task = GeometricTaskCommand {
+            task_name: "Test Task".to_string(),
+            geometric_operator: GeometricOperator::SemanticSynthesis,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({ "anchor": "reset-probe" }),
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let task_id = processor.submit_task(task).unwrap();
+        let _ = processor.execute_task(task_id).unwrap();
+        assert!(!processor.get_metrics().unwrap().custom_metrics.is_empty());
+
+        processor.reset_metrics().unwrap();
+
+        let metrics = processor.get_metrics().unwrap();
+        assert_eq!(metrics, SemanticTaskProcessor::baseline_metrics());
+        assert!(metrics.custom_metrics.is_empty());
+        assert_eq!(processor.metrics_history().unwrap(), vec![metrics]);
+    }
+
+    #[test]
+    fn test_replay_task() {
+        let processor = fast_processor();
+        let task = GeometricTaskCommand {
+            task_name: "Test Task".to_string(),
+            geometric_operator: GeometricOperator::GeometricDerivation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({ "delta": 0.5 }),
+            expected_output_metric: "s_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let task_id = processor.submit_task(task).unwrap();
+        processor.execute_task(task_id).unwrap();
+
+        let replay = processor.replay_task(task_id).unwrap();
+        assert!(replay.success);
+        assert_ne!(replay.task_id, task_id);
+        assert_eq!(replay.replayed_from, Some(task_id));
+        assert!(matches!(
+            processor.get_task_status(replay.task_id).unwrap(),
+            TaskStatus::Completed(_)
+        ));
+
+        let unknown = Uuid::new_v4();
+        assert!(matches!(
+            processor.replay_task(unknown),
+            Err(Error::TaskNotFound(id)) if id == unknown
+        ));
+    }
+
+    #[test]
+    fn test_operator_stats() {
+        let processor = fast_processor();
+        let operators = [
+            GeometricOperator::QuaternionRotation,
+            GeometricOperator::Zitterbewegung,
+            GeometricOperator::QuaternionRotation,
+            GeometricOperator::GeometricDerivation,
+            GeometricOperator::QuaternionRotation,
+        ];
+
+        for op in operators {
+            let task = GeometricTaskCommand {
+                task_name: "Test Task".to_string(),
+                geometric_operator: op,
+                target_module: "test_module".to_string(),
+                parameters: serde_json::json!({}),
+                expected_output_metric: "v_geometric".to_string(),
+                task_id: None,
+                execute_at: None,
+                tags: Vec::new(),
+            };
+            let task_id = processor.submit_task(task).unwrap();
+            processor.execute_task(task_id).unwrap();
+        }
+
+        let stats = processor.operator_stats().unwrap();
+        assert_eq!(stats.len(), 4);
+        assert_eq!(stats[&GeometricOperator::QuaternionRotation], 3);
+        assert_eq!(stats[&GeometricOperator::Zitterbewegung], 1);
+        assert_eq!(stats[&GeometricOperator::GeometricDerivation], 1);
+        assert_eq!(stats[&GeometricOperator::SemanticSynthesis], 0);
+    }
+
+    #[test]
+    fn test_empty_processor_edge_cases() {
+        let processor = fast_processor();
+
+        assert_eq!(processor.len().unwrap(), 0);
+        assert!(processor.is_empty().unwrap());
+        assert!(processor.list_tasks().unwrap().is_empty());
+        assert_eq!(processor.prune_finished().unwrap(), 0);
+        assert!(processor.execute_next().unwrap().is_none());
+        assert!(processor.execute_ready().unwrap().is_empty());
+        assert!(!processor.remove_task(Uuid::new_v4()).unwrap());
+    }
+
+    #[test]
+    fn test_task_accessors_after_submit_and_remove() {
+        let processor = fast_processor();
+        let mut ids = Vec::new();
+        for name in ["First", "Second", "Third"] {
+            let task = GeometricTaskCommand {
+                task_name: name.to_string(),
+                geometric_operator: GeometricOperator::QuaternionRotation,
+                target_module: "test_module".to_string(),
+                parameters: serde_json::json!({}),
+                expected_output_metric: "v_geometric".to_string(),
+                task_id: None,
+                execute_at: None,
+                tags: Vec::new(),
+            };
+            ids.push(processor.submit_task(task).unwrap());
+        }
+        assert_eq!(processor.len().unwrap(), 3);
+        assert!(!processor.is_empty().unwrap());
+
+        assert!(processor.remove_task(ids[1]).unwrap());
+        assert_eq!(processor.len().unwrap(), 2);
+
+        let next = processor.execute_next().unwrap().unwrap();
+        assert_eq!(next.task_id, ids[0]);
+        let rest = processor.execute_ready().unwrap();
+        assert_eq!(
+            rest.iter().map(|r| r.task_id).collect::<Vec<_>>(),
+            vec![ids[2]]
+        );
+        assert!(processor.execute_next().unwrap().is_none());
+
+        assert_eq!(processor.prune_finished().unwrap(), 2);
+        assert!(processor.is_empty().unwrap());
+    }
+
+    #[test]
+    fn test_zero_exec_delay_is_faster_than_default() {
+        fn timed_execute(processor: &SemanticTaskProcessor) -> Duration {
+            let task = GeometricTaskCommand {
+                task_id: None,
+                task_name: "Delay probe".to_string(),
+                geometric_operator: GeometricOperator::QuaternionRotation,
+                target_module: "sys7_core".to_string(),
+                parameters: serde_json::json!({}),
+                expected_output_metric: "v_geometric".to_string(),
+                execute_at: None,
+                tags: Vec::new(),
+            };
+            let task_id = processor.submit_task(task).unwrap();
+            let started = std::time::Instant::now();
+            processor.execute_task(task_id).unwrap();
+            started.elapsed()
+        }
+
+        let default_elapsed = timed_execute(&SemanticTaskProcessor::new());
+        let fast_elapsed = timed_execute(&fast_processor());
+
+        assert!(default_elapsed >= ProcessorConfig::default().exec_delay);
+        assert!(fast_elapsed * 10 < default_elapsed);
+    }
+
+    #[test]
+    fn test_status_counts() {
+        let processor = fast_processor();
+        let counts = processor.status_counts().unwrap();
+        assert_eq!(counts.len(), TaskStatusKind::ALL.len());
+        assert!(counts.values().all(|count| *count == 0));
+
+        let ids: Vec<Uuid> = (0..5)
+            .map(|i| {
+                processor
+                    .submit_task(GeometricTaskCommand {
+                        task_name: format!("Count {}", i),
+                        geometric_operator: GeometricOperator::Zitterbewegung,
+                        target_module: "test_module".to_string(),
+                        parameters: serde_json::json!({}),
+                        expected_output_metric: "v_geometric".to_string(),
+                        task_id: None,
+                        execute_at: None,
+                        tags: Vec::new(),
+                    })
+                    .unwrap()
+            })
+            .collect();
+        processor.execute_task(ids[0]).unwrap();
+        processor.execute_task(ids[1]).unwrap();
+        {
+            let mut tasks = processor.tasks.lock().unwrap();
+            tasks.get_mut(&ids[2]).unwrap().status = TaskStatus::InProgress;
+            tasks.get_mut(&ids[3]).unwrap().status = TaskStatus::Failed("boom".to_string());
+        }
+
+        let counts = processor.status_counts().unwrap();
+        assert_eq!(counts[&TaskStatusKind::Pending], 1);
+        assert_eq!(counts[&TaskStatusKind::InProgress], 1);
+        assert_eq!(counts[&TaskStatusKind::Completed], 2);
+        assert_eq!(counts[&TaskStatusKind::Failed], 1);
+        assert_eq!(processor.history_len().unwrap(), 3);
+    }
+
+    fn command(task_name: &str, parameters: serde_json::Value) -> GeometricTaskCommand {
+        GeometricTaskCommand {
+            task_name: task_name.to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "test_module".to_string(),
+            parameters,
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_deterministic_ids_collapse_identical_commands() {
+        let processor = fast_processor();
+        let first = command(
+            "Same",
+            serde_json::json!({ "theta": 0.5, "axis": [0.0, 0.0, 1.0] }),
+        );
+        let retry = command(
+            "Same",
+            serde_json::json!({ "axis": [0.0, 0.0, 1.0], "theta": 0.5 }),
+        );
+        assert_eq!(first.deterministic_id(), retry.deterministic_id());
+
+        let id = processor.submit_task_with(first, true).unwrap();
+        assert_eq!(id, retry.deterministic_id());
+        assert!(matches!(
+            processor.submit_task_with(retry.clone(), true),
+            Err(Error::TaskExecution(_))
+        ));
+        assert_eq!(processor.len().unwrap(), 1);
+
+        // Without the flag the retry still gets a fresh random ID
+        assert_ne!(processor.submit_task(retry).unwrap(), id);
+    }
+
+    #[test]
+    fn test_deterministic_ids_differ_with_content() {
+        let base = command("Same", serde_json::json!({ "theta": 0.5 }));
+        let other_params = command("Same", serde_json::json!({ "theta": 0.6 }));
+        let other_name = command("Other", serde_json::json!({ "theta": 0.5 }));
+        let mut other_operator = base.clone();
+        other_operator.geometric_operator = GeometricOperator::Zitterbewegung;
+
+        let id = base.deterministic_id();
+        assert_eq!(id.get_version_num(), 5);
+        assert_ne!(id, other_params.deterministic_id());
+        assert_ne!(id, other_name.deterministic_id());
+        assert_ne!(id, other_operator.deterministic_id());
+    }
+
+    #[test]
+    fn test_detect_anomalies_flags_outlier() {
+        let processor = fast_processor();
+        let baseline = processor.get_metrics().unwrap();
+        processor.history.lock().unwrap().clear();
+        for i in 0..20 {
+            let mut snapshot = baseline.clone();
+            // Small alternating jitter keeps every field's spread non-zero
+            let jitter = if i % 2 == 0 { 1e-3 } else { -1e-3 };
+            snapshot.v_geometric += jitter;
+            snapshot.s_geometric += jitter;
+            snapshot.q_oscillator += jitter;
+            snapshot.quaternion_coherence += jitter;
+            snapshot.emergent_electron_mass += jitter;
+            snapshot.fine_structure_constant += jitter;
+            snapshot.zitterbewegung_entropy += jitter;
+            snapshot.topological_winding += jitter;
+            processor.record_history(&snapshot).unwrap();
+        }
+        assert!(processor.detect_anomalies(3.0).unwrap().is_empty());
+
+        let mut outlier = processor.metrics_history().unwrap().pop().unwrap();
+        outlier.zitterbewegung_entropy += 0.5;
+        processor.record_history(&outlier).unwrap();
+
+        let anomalies = processor.detect_anomalies(3.0).unwrap();
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].field, "zitterbewegung_entropy");
+        assert_eq!(anomalies[0].value, outlier.zitterbewegung_entropy);
+        assert!(anomalies[0].z_score > 3.0);
+    }
+
+    #[test]
+    fn test_fail_stale_reaps_only_old_in_progress_tasks() {
+        let clock = MockClock::default();
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            clock: Arc::new(clock.clone()),
+            ..Default::default()
+        });
+        let task = |name: &str| GeometricTaskCommand {
+            task_name: name.to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({}),
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let stalled = processor.submit_task(task("Stalled")).unwrap();
+        processor.mark_in_progress(stalled);
+        clock.advance(Duration::from_secs(240));
+        let fresh = processor.submit_task(task("Fresh")).unwrap();
+        processor.mark_in_progress(fresh);
+        let pending = processor.submit_task(task("Pending")).unwrap();
+        clock.advance(Duration::from_secs(60));
+
+        let max_age = Duration::from_secs(300);
+        assert_eq!(processor.fail_stale(max_age).unwrap(), vec![stalled]);
+        assert_eq!(
+            processor.get_task_status(stalled).unwrap(),
+            TaskStatus::Failed("stale".to_string())
+        );
+        assert_eq!(
+            processor.get_task_status(fresh).unwrap(),
+            TaskStatus::InProgress
+        );
+        assert_eq!(
+            processor.get_task_status(pending).unwrap(),
+            TaskStatus::Pending
+        );
+
+        // The reaped task now counts as finished
+        assert_eq!(
+            processor.prune_finished_older_than(Duration::ZERO).unwrap(),
+            1
+        );
+        assert!(processor.fail_stale(max_age).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_prune_finished_older_than_with_mock_clock() {
+        let clock = MockClock::default();
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            clock: Arc::new(clock.clone()),
+            ..Default::default()
+        });
+        let task = |name: &str| GeometricTaskCommand {
+            task_name: name.to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "test_module".to_string(),
+            parameters: serde_json::json!({}),
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+
+        let old = processor.submit_task(task("Old")).unwrap();
+        processor.execute_task(old).unwrap();
+        clock.advance(Duration::from_secs(300));
+        let recent = processor.submit_task(task("Recent")).unwrap();
+        processor.execute_task(recent).unwrap();
+        let pending = processor.submit_task(task("Pending")).unwrap();
+        clock.advance(Duration::from_secs(60));
+
+        let two_minutes = Duration::from_secs(120);
+        let ten_minutes = two_minutes * 5;
+        assert_eq!(processor.prune_finished_older_than(ten_minutes).unwrap(), 0);
+        assert_eq!(processor.prune_finished_older_than(two_minutes).unwrap(), 1);
+        assert!(processor.get_task_status(old).is_err());
+        assert!(processor.get_task_status(recent).is_ok());
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(processor.prune_finished_older_than(two_minutes).unwrap(), 1);
+        assert_eq!(processor.len().unwrap(), 1);
+        assert!(processor.get_task_status(pending).is_ok());
+
+        // History timestamps come from the same clock
+        let history = processor.timestamped_history().unwrap();
+        assert_eq!(history[0].0, DateTime::UNIX_EPOCH);
+        assert_eq!(history[2].0 - history[1].0, chrono::Duration::seconds(300));
+    }
+
+    #[test]
+    fn test_detect_anomalies_needs_history() {
+        let processor = fast_processor();
+        assert!(processor.detect_anomalies(0.0).unwrap().is_empty());
+    }
 }
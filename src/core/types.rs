@@ -1,9 +1,12 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::core::semantic_task_processor::TaskStatus;
+
 /// Geometric operators for the MMSS system
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
 pub enum GeometricOperator {
     /// Quaternion rotation operator (⟲Q)
     QuaternionRotation,
@@ -15,8 +18,18 @@ pub enum GeometricOperator {
     SemanticSynthesis,
 }
 
+impl GeometricOperator {
+    /// Every operator, in declaration order
+    pub const ALL: [GeometricOperator; 4] = [
+        GeometricOperator::QuaternionRotation,
+        GeometricOperator::Zitterbewegung,
+        GeometricOperator::GeometricDerivation,
+        GeometricOperator::SemanticSynthesis,
+    ];
+}
+
 /// Geometric task command structure for LLM interaction
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct GeometricTaskCommand {
     /// Brief description of the task
     pub task_name: String,
@@ -31,10 +44,104 @@ pub struct GeometricTaskCommand {
     /// Optional task ID for tracking
     #[serde(skip_serializing_if = "Option::is_none")]
     pub task_id: Option<Uuid>,
+    /// Run the task at this time instead of on demand; it stays pending until then
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execute_at: Option<chrono::DateTime<chrono::Utc>>,
+    /// Free-form labels for grouping tasks, e.g. by experiment
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Namespace for content-derived task IDs
+pub const TASK_ID_NAMESPACE: Uuid = Uuid::from_u128(0x3f6c_9a1e_52d4_4b8e_9f07_c2a8_6e15_d9b3);
+
+impl GeometricTaskCommand {
+    /// UUIDv5 derived from the task name, operator and parameters, so retried
+    /// submissions of the same command get the same ID. Other fields, including
+    /// `task_id`, do not contribute.
+    pub fn deterministic_id(&self) -> Uuid {
+        let content = serde_json::json!({
+            "task_name": self.task_name,
+            "geometric_operator": self.geometric_operator,
+            "parameters": self.parameters,
+        });
+        Uuid::new_v5(&TASK_ID_NAMESPACE, content.to_string().as_bytes())
+    }
+
+    /// Rotate by `angle` radians about `axis`, watching `v_geometric`
+    pub fn quaternion_rotation(name: impl Into<String>, axis: [f64; 3], angle: f64) -> Self {
+        Self::with_operator(
+            name,
+            GeometricOperator::QuaternionRotation,
+            "sys7_core",
+            serde_json::json!({ "theta": angle, "axis": axis }),
+            "v_geometric",
+        )
+    }
+
+    /// Drive the zitterbewegung resonator at `frequency_scale` times its base
+    /// frequency, watching `topological_winding`
+    pub fn zitterbewegung(name: impl Into<String>, frequency_scale: f64) -> Self {
+        Self::with_operator(
+            name,
+            GeometricOperator::Zitterbewegung,
+            "sys6_resonator",
+            serde_json::json!({ "frequency_scale": frequency_scale }),
+            "topological_winding",
+        )
+    }
+
+    /// Shift geometric entropy by `delta`, watching `s_geometric`
+    pub fn geometric_derivation(name: impl Into<String>, delta: f64) -> Self {
+        Self::with_operator(
+            name,
+            GeometricOperator::GeometricDerivation,
+            "sys5_topology",
+            serde_json::json!({ "delta": delta }),
+            "s_geometric",
+        )
+    }
+
+    /// Synthesize a semantic anchor, watching its `anchor:<name>` custom metric
+    pub fn semantic_synthesis(
+        name: impl Into<String>,
+        anchor: impl Into<String>,
+        coherence_hint: f64,
+    ) -> Self {
+        let anchor = anchor.into();
+        let expected = format!("anchor:{}", anchor);
+        Self::with_operator(
+            name,
+            GeometricOperator::SemanticSynthesis,
+            "sys7_core",
+            serde_json::json!({ "anchor": anchor, "coherence_hint": coherence_hint }),
+            expected,
+        )
+    }
+
+    fn with_operator(
+        name: impl Into<String>,
+        geometric_operator: GeometricOperator,
+        target_module: &str,
+        parameters: serde_json::Value,
+        expected_output_metric: impl Into<String>,
+    ) -> Self {
+        Self {
+            task_name: name.into(),
+            geometric_operator,
+            target_module: target_module.to_string(),
+            parameters,
+            expected_output_metric: expected_output_metric.into(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        }
+    }
 }
 
 /// Quaternion type for geometric operations
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "crate::core::geometric_quaternion_core::QuaternionComponents")]
 pub struct Quaternion {
     pub w: f64,
     pub x: f64,
@@ -43,7 +150,7 @@ pub struct Quaternion {
 }
 
 /// Geometric metrics for system monitoring
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct GeometricMetrics {
     /// Geometric volume metric
     pub v_geometric: f64,
@@ -71,7 +178,7 @@ pub struct GeometricMetrics {
 }
 
 /// Semantic anchor for linguistic elements
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
 pub struct SemanticAnchor {
     pub id: Uuid,
     pub name: String,
@@ -81,13 +188,24 @@ pub struct SemanticAnchor {
 }
 
 /// Task execution result
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TaskExecutionResult {
     pub task_id: Uuid,
     pub success: bool,
     pub metrics: GeometricMetrics,
     pub output: serde_json::Value,
     pub error: Option<String>,
+    /// Task this execution replayed, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replayed_from: Option<Uuid>,
+}
+
+/// Outcome of submitting a task, and of executing it when requested
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct CreateTaskResponse {
+    pub task_id: Uuid,
+    pub status: TaskStatus,
+    pub execution_result: Option<TaskExecutionResult>,
 }
 
 /// System state snapshot
@@ -99,3 +217,43 @@ pub struct SystemState {
     pub active_anchors: Vec<SemanticAnchor>,
     pub active_tasks: Vec<Uuid>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::semantic_task_processor::{ProcessorConfig, SemanticTaskProcessor};
+    use crate::core::validation::validate_task_command;
+
+    #[test]
+    fn test_operator_builders_produce_valid_commands() {
+        let commands = [
+            GeometricTaskCommand::quaternion_rotation("rotate", [0.0, 0.0, 1.0], 0.5),
+            GeometricTaskCommand::zitterbewegung("resonate", 2.0),
+            GeometricTaskCommand::geometric_derivation("derive", 0.1),
+            GeometricTaskCommand::semantic_synthesis("synthesize", "quantum-atom", 0.8),
+        ];
+        let operators: Vec<_> = commands.iter().map(|c| c.geometric_operator).collect();
+        assert_eq!(operators, GeometricOperator::ALL);
+
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: std::time::Duration::ZERO,
+            ..Default::default()
+        });
+        for command in commands {
+            validate_task_command(&command).unwrap();
+            let json = serde_json::to_string(&command).unwrap();
+            let decoded: GeometricTaskCommand = serde_json::from_str(&json).unwrap();
+            assert_eq!(decoded, command);
+
+            // The watched metric exists once the operator has run
+            let task_id = processor.submit_task(command).unwrap();
+            assert!(processor.execute_task(task_id).unwrap().success);
+        }
+
+        let rotation = GeometricTaskCommand::quaternion_rotation("rotate", [1.0, 0.0, 0.0], 0.25);
+        assert_eq!(
+            rotation.parameters,
+            serde_json::json!({ "theta": 0.25, "axis": [1.0, 0.0, 0.0] })
+        );
+    }
+}
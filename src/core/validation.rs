@@ -0,0 +1,199 @@
+use serde_json::Value;
+
+use crate::core::error::{Error, FieldError, Result};
+use crate::core::types::{GeometricMetrics, GeometricOperator, GeometricTaskCommand};
+
+/// Check a task command before it is queued, collecting every problem
+/// rather than stopping at the first.
+pub fn validate_task_command(command: &GeometricTaskCommand) -> Result<()> {
+    let errors = command_errors(command);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Validation(errors))
+    }
+}
+
+/// Every problem [`validate_task_command`] reports, plus an
+/// `expected_output_metric` that no execution could produce.
+///
+/// Stricter than submission, which only finds an unknown metric when the
+/// task runs. Custom metrics are only known at run time, so any namespaced
+/// name such as `anchor:quantum-atom` is accepted.
+pub fn lint_task_command(command: &GeometricTaskCommand) -> Vec<FieldError> {
+    let mut errors = command_errors(command);
+
+    let metric = command.expected_output_metric.trim();
+    let is_scalar = GeometricMetrics::default()
+        .scalar_fields()
+        .iter()
+        .any(|(name, _)| *name == metric);
+    let is_custom = metric
+        .split_once(':')
+        .is_some_and(|(namespace, name)| !namespace.is_empty() && !name.is_empty());
+    if !metric.is_empty() && !is_scalar && !is_custom {
+        errors.push(FieldError::new(
+            "expected_output_metric",
+            format!("unknown metric '{}'", metric),
+        ));
+    }
+
+    errors
+}
+
+fn command_errors(command: &GeometricTaskCommand) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+
+    for (path, value) in [
+        ("task_name", &command.task_name),
+        ("target_module", &command.target_module),
+        ("expected_output_metric", &command.expected_output_metric),
+    ] {
+        if value.trim().is_empty() {
+            errors.push(FieldError::new(path, "must not be empty"));
+        }
+    }
+
+    validate_parameters(command.geometric_operator, &command.parameters, &mut errors);
+    errors
+}
+
+fn validate_parameters(op: GeometricOperator, params: &Value, errors: &mut Vec<FieldError>) {
+    let obj = match params {
+        Value::Object(obj) => obj,
+        Value::Null | Value::Number(_) => return,
+        _ => {
+            errors.push(FieldError::new(
+                "parameters",
+                "must be an object or a number",
+            ));
+            return;
+        }
+    };
+
+    let numeric: &[&str] = match op {
+        GeometricOperator::QuaternionRotation => &["theta"],
+        GeometricOperator::Zitterbewegung => &["frequency_scale"],
+        GeometricOperator::GeometricDerivation => &["delta"],
+        GeometricOperator::SemanticSynthesis => &["coherence_hint"],
+    };
+    for key in numeric {
+        if let Some(value) = obj.get(*key) {
+            if !value.is_number() {
+                errors.push(FieldError::new(
+                    format!("parameters.{}", key),
+                    "must be a number",
+                ));
+            }
+        }
+    }
+
+    match op {
+        GeometricOperator::QuaternionRotation => {
+            if let Some(axis) = obj.get("axis") {
+                validate_axis(axis, errors);
+            }
+        }
+        GeometricOperator::Zitterbewegung => {
+            if let Some(scale) = obj.get("frequency_scale").and_then(Value::as_f64) {
+                if scale <= 0.0 {
+                    errors.push(FieldError::new(
+                        "parameters.frequency_scale",
+                        "must be positive",
+                    ));
+                }
+            }
+        }
+        GeometricOperator::SemanticSynthesis => {
+            if let Some(anchor) = obj.get("anchor") {
+                if !anchor.is_string() {
+                    errors.push(FieldError::new("parameters.anchor", "must be a string"));
+                }
+            }
+        }
+        GeometricOperator::GeometricDerivation => {}
+    }
+}
+
+fn validate_axis(axis: &Value, errors: &mut Vec<FieldError>) {
+    let Some(components) = axis.as_array() else {
+        errors.push(FieldError::new(
+            "parameters.axis",
+            "must be an array of 3 numbers",
+        ));
+        return;
+    };
+
+    if components.len() != 3 {
+        errors.push(FieldError::new(
+            "parameters.axis",
+            "must have exactly 3 components",
+        ));
+    }
+    for (i, component) in components.iter().enumerate().take(3) {
+        if !component.is_number() {
+            errors.push(FieldError::new(
+                format!("parameters.axis[{}]", i),
+                "must be a number",
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn command(params: Value) -> GeometricTaskCommand {
+        GeometricTaskCommand {
+            task_name: "Rotate".to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "sys7_core".to_string(),
+            parameters: params,
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_valid_command_passes() {
+        assert!(
+            validate_task_command(&command(json!({ "theta": 0.5, "axis": [0, 1, 0] }))).is_ok()
+        );
+        assert!(validate_task_command(&command(json!(1.0))).is_ok());
+    }
+
+    #[test]
+    fn test_collects_every_field_error() {
+        let mut cmd = command(json!({ "theta": "fast", "axis": [0.0, 1.0, "z"] }));
+        cmd.task_name = " ".to_string();
+
+        let Err(Error::Validation(errors)) = validate_task_command(&cmd) else {
+            panic!("expected validation error");
+        };
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["task_name", "parameters.theta", "parameters.axis[2]"]
+        );
+    }
+
+    #[test]
+    fn test_lint_checks_expected_metric() {
+        let mut cmd = command(json!({ "theta": 0.5 }));
+        assert!(lint_task_command(&cmd).is_empty());
+
+        cmd.expected_output_metric = "anchor:quantum-atom".to_string();
+        assert!(lint_task_command(&cmd).is_empty());
+
+        cmd.expected_output_metric = "v_geometrc".to_string();
+        let errors = lint_task_command(&cmd);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "expected_output_metric");
+        // Submission does not know about metrics until the task runs
+        assert!(validate_task_command(&cmd).is_ok());
+    }
+}
@@ -0,0 +1,180 @@
+use arrow2::{
+    array::{Array, Float64Array, Int64Array},
+    chunk::Chunk,
+    datatypes::{DataType, Field, Schema, TimeUnit},
+    io::ipc::write::{FileWriter, WriteOptions},
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::{fs::File, io::Write, path::Path};
+
+use crate::core::error::Result;
+use crate::core::types::GeometricMetrics;
+
+/// Name of the column holding each snapshot's recording time.
+pub const TIMESTAMP_COLUMN: &str = "timestamp";
+
+/// MIME type of the Arrow IPC file format.
+pub const ARROW_CONTENT_TYPE: &str = "application/vnd.apache.arrow.file";
+
+/// MIME type of CSV exports.
+pub const CSV_CONTENT_TYPE: &str = "text/csv; charset=utf-8";
+
+/// Write a metrics trajectory to an Arrow IPC file at `path`.
+///
+/// See [`write_metrics_history_to`] for the layout.
+pub fn write_metrics_history(
+    path: &Path,
+    history: &[(DateTime<Utc>, GeometricMetrics)],
+) -> Result<()> {
+    let mut file = File::create(path)?;
+    write_metrics_history_to(&mut file, history)
+}
+
+/// Write a metrics trajectory as an Arrow IPC file to `writer`.
+///
+/// The first column is a UTC microsecond `timestamp`, followed by one
+/// `Float64` column per scalar metric field. Custom metrics are not exported.
+pub fn write_metrics_history_to<W: Write>(
+    writer: W,
+    history: &[(DateTime<Utc>, GeometricMetrics)],
+) -> Result<()> {
+    let field_names = GeometricMetrics::default()
+        .scalar_fields()
+        .map(|(name, _)| name);
+
+    let mut fields = vec![Field::new(
+        TIMESTAMP_COLUMN,
+        DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".to_string())),
+        false,
+    )];
+    fields.extend(
+        field_names
+            .iter()
+            .map(|name| Field::new(*name, DataType::Float64, false)),
+    );
+
+    let timestamps: Vec<i64> = history
+        .iter()
+        .map(|(at, _)| at.timestamp_micros())
+        .collect();
+    let mut columns: Vec<Box<dyn Array>> = vec![Int64Array::from_vec(timestamps)
+        .to(fields[0].data_type().clone())
+        .boxed()];
+    for index in 0..field_names.len() {
+        let values = history
+            .iter()
+            .map(|(_, metrics)| metrics.scalar_fields()[index].1)
+            .collect();
+        columns.push(Float64Array::from_vec(values).boxed());
+    }
+
+    let mut writer = FileWriter::try_new(
+        writer,
+        Schema::from(fields),
+        None,
+        WriteOptions { compression: None },
+    )
+    .map_err(anyhow::Error::from)?;
+    let chunk = Chunk::try_new(columns).map_err(anyhow::Error::from)?;
+    writer.write(&chunk, None).map_err(anyhow::Error::from)?;
+    writer.finish().map_err(anyhow::Error::from)?;
+    Ok(())
+}
+
+/// Write a metrics trajectory as CSV to `writer`.
+///
+/// The header is `timestamp` followed by the scalar metric fields, with one
+/// row per snapshot. Timestamps are RFC 3339 in UTC; custom metrics are not
+/// exported.
+pub fn write_metrics_history_csv_to<W: Write>(
+    mut writer: W,
+    history: &[(DateTime<Utc>, GeometricMetrics)],
+) -> Result<()> {
+    let field_names = GeometricMetrics::default()
+        .scalar_fields()
+        .map(|(name, _)| name);
+    writeln!(writer, "{},{}", TIMESTAMP_COLUMN, field_names.join(","))?;
+
+    for (at, metrics) in history {
+        let values: Vec<String> = metrics
+            .scalar_fields()
+            .iter()
+            .map(|(_, value)| value.to_string())
+            .collect();
+        writeln!(
+            writer,
+            "{},{}",
+            at.to_rfc3339_opts(SecondsFormat::Micros, true),
+            values.join(",")
+        )?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow2::io::ipc::read::{read_file_metadata, FileReader};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_metrics_history_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("metrics.arrow");
+
+        let history: Vec<_> = (0..3)
+            .map(|i| {
+                let metrics = GeometricMetrics {
+                    v_geometric: i as f64,
+                    quaternion_coherence: 0.5 + i as f64 * 0.1,
+                    ..Default::default()
+                };
+                (
+                    Utc.timestamp_opt(1_732_400_000 + i * 60, 0).unwrap(),
+                    metrics,
+                )
+            })
+            .collect();
+        write_metrics_history(&path, &history).unwrap();
+
+        let mut file = File::open(&path).unwrap();
+        let metadata = read_file_metadata(&mut file).unwrap();
+        let names: Vec<_> = metadata
+            .schema
+            .fields
+            .iter()
+            .map(|f| f.name.as_str())
+            .collect();
+        assert_eq!(names.len(), 9);
+        assert_eq!(names[0], TIMESTAMP_COLUMN);
+        assert_eq!(names[1], "v_geometric");
+
+        let chunk = FileReader::new(file, metadata, None, None)
+            .next()
+            .unwrap()
+            .unwrap();
+        assert_eq!(chunk.len(), 3);
+
+        let timestamps = chunk.arrays()[0]
+            .as_any()
+            .downcast_ref::<Int64Array>()
+            .unwrap();
+        assert_eq!(timestamps.value(2), history[2].0.timestamp_micros());
+
+        for (index, (name, _)) in history[0].1.scalar_fields().iter().enumerate() {
+            let column = chunk.arrays()[index + 1]
+                .as_any()
+                .downcast_ref::<Float64Array>()
+                .unwrap();
+            for (row, (_, metrics)) in history.iter().enumerate() {
+                assert_eq!(
+                    column.value(row),
+                    metrics.scalar_fields()[index].1,
+                    "{}",
+                    name
+                );
+            }
+        }
+    }
+}
@@ -1,32 +1,43 @@
 pub mod core {
+    pub mod alerts;
+    pub mod clock;
     pub mod emergence_logic;
     pub mod eqgft_types;
     pub mod error;
     pub mod geometric_metrics;
     pub mod geometric_quaternion_core;
+    pub mod metrics_stream;
     pub mod semantic_task_processor;
     pub mod types;
-    
+    pub mod validation;
+
     // Re-export commonly used types
     pub use eqgft_types::{
-        EQGFTFields, QuaternionField, DiracSpinor, GaugeField, Metric, 
-        EQGFTAction, VisualizationPacket, VisualizationType, 
-        VisualizationRequest, VisualizationResponse, VisualizationStatus,
-        ExternalTool, ToolRegistry
+        DiracSpinor, EQGFTAction, EQGFTFields, ExternalTool, GaugeField, Metric, QuaternionField,
+        ToolRegistry, VisualizationPacket, VisualizationRequest, VisualizationResponse,
+        VisualizationStatus, VisualizationType,
     };
 }
 
 pub mod api {
+    pub mod circuit_breaker;
     pub mod data_io;
     pub mod llm_gateway;
 }
 
 pub mod visualization {
+    pub mod chart;
     pub mod protocol;
 }
 
+pub mod export {
+    pub mod metrics;
+}
+
+pub mod config;
 pub mod routes;
 pub mod state;
+pub mod telemetry;
 
 pub use crate::core::error::{Error, Result};
 pub use crate::core::types::*;
@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::core::types::SemanticAnchor;
+use crate::state::AppState;
+
+use super::{bad_request, internal_error, not_found, ApiResult};
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateAnchorRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// 4D position in quaternion space
+    pub position: [f64; 4],
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+}
+
+#[utoipa::path(
+    get,
+    path = "/anchors",
+    tag = "anchors",
+    responses(
+        (status = 200, description = "Registered anchors in registration order", body = [SemanticAnchor]),
+        (status = 500, description = "Anchor storage unavailable", body = String)
+    )
+)]
+pub async fn list_anchors(State(state): State<AppState>) -> ApiResult<Json<Vec<SemanticAnchor>>> {
+    let anchors = state.processor.list_anchors().map_err(internal_error)?;
+    Ok(Json(anchors))
+}
+
+#[utoipa::path(
+    post,
+    path = "/anchors",
+    tag = "anchors",
+    request_body = CreateAnchorRequest,
+    responses(
+        (status = 201, description = "Anchor registered", body = SemanticAnchor),
+        (status = 400, description = "Invalid anchor", body = String)
+    )
+)]
+pub async fn create_anchor(
+    State(state): State<AppState>,
+    Json(request): Json<CreateAnchorRequest>,
+) -> ApiResult<(StatusCode, Json<SemanticAnchor>)> {
+    if request.name.trim().is_empty() {
+        return Err(bad_request("Anchor name cannot be empty"));
+    }
+    if !request.position.iter().all(|c| c.is_finite()) {
+        return Err(bad_request("Anchor position must be finite"));
+    }
+
+    let anchor = SemanticAnchor {
+        id: Uuid::new_v4(),
+        name: request.name,
+        description: request.description,
+        position: request.position,
+        metadata: request.metadata,
+    };
+    state
+        .processor
+        .add_anchor(anchor.clone())
+        .map_err(internal_error)?;
+
+    Ok((StatusCode::CREATED, Json(anchor)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/anchors/{id}",
+    tag = "anchors",
+    params(("id" = Uuid, Path, description = "Anchor ID")),
+    responses(
+        (status = 200, description = "Anchor removed", body = SemanticAnchor),
+        (status = 400, description = "Invalid anchor ID", body = String),
+        (status = 404, description = "Anchor not found", body = String)
+    )
+)]
+pub async fn delete_anchor(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<SemanticAnchor>> {
+    let id = Uuid::parse_str(&id).map_err(|_| bad_request("Invalid anchor ID"))?;
+
+    let removed = state
+        .processor
+        .remove_anchor(id)
+        .map_err(internal_error)?
+        .ok_or_else(|| not_found("Anchor not found"))?;
+
+    Ok(Json(removed))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::test_support::{get, post_json, send, test_app};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_anchor_routes_round_trip() {
+        let app = test_app();
+        let (status, created) = post_json(
+            app.clone(),
+            "/anchors",
+            &json!({ "name": "Origin", "position": [0.0, 0.0, 0.0, 1.0] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["name"], "Origin");
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let (status, listed) = get(app.clone(), "/anchors").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed, json!([created]));
+
+        let delete = |uri: String| Request::delete(uri).body(Body::empty()).unwrap();
+        let (status, removed) = send(app.clone(), delete(format!("/anchors/{}", id))).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(removed, created);
+        let (status, _) = send(app.clone(), delete(format!("/anchors/{}", id))).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (_, listed) = get(app.clone(), "/anchors").await;
+        assert_eq!(listed, json!([]));
+
+        let (status, _) = post_json(
+            app,
+            "/anchors",
+            &json!({ "name": " ", "position": [0.0, 0.0, 0.0, 1.0] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
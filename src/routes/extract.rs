@@ -0,0 +1,49 @@
+use axum::extract::rejection::JsonRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::{async_trait, Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// Body returned with a 400 when a JSON request body cannot be parsed.
+#[derive(Serialize, ToSchema)]
+pub struct JsonErrorResponse {
+    /// The parser's message, including the path to the offending field
+    pub error: String,
+}
+
+/// [`Json`] whose rejection says what was wrong with the body.
+///
+/// Malformed JSON and JSON of the wrong shape are both a 400 with a
+/// [`JsonErrorResponse`], instead of axum's plain-text 400/422. Other
+/// rejections, such as an oversized body or a missing content type, keep
+/// their status.
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<S, T> FromRequest<S> for ApiJson<T>
+where
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        match Json::<T>::from_request(req, state).await {
+            Ok(Json(value)) => Ok(Self(value)),
+            Err(rejection) => {
+                let status = match rejection {
+                    JsonRejection::JsonDataError(_) | JsonRejection::JsonSyntaxError(_) => {
+                        StatusCode::BAD_REQUEST
+                    }
+                    ref other => other.status(),
+                };
+                let body = JsonErrorResponse {
+                    error: rejection.body_text(),
+                };
+                Err((status, Json(body)).into_response())
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use axum::Json;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::core::eqgft_types::QuaternionField;
+
+use super::{bad_request, ApiResult};
+
+/// Largest `steps` accepted by `/fields/interpolate_path`
+pub const MAX_INTERPOLATION_STEPS: usize = 1000;
+
+#[derive(Deserialize, ToSchema)]
+pub struct InterpolateFieldsRequest {
+    pub from: QuaternionField,
+    pub to: QuaternionField,
+    /// Interpolation parameter in [0, 1]
+    pub t: f64,
+}
+
+#[utoipa::path(
+    post,
+    path = "/fields/interpolate",
+    tag = "fields",
+    request_body = InterpolateFieldsRequest,
+    responses(
+        (status = 200, description = "Interpolated field", body = QuaternionField),
+        (status = 400, description = "t outside [0, 1]", body = String)
+    )
+)]
+pub async fn interpolate_fields(
+    Json(payload): Json<InterpolateFieldsRequest>,
+) -> ApiResult<Json<QuaternionField>> {
+    if !(0.0..=1.0).contains(&payload.t) {
+        return Err(bad_request("t must be within [0, 1]"));
+    }
+
+    Ok(Json(payload.from.slerp(&payload.to, payload.t)))
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InterpolatePathRequest {
+    pub from: QuaternionField,
+    pub to: QuaternionField,
+    /// Number of segments; the path has `steps + 1` fields
+    pub steps: usize,
+}
+
+#[utoipa::path(
+    post,
+    path = "/fields/interpolate_path",
+    tag = "fields",
+    request_body = InterpolatePathRequest,
+    responses(
+        (status = 200, description = "Evenly spaced fields from `from` to `to`, both included", body = Vec<QuaternionField>),
+        (status = 400, description = "steps is 0 or above the limit", body = String)
+    )
+)]
+pub async fn interpolate_path(
+    Json(payload): Json<InterpolatePathRequest>,
+) -> ApiResult<Json<Vec<QuaternionField>>> {
+    if !(1..=MAX_INTERPOLATION_STEPS).contains(&payload.steps) {
+        return Err(bad_request(format!(
+            "steps must be within [1, {}]",
+            MAX_INTERPOLATION_STEPS
+        )));
+    }
+
+    Ok(Json(payload.from.slerp_path(&payload.to, payload.steps)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::test_support::{post_json, test_app};
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_interpolate_fields_route() {
+        let from = json!({ "q0": 1.0, "q1": 0.0, "q2": 0.0, "q3": 0.0, "coordinates": [0.0, 0.0, 0.0, 0.0] });
+        let to = json!({ "q0": 0.0, "q1": 1.0, "q2": 0.0, "q3": 0.0, "coordinates": [2.0, 2.0, 2.0, 2.0] });
+
+        let (status, body) = post_json(
+            test_app(),
+            "/fields/interpolate",
+            &json!({ "from": from, "to": to, "t": 0.5 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["coordinates"], json!([1.0, 1.0, 1.0, 1.0]));
+        assert!((body["q0"].as_f64().unwrap() - std::f64::consts::FRAC_1_SQRT_2).abs() < 1e-10);
+
+        let (status, _) = post_json(
+            test_app(),
+            "/fields/interpolate",
+            &json!({ "from": from, "to": to, "t": 1.5 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_interpolate_path_route() {
+        use super::MAX_INTERPOLATION_STEPS;
+
+        let from = json!({ "q0": 1.0, "q1": 0.0, "q2": 0.0, "q3": 0.0, "coordinates": [0.0, 0.0, 0.0, 0.0] });
+        let to = json!({ "q0": 0.0, "q1": 0.0, "q2": 1.0, "q3": 0.0, "coordinates": [4.0, 0.0, -4.0, 1.0] });
+
+        let request = json!({ "from": from, "to": to, "steps": 4 });
+        let (status, body) = post_json(test_app(), "/fields/interpolate_path", &request).await;
+        assert_eq!(status, StatusCode::OK);
+        let path = body.as_array().unwrap();
+        assert_eq!(path.len(), 5);
+        for (field, expected) in [(&path[0], &from), (&path[4], &to)] {
+            assert_eq!(field["coordinates"], expected["coordinates"]);
+            for key in ["q0", "q1", "q2", "q3"] {
+                let delta = field[key].as_f64().unwrap() - expected[key].as_f64().unwrap();
+                assert!(delta.abs() < 1e-12, "{key}: {field}");
+            }
+        }
+        assert_eq!(path[1]["coordinates"], json!([1.0, 0.0, -1.0, 0.25]));
+
+        for steps in [0, MAX_INTERPOLATION_STEPS + 1] {
+            let request = json!({ "from": from, "to": to, "steps": steps });
+            let (status, _) = post_json(test_app(), "/fields/interpolate_path", &request).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+        }
+    }
+}
@@ -1,16 +1,117 @@
-use axum::Json;
+use std::collections::BTreeMap;
+
+use axum::{extract::State, Json};
 use chrono::Utc;
 use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::core::semantic_task_processor::TaskStatusKind;
+use crate::state::AppState;
+
+use super::{internal_error, ApiResult};
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct HealthResponse {
     pub status: &'static str,
     pub timestamp: String,
+    /// Crate version of the running build
+    pub version: &'static str,
+    /// Commit the running build was made from, or "unknown"
+    pub git_commit: &'static str,
 }
 
+#[derive(Serialize, ToSchema)]
+pub struct HealthDetailResponse {
+    pub status: &'static str,
+    pub timestamp: String,
+    /// Seconds since the server state was initialized
+    pub uptime_secs: u64,
+    /// Number of tasks in each state
+    pub tasks: BTreeMap<TaskStatusKind, usize>,
+    /// Snapshots held in the metrics history buffer
+    pub metrics_history_len: usize,
+}
+
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, description = "Service is up", body = HealthResponse))
+)]
 pub async fn health_check() -> Json<HealthResponse> {
     Json(HealthResponse {
         status: "ok",
         timestamp: Utc::now().to_rfc3339(),
+        version: env!("CARGO_PKG_VERSION"),
+        git_commit: env!("MMSS_GIT_COMMIT"),
     })
 }
+
+#[utoipa::path(
+    get,
+    path = "/health/detail",
+    tag = "health",
+    responses(
+        (status = 200, description = "Task backlog, history size and uptime", body = HealthDetailResponse),
+        (status = 500, description = "Processor state unavailable", body = String)
+    )
+)]
+pub async fn health_detail(State(state): State<AppState>) -> ApiResult<Json<HealthDetailResponse>> {
+    let tasks = state.processor.status_counts().map_err(internal_error)?;
+    let metrics_history_len = state.processor.history_len().map_err(internal_error)?;
+
+    Ok(Json(HealthDetailResponse {
+        status: "ok",
+        timestamp: Utc::now().to_rfc3339(),
+        uptime_secs: state.started_at.elapsed().as_secs(),
+        tasks: tasks.into_iter().collect(),
+        metrics_history_len,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::types::{GeometricOperator, GeometricTaskCommand};
+    use crate::routes::build_router;
+    use crate::routes::test_support::{get, test_app, test_state};
+    use axum::http::StatusCode;
+
+    #[tokio::test]
+    async fn test_health_reports_build_info() {
+        let (status, body) = get(test_app(), "/health").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["version"], env!("CARGO_PKG_VERSION"));
+        assert!(!body["version"].as_str().unwrap().is_empty());
+        assert!(!body["git_commit"].as_str().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_health_detail_reports_task_counts() {
+        let state = test_state();
+        let task = || GeometricTaskCommand {
+            task_name: "Health probe".to_string(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "sys7_core".to_string(),
+            parameters: serde_json::json!({}),
+            expected_output_metric: "v_geometric".to_string(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+        for _ in 0..3 {
+            state.processor.submit_task(task()).unwrap();
+        }
+        let executed = state.processor.submit_task(task()).unwrap();
+        state.processor.execute_task(executed).unwrap();
+
+        let app = build_router().with_state(state);
+        let (status, body) = get(app, "/health/detail").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["tasks"]["Pending"], 3);
+        assert_eq!(body["tasks"]["InProgress"], 0);
+        assert_eq!(body["tasks"]["Completed"], 1);
+        assert_eq!(body["tasks"]["Failed"], 0);
+        assert_eq!(body["metrics_history_len"], 2);
+        assert!(body["uptime_secs"].is_u64());
+    }
+}
@@ -1,24 +1,45 @@
-use axum::{extract::State, Json};
-use log::warn;
+use axum::{extract::State, response::IntoResponse, Json};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use tracing::warn;
+use utoipa::ToSchema;
 
+use crate::core::error::Error;
 use crate::core::types::{GeometricMetrics, GeometricOperator, GeometricTaskCommand};
 use crate::state::AppState;
 
-use super::{bad_request, internal_error, ApiResult};
+use super::{bad_request, internal_error, service_unavailable, ApiResult};
 
-#[derive(Deserialize)]
+const LLM_NOT_CONFIGURED: &str = "LLM not configured";
+
+#[derive(Deserialize, ToSchema)]
 pub struct LlmQuery {
     pub query: String,
     #[serde(default)]
     pub context: Value,
 }
 
+#[utoipa::path(
+    post,
+    path = "/llm/query",
+    tag = "llm",
+    request_body = LlmQuery,
+    responses(
+        (status = 200, description = "Task command proposed by the LLM", body = GeometricTaskCommand),
+        (status = 400, description = "LLM request failed", body = String),
+        (status = 429, description = "LLM API rate limit hit; see Retry-After", body = String),
+        (status = 503, description = "LLM not configured", body = String)
+    )
+)]
 pub async fn llm_query(
     State(state): State<AppState>,
     Json(payload): Json<LlmQuery>,
 ) -> ApiResult<Json<GeometricTaskCommand>> {
+    let gateway = state
+        .llm_gateway
+        .as_deref()
+        .ok_or_else(|| service_unavailable(LLM_NOT_CONFIGURED))?;
+
     let context = if payload.context.is_null() {
         serde_json::json!({
             "current_metrics": state
@@ -30,16 +51,18 @@ pub async fn llm_query(
         payload.context
     };
 
-    let result = state
-        .llm_gateway
+    let result = gateway
         .submit_geometric_query(&payload.query, &context)
         .await
-        .map_err(|err| bad_request(err.to_string()))?;
+        .map_err(|err| match err {
+            Error::CapacityExceeded { .. } => err.into_response(),
+            other => bad_request(other.to_string()),
+        })?;
 
     Ok(Json(result))
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct ResearchCampaignRequest {
     pub goal: String,
     #[serde(default = "default_max_steps")]
@@ -50,7 +73,7 @@ pub struct ResearchCampaignRequest {
     pub context: Value,
 }
 
-#[derive(Serialize, Clone)]
+#[derive(Serialize, Clone, ToSchema)]
 pub struct ResearchStepSummary {
     pub step: usize,
     pub task: GeometricTaskCommand,
@@ -59,7 +82,7 @@ pub struct ResearchStepSummary {
     pub progress: f64,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ResearchCampaignResponse {
     pub goal: String,
     pub optimization_target: String,
@@ -74,25 +97,36 @@ fn default_max_steps() -> usize {
     5
 }
 
+#[utoipa::path(
+    post,
+    path = "/llm/research-campaign",
+    tag = "llm",
+    request_body = ResearchCampaignRequest,
+    responses(
+        (status = 200, description = "Campaign history and final metrics", body = ResearchCampaignResponse),
+        (status = 400, description = "Task rejected", body = String),
+        (status = 500, description = "Task execution failed", body = String),
+        (status = 503, description = "LLM not configured", body = String)
+    )
+)]
 pub async fn start_research_campaign(
     State(state): State<AppState>,
     Json(request): Json<ResearchCampaignRequest>,
 ) -> ApiResult<Json<ResearchCampaignResponse>> {
+    let gateway = state
+        .llm_gateway
+        .as_deref()
+        .ok_or_else(|| service_unavailable(LLM_NOT_CONFIGURED))?;
+
     let mut history = Vec::new();
-    let mut current_metrics = state
-        .processor
-        .get_metrics()
-        .map_err(internal_error)?;
+    let mut current_metrics = state.processor.get_metrics().map_err(internal_error)?;
 
     let target_value = request
         .target_value
         .unwrap_or_else(|| infer_default_target(&request.optimization_target));
 
-    let mut best_progress = evaluate_research_progress(
-        &current_metrics,
-        &request.optimization_target,
-        target_value,
-    );
+    let mut best_progress =
+        evaluate_research_progress(&current_metrics, &request.optimization_target, target_value);
 
     for step_idx in 1..=request.max_steps {
         let llm_context = json!({
@@ -110,14 +144,13 @@ pub async fn start_research_campaign(
             request.goal, request.optimization_target
         );
 
-        let mut task_template = match state
-            .llm_gateway
-            .submit_geometric_query(&query, &llm_context)
-            .await
-        {
+        let mut task_template = match gateway.submit_geometric_query(&query, &llm_context).await {
             Ok(task) => task,
             Err(err) => {
-                warn!("LLM research step failed ({}). Using fallback command.", err);
+                warn!(
+                    "LLM research step failed ({}). Using fallback command.",
+                    err
+                );
                 fallback_task_for_target(&request.optimization_target, target_value)
             }
         };
@@ -215,6 +248,8 @@ fn fallback_task_for_target(target: &str, target_value: f64) -> GeometricTaskCom
             parameters: json!({ "frequency_scale": target_value / 9.0 }),
             expected_output_metric: target.into(),
             task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
         },
         "quaternion_coherence" | "v_geometric" => GeometricTaskCommand {
             task_name: "Fallback Quaternion coherence".into(),
@@ -223,6 +258,8 @@ fn fallback_task_for_target(target: &str, target_value: f64) -> GeometricTaskCom
             parameters: json!({ "theta": 0.25, "axis": [0.0, 1.0, 0.0] }),
             expected_output_metric: target.into(),
             task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
         },
         "emergent_electron_mass" => GeometricTaskCommand {
             task_name: "Fallback mass adjustment".into(),
@@ -231,6 +268,8 @@ fn fallback_task_for_target(target: &str, target_value: f64) -> GeometricTaskCom
             parameters: json!({ "frequency_scale": 1.0 }),
             expected_output_metric: target.into(),
             task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
         },
         "fine_structure_constant" => GeometricTaskCommand {
             task_name: "Fallback α tuning".into(),
@@ -239,6 +278,8 @@ fn fallback_task_for_target(target: &str, target_value: f64) -> GeometricTaskCom
             parameters: json!({ "theta": 0.1 }),
             expected_output_metric: target.into(),
             task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
         },
         _ => GeometricTaskCommand {
             task_name: "Fallback geometric derivation".into(),
@@ -247,6 +288,77 @@ fn fallback_task_for_target(target: &str, target_value: f64) -> GeometricTaskCom
             parameters: json!({ "delta": 0.01 }),
             expected_output_metric: target.into(),
             task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::config::AppConfig;
+    use crate::routes::build_router;
+    use crate::routes::test_support::{post_json, test_state};
+    use crate::state::AppState;
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    #[test]
+    fn test_initialize_without_api_key() {
+        let state = AppState::initialize(&AppConfig::default()).unwrap();
+        assert!(state.llm_gateway.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_llm_routes_unavailable_without_gateway() {
+        let mut state = test_state();
+        state.llm_gateway = None;
+        let app = build_router().with_state(state);
+
+        let query = json!({ "query": "rotate" });
+        let (status, _) = post_json(app.clone(), "/llm/query", &query).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+
+        let campaign =
+            json!({ "goal": "stabilize", "optimization_target": "quaternion_coherence" });
+        let (status, _) = post_json(app, "/llm/research-campaign", &campaign).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn test_llm_query_rate_limited_is_429() {
+        use crate::api::llm_gateway::LlmGateway;
+        use axum::body::Body;
+        use axum::http::{header, Request};
+        use axum::routing::post;
+        use axum::Router;
+        use std::sync::Arc;
+        use tower::ServiceExt;
+
+        let limited = Router::new().route(
+            "/v1/chat/completions",
+            post(|| async { (StatusCode::TOO_MANY_REQUESTS, [("retry-after", "30")]) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, limited).await.unwrap() });
+
+        let mut state = test_state();
+        state.llm_gateway = Some(Arc::new(
+            LlmGateway::new(Some("test-key".into()))
+                .unwrap()
+                .with_base_url(format!("http://{}/v1", addr)),
+        ));
+        let request = Request::post("/llm/query")
+            .header("content-type", "application/json")
+            .body(Body::from(json!({ "query": "rotate" }).to_string()))
+            .unwrap();
+        let response = build_router()
+            .with_state(state)
+            .oneshot(request)
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+    }
+}
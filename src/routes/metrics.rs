@@ -1,33 +1,638 @@
-use axum::{extract::State, Json};
-use serde::Serialize;
+use std::collections::BTreeMap;
 
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    Json,
+};
+use futures_util::{stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::core::geometric_metrics::{diff_metrics, select_fields, MetricsDiff};
+use crate::core::metrics_stream::MetricsEvent;
+use crate::export::metrics::{
+    write_metrics_history_csv_to, write_metrics_history_to, ARROW_CONTENT_TYPE, CSV_CONTENT_TYPE,
+};
 use crate::state::AppState;
 
-use super::{internal_error, ApiResult};
+use super::{bad_request, internal_error, ApiResult};
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct MetricsResponse {
     pub metrics: crate::core::types::GeometricMetrics,
     pub rule_names: Vec<String>,
     pub rule_count: usize,
+    /// The live metrics were unavailable; these are the last good values
+    pub stale: bool,
 }
 
-pub async fn get_metrics(State(state): State<AppState>) -> ApiResult<Json<MetricsResponse>> {
-    let metrics = state.processor.get_metrics().map_err(internal_error)?;
+#[derive(Deserialize, IntoParams)]
+pub struct MetricsQuery {
+    /// Comma-separated scalar field or custom metric names, e.g.
+    /// `v_geometric,quaternion_coherence`
+    pub fields: Option<String>,
+    /// Round every value to this many decimal places; full precision when
+    /// omitted
+    pub precision: Option<u32>,
+}
+
+/// Either the full metrics response or just the requested fields.
+#[derive(Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum MetricsView {
+    Full(MetricsResponse),
+    Selected(BTreeMap<String, f64>),
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "metrics",
+    params(MetricsQuery),
+    responses(
+        (status = 200, description = "Current metrics and registered rules, or only the requested fields", body = MetricsView),
+        (status = 400, description = "Unknown metric field names", body = super::ValidationErrorResponse),
+        (status = 500, description = "Metrics unavailable", body = String)
+    )
+)]
+pub async fn get_metrics(
+    State(state): State<AppState>,
+    Query(query): Query<MetricsQuery>,
+) -> ApiResult<Json<MetricsView>> {
+    let snapshot = state.processor.metrics_snapshot().map_err(internal_error)?;
+    let mut metrics = snapshot.metrics;
+    if let Some(precision) = query.precision {
+        metrics = metrics.rounded(precision);
+    }
+
+    let names: Vec<&str> = query
+        .fields
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .collect();
+    if !names.is_empty() {
+        let selected = select_fields(&metrics, &names).map_err(IntoResponse::into_response)?;
+        return Ok(Json(MetricsView::Selected(selected)));
+    }
+
     let engine = state.metric_engine.read().await;
     let rule_names = engine.rule_names();
     let rule_count = rule_names.len();
 
-    Ok(Json(MetricsResponse {
+    Ok(Json(MetricsView::Full(MetricsResponse {
         metrics,
         rule_names,
         rule_count,
-    }))
+        stale: snapshot.stale,
+    })))
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct PrecisionQuery {
+    /// Round every value to this many decimal places; full precision when
+    /// omitted
+    pub precision: Option<u32>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/vectorized",
+    tag = "metrics",
+    params(PrecisionQuery),
+    responses(
+        (status = 200, description = "Current metrics", body = crate::core::types::GeometricMetrics),
+        (status = 500, description = "Metrics unavailable", body = String)
+    )
+)]
 pub async fn get_vectorized_metrics(
     State(state): State<AppState>,
+    Query(query): Query<PrecisionQuery>,
+) -> ApiResult<Json<crate::core::types::GeometricMetrics>> {
+    let metrics = state.processor.get_metrics().map_err(internal_error)?;
+    Ok(Json(match query.precision {
+        Some(precision) => metrics.rounded(precision),
+        None => metrics,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/metrics/reset",
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Baseline metrics after reset", body = crate::core::types::GeometricMetrics),
+        (status = 500, description = "Metrics unavailable", body = String)
+    )
+)]
+pub async fn reset_metrics(
+    State(state): State<AppState>,
 ) -> ApiResult<Json<crate::core::types::GeometricMetrics>> {
+    state.processor.reset_metrics().map_err(internal_error)?;
     let metrics = state.processor.get_metrics().map_err(internal_error)?;
     Ok(Json(metrics))
 }
+
+#[utoipa::path(
+    post,
+    path = "/metrics/recompute",
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Final metrics after re-running every command since the last reset under the current rule set", body = crate::core::types::GeometricMetrics),
+        (status = 500, description = "Metrics unavailable", body = String)
+    )
+)]
+pub async fn recompute_metrics(
+    State(state): State<AppState>,
+) -> ApiResult<Json<crate::core::types::GeometricMetrics>> {
+    let metrics = state
+        .processor
+        .recompute_metrics()
+        .map_err(internal_error)?;
+    Ok(Json(metrics))
+}
+
+/// Header an EventSource sends on reconnect with the last ID it received
+const LAST_EVENT_ID: &str = "last-event-id";
+
+#[utoipa::path(
+    get,
+    path = "/metrics/stream",
+    tag = "metrics",
+    params(
+        ("Last-Event-ID" = Option<u64>, Header, description = "Replay the snapshots recorded after this event ID before streaming new ones")
+    ),
+    responses(
+        (status = 200, description = "Server-sent MetricsEvents. `metrics` events carry their history index as the event ID; a `lagged` event reports snapshots the client can no longer get", body = MetricsEvent, content_type = "text/event-stream"),
+        (status = 400, description = "Last-Event-ID is not an event ID", body = String),
+        (status = 500, description = "Metrics history unavailable", body = String)
+    )
+)]
+pub async fn stream_metrics(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> ApiResult<Sse<impl Stream<Item = Result<Event, axum::Error>>>> {
+    let last_id = match headers.get(LAST_EVENT_ID) {
+        Some(value) => Some(
+            value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .ok_or_else(|| bad_request("Last-Event-ID must be an event ID"))?,
+        ),
+        None => None,
+    };
+
+    // Subscribe before reading the history so nothing recorded in between
+    // is missed; anything in both is only sent once.
+    let subscription = state.processor.subscribe_metrics();
+    let replay = match last_id {
+        Some(id) => state.processor.history_after(id).map_err(internal_error)?,
+        None => Vec::new(),
+    };
+
+    let mut events = Vec::new();
+    if let (Some(last_id), Some((first_id, _))) = (last_id, replay.first()) {
+        let skipped = first_id - last_id - 1;
+        if skipped > 0 {
+            events.push(sse_event(None, &MetricsEvent::Lagged { skipped }));
+        }
+    }
+    let replayed_up_to = replay.last().map(|(id, _)| *id);
+    events.extend(
+        replay
+            .into_iter()
+            .map(|(id, metrics)| sse_event(Some(id), &MetricsEvent::Metrics(metrics))),
+    );
+
+    let live = stream::unfold(subscription, move |mut subscription| async move {
+        loop {
+            let (id, event) = subscription.recv_with_id().await?;
+            if id.zip(replayed_up_to).is_some_and(|(id, seen)| id <= seen) {
+                continue;
+            }
+            return Some((sse_event(id, &event), subscription));
+        }
+    });
+
+    Ok(Sse::new(stream::iter(events).chain(live)).keep_alive(KeepAlive::default()))
+}
+
+fn sse_event(id: Option<u64>, event: &MetricsEvent) -> Result<Event, axum::Error> {
+    let name = match event {
+        MetricsEvent::Metrics(_) => "metrics",
+        MetricsEvent::Lagged { .. } => "lagged",
+    };
+    let sse = Event::default().event(name);
+    match id {
+        Some(id) => sse.id(id.to_string()),
+        None => sse,
+    }
+    .json_data(event)
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct MetricsDiffQuery {
+    /// History index of the earlier snapshot
+    pub from: usize,
+    /// History index of the later snapshot
+    pub to: usize,
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/diff",
+    tag = "metrics",
+    params(MetricsDiffQuery),
+    responses(
+        (status = 200, description = "Per-field deltas between two history snapshots", body = MetricsDiff),
+        (status = 400, description = "History index out of range", body = String),
+        (status = 500, description = "Metrics history unavailable", body = String)
+    )
+)]
+pub async fn diff_metrics_history(
+    State(state): State<AppState>,
+    Query(query): Query<MetricsDiffQuery>,
+) -> ApiResult<Json<MetricsDiff>> {
+    let history = state.processor.metrics_history().map_err(internal_error)?;
+
+    let out_of_range = |index: usize| {
+        bad_request(format!(
+            "History index {} out of range (history has {} entries)",
+            index,
+            history.len()
+        ))
+    };
+    let from = history
+        .get(query.from)
+        .ok_or_else(|| out_of_range(query.from))?;
+    let to = history
+        .get(query.to)
+        .ok_or_else(|| out_of_range(query.to))?;
+
+    Ok(Json(diff_metrics(from, to)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/export.arrow",
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Metrics history as an Arrow IPC file: a UTC timestamp column plus one float column per scalar field", content_type = "application/vnd.apache.arrow.file"),
+        (status = 500, description = "History unavailable or export failed", body = String)
+    )
+)]
+pub async fn export_metrics_arrow(State(state): State<AppState>) -> ApiResult<Response> {
+    let history = state
+        .processor
+        .timestamped_history()
+        .map_err(internal_error)?;
+    let mut bytes = Vec::new();
+    write_metrics_history_to(&mut bytes, &history).map_err(internal_error)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, ARROW_CONTENT_TYPE),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"metrics.arrow\"",
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/metrics/export.csv",
+    tag = "metrics",
+    responses(
+        (status = 200, description = "Metrics history as CSV: a header row of `timestamp` plus the scalar fields, then one row per snapshot", content_type = "text/csv"),
+        (status = 500, description = "History unavailable or export failed", body = String)
+    )
+)]
+pub async fn export_metrics_csv(State(state): State<AppState>) -> ApiResult<Response> {
+    let history = state
+        .processor
+        .timestamped_history()
+        .map_err(internal_error)?;
+    let mut bytes = Vec::new();
+    write_metrics_history_csv_to(&mut bytes, &history).map_err(internal_error)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, CSV_CONTENT_TYPE),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"metrics.csv\"",
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{GeometricOperator, GeometricTaskCommand};
+    use crate::routes::build_router;
+    use crate::routes::test_support::{get, test_app, test_state};
+    use arrow2::io::ipc::read::{read_file_metadata, FileReader};
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_metrics_diff_matches_history() {
+        let state = test_state();
+        for op in [
+            GeometricOperator::QuaternionRotation,
+            GeometricOperator::Zitterbewegung,
+        ] {
+            let task = GeometricTaskCommand {
+                task_name: "Diff probe".to_string(),
+                geometric_operator: op,
+                target_module: "sys7_core".to_string(),
+                parameters: serde_json::json!({ "theta": 0.8, "frequency_scale": 2.0 }),
+                expected_output_metric: "v_geometric".to_string(),
+                task_id: None,
+                execute_at: None,
+                tags: Vec::new(),
+            };
+            let task_id = state.processor.submit_task(task).unwrap();
+            state.processor.execute_task(task_id).unwrap();
+        }
+        let history = state.processor.metrics_history().unwrap();
+        assert_eq!(history.len(), 3);
+
+        let app = build_router().with_state(state);
+        let (status, diff) = get(app.clone(), "/metrics/diff?from=0&to=2").await;
+        assert_eq!(status, StatusCode::OK);
+
+        for ((name, before), (_, after)) in history[0]
+            .scalar_fields()
+            .into_iter()
+            .zip(history[2].scalar_fields())
+        {
+            assert_eq!(
+                diff["deltas"][name].as_f64().unwrap(),
+                after - before,
+                "{}",
+                name
+            );
+        }
+        let most_changed = diff["most_changed"].as_array().unwrap();
+        assert!(!most_changed.is_empty());
+
+        let (status, _) = get(app, "/metrics/diff?from=0&to=3").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_fields_subset() {
+        let uri = "/metrics?fields=v_geometric,quaternion_coherence";
+        let (status, body) = get(test_app(), uri).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let fields = body.as_object().unwrap();
+        assert_eq!(fields.len(), 2);
+        assert!(fields["v_geometric"].is_f64());
+        assert!(fields["quaternion_coherence"].is_f64());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_without_fields_returns_everything() {
+        let (status, body) = get(test_app(), "/metrics").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body["metrics"]["s_geometric"].is_f64());
+        assert!(body["rule_names"].is_array());
+        assert_eq!(body["stale"], false);
+    }
+
+    #[tokio::test]
+    async fn test_metrics_unknown_field_rejected() {
+        let (status, body) = get(test_app(), "/metrics?fields=v_geometric,bogus").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let errors = body["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0]["path"], "fields[1]");
+        assert!(errors[0]["message"].as_str().unwrap().contains("bogus"));
+    }
+
+    #[tokio::test]
+    async fn test_metrics_precision_rounds_responses_only() {
+        let state = test_state();
+        let metrics = crate::core::types::GeometricMetrics {
+            v_geometric: 0.9997000000000001,
+            custom_metrics: [("drift".to_string(), 1.234_567_89)].into(),
+            ..Default::default()
+        };
+        state.processor.set_metrics(metrics.clone());
+        let app = build_router().with_state(state.clone());
+
+        let (status, body) = get(app.clone(), "/metrics?precision=4").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["metrics"]["v_geometric"], 0.9997);
+        assert_eq!(body["metrics"]["custom_metrics"]["drift"], 1.2346);
+
+        let (_, body) = get(app.clone(), "/metrics?fields=drift&precision=2").await;
+        assert_eq!(body["drift"], 1.23);
+        let (_, body) = get(app.clone(), "/metrics/vectorized?precision=4").await;
+        assert_eq!(body["v_geometric"], 0.9997);
+
+        let (_, body) = get(app, "/metrics/vectorized").await;
+        assert_eq!(body["v_geometric"], 0.9997000000000001);
+        assert_eq!(state.processor.get_metrics().unwrap(), metrics);
+    }
+
+    #[tokio::test]
+    async fn test_recompute_metrics_applies_new_rule_set() {
+        use crate::core::emergence_logic::RuleSet;
+
+        let state = test_state();
+        let mut task_ids = Vec::new();
+        for delta in [0.2, 0.1] {
+            let task = GeometricTaskCommand {
+                task_name: "Recompute probe".to_string(),
+                geometric_operator: GeometricOperator::GeometricDerivation,
+                target_module: "sys7_core".to_string(),
+                parameters: serde_json::json!({ "delta": delta }),
+                expected_output_metric: "s_geometric".to_string(),
+                task_id: None,
+                execute_at: None,
+                tags: Vec::new(),
+            };
+            let task_id = state.processor.submit_task(task).unwrap();
+            state.processor.execute_task(task_id).unwrap();
+            task_ids.push(task_id);
+        }
+        let original = state.processor.get_metrics().unwrap();
+        let commands: Vec<_> = task_ids
+            .iter()
+            .map(|id| state.processor.get_task(*id).unwrap())
+            .collect();
+
+        let mut rules = RuleSet::default();
+        rules.geometric_derivation.entropy_gain *= 2.0;
+        state.processor.set_rule_set(rules).unwrap();
+
+        let app = build_router().with_state(state.clone());
+        let request = Request::post("/metrics/recompute")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let recomputed: crate::core::types::GeometricMetrics =
+            serde_json::from_slice(&body).unwrap();
+
+        assert!(recomputed.s_geometric > original.s_geometric);
+        assert_eq!(state.processor.get_metrics().unwrap(), recomputed);
+        assert_eq!(state.processor.history_len().unwrap(), 3);
+        for (id, before) in task_ids.iter().zip(commands) {
+            assert_eq!(state.processor.get_task(*id).unwrap(), before);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_stream_resumes_after_last_event_id() {
+        use futures_util::StreamExt;
+
+        let state = test_state();
+        let execute = |state: &crate::state::AppState| {
+            let task = GeometricTaskCommand::geometric_derivation("Stream probe", 0.1);
+            let task_id = state.processor.submit_task(task).unwrap();
+            state.processor.execute_task(task_id).unwrap();
+        };
+        // History IDs 1 to 3 after the baseline at 0
+        for _ in 0..3 {
+            execute(&state);
+        }
+
+        let app = build_router().with_state(state.clone());
+        let request = Request::get("/metrics/stream")
+            .header("last-event-id", "1")
+            .body(Body::empty())
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+
+        // One live snapshot after the replayed ones
+        execute(&state);
+
+        let mut body = response.into_body().into_data_stream();
+        let mut text = String::new();
+        let mut ids = Vec::new();
+        let read = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            while ids.len() < 3 {
+                text.push_str(std::str::from_utf8(&body.next().await.unwrap().unwrap()).unwrap());
+                while let Some(end) = text.find("\n\n") {
+                    let frame: String = text.drain(..end + 2).collect();
+                    assert!(frame.contains("event: metrics"), "{frame}");
+                    let id = frame
+                        .lines()
+                        .find_map(|line| line.strip_prefix("id: "))
+                        .unwrap();
+                    ids.push(id.parse::<u64>().unwrap());
+                }
+            }
+        })
+        .await;
+        assert!(read.is_ok(), "stream stalled after {ids:?}");
+        assert_eq!(ids, vec![2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_export_metrics_arrow() {
+        let app = test_app();
+        let response = app
+            .oneshot(
+                Request::get("/metrics/export.arrow")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], ARROW_CONTENT_TYPE);
+
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut cursor = std::io::Cursor::new(bytes.to_vec());
+        let metadata = read_file_metadata(&mut cursor).unwrap();
+        assert_eq!(metadata.schema.fields.len(), 9);
+        let chunk = FileReader::new(cursor, metadata, None, None)
+            .next()
+            .unwrap()
+            .unwrap();
+        // A fresh processor only holds the baseline snapshot
+        assert_eq!(chunk.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_export_metrics_csv() {
+        let state = test_state();
+        let task_id = state
+            .processor
+            .submit_task(GeometricTaskCommand {
+                task_name: "CSV probe".to_string(),
+                geometric_operator: GeometricOperator::Zitterbewegung,
+                target_module: "sys7_core".to_string(),
+                parameters: serde_json::json!({}),
+                expected_output_metric: "v_geometric".to_string(),
+                task_id: None,
+                execute_at: None,
+                tags: Vec::new(),
+            })
+            .unwrap();
+        state.processor.execute_task(task_id).unwrap();
+        let history_len = state.processor.history_len().unwrap();
+        assert_eq!(history_len, 2);
+
+        let response = build_router()
+            .with_state(state)
+            .oneshot(
+                Request::get("/metrics/export.csv")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], CSV_CONTENT_TYPE);
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            "attachment; filename=\"metrics.csv\""
+        );
+
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+        let mut lines = text.lines();
+        let header_row: Vec<_> = lines.next().unwrap().split(',').collect();
+        assert_eq!(
+            &header_row[..3],
+            ["timestamp", "v_geometric", "s_geometric"]
+        );
+        assert_eq!(header_row.len(), 9);
+
+        let rows: Vec<Vec<&str>> = lines.map(|line| line.split(',').collect()).collect();
+        assert_eq!(rows.len(), history_len);
+        for row in rows {
+            assert_eq!(row.len(), header_row.len());
+            assert!(chrono::DateTime::parse_from_rfc3339(row[0]).is_ok());
+            for value in &row[1..] {
+                value.parse::<f64>().unwrap();
+            }
+        }
+    }
+}
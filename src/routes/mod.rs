@@ -1,41 +1,237 @@
+pub mod anchors;
+pub mod extract;
+pub mod fields;
 pub mod health;
 pub mod llm;
 pub mod metrics;
+pub mod openapi;
+pub mod physics;
+pub mod quaternion;
+pub mod records;
+pub mod rpc;
 pub mod rules;
+pub mod simulate;
+pub mod stats;
 pub mod tasks;
+pub mod templates;
 pub mod visualization;
+pub mod ws;
 
+#[cfg(test)]
+pub(crate) mod test_support;
+
+use crate::core::error::{Error, FieldError};
 use crate::state::AppState;
-use axum::http::StatusCode;
+use axum::extract::{DefaultBodyLimit, OriginalUri};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
 use axum::{
-    routing::{delete, get, post},
-    Router,
+    routing::{delete, get, get_service, post},
+    Json, Router,
 };
+use serde::Serialize;
+use std::path::Path;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::services::ServeDir;
+use utoipa::ToSchema;
+
+pub type ApiResult<T> = Result<T, Response>;
+
+pub(crate) fn internal_error<E: ToString>(err: E) -> Response {
+    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response()
+}
 
-pub type ApiResult<T> = Result<T, (StatusCode, String)>;
+pub(crate) fn bad_request<E: ToString>(err: E) -> Response {
+    (StatusCode::BAD_REQUEST, err.to_string()).into_response()
+}
+
+pub(crate) fn not_found<E: ToString>(err: E) -> Response {
+    (StatusCode::NOT_FOUND, err.to_string()).into_response()
+}
+
+pub(crate) fn conflict<E: ToString>(err: E) -> Response {
+    (StatusCode::CONFLICT, err.to_string()).into_response()
+}
+
+pub(crate) fn service_unavailable<E: ToString>(err: E) -> Response {
+    (StatusCode::SERVICE_UNAVAILABLE, err.to_string()).into_response()
+}
+
+/// Body returned with a 404 for a path under `/api` that matches no route.
+#[derive(Serialize, ToSchema)]
+pub struct NotFoundResponse {
+    pub error: String,
+    pub path: String,
+}
 
-pub(crate) fn internal_error<E: ToString>(err: E) -> (StatusCode, String) {
-    (StatusCode::INTERNAL_SERVER_ERROR, err.to_string())
+/// Fallback for unmatched `/api` paths, so API clients get JSON instead of
+/// the static file server's 404 page.
+pub async fn api_not_found(OriginalUri(uri): OriginalUri) -> Response {
+    let body = NotFoundResponse {
+        error: "not found".into(),
+        path: uri.path().to_string(),
+    };
+    (StatusCode::NOT_FOUND, Json(body)).into_response()
 }
 
-pub(crate) fn bad_request<E: ToString>(err: E) -> (StatusCode, String) {
-    (StatusCode::BAD_REQUEST, err.to_string())
+/// Body returned with a 400 when a request fails validation.
+#[derive(Serialize, ToSchema)]
+pub struct ValidationErrorResponse {
+    pub errors: Vec<FieldError>,
 }
 
-pub(crate) fn not_found<E: ToString>(err: E) -> (StatusCode, String) {
-    (StatusCode::NOT_FOUND, err.to_string())
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        match self {
+            Error::Validation(errors) => (
+                StatusCode::BAD_REQUEST,
+                Json(ValidationErrorResponse { errors }),
+            )
+                .into_response(),
+            Error::TaskNotFound(_) => not_found(self),
+            Error::InvalidParameter(..) => bad_request(self),
+            Error::CapacityExceeded { retry_after, .. } => {
+                // Retry-After is in whole seconds; never tell clients to retry at once
+                let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+                (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    [(header::RETRY_AFTER, seconds.to_string())],
+                    self.to_string(),
+                )
+                    .into_response()
+            }
+            other => internal_error(other),
+        }
+    }
 }
 
 pub fn build_router() -> Router<AppState> {
     Router::new()
         .route("/health", get(health::health_check))
+        .route("/health/detail", get(health::health_detail))
+        .route(
+            "/anchors",
+            get(anchors::list_anchors).post(anchors::create_anchor),
+        )
+        .route("/anchors/:id", delete(anchors::delete_anchor))
+        .route("/fields/interpolate", post(fields::interpolate_fields))
+        .route("/fields/interpolate_path", post(fields::interpolate_path))
+        .route("/openapi.json", get(openapi::openapi_spec))
         .route("/metrics", get(metrics::get_metrics))
         .route("/metrics/vectorized", get(metrics::get_vectorized_metrics))
+        .route("/metrics/reset", post(metrics::reset_metrics))
+        .route("/metrics/recompute", post(metrics::recompute_metrics))
+        .route("/metrics/stream", get(metrics::stream_metrics))
+        .route("/metrics/diff", get(metrics::diff_metrics_history))
+        .route("/metrics/export.arrow", get(metrics::export_metrics_arrow))
+        .route("/metrics/export.csv", get(metrics::export_metrics_csv))
         .route("/tasks", get(tasks::list_tasks).post(tasks::create_task))
+        .route("/tasks/stream", get(tasks::stream_tasks))
+        .route("/tasks/validate", post(tasks::validate_task))
         .route("/tasks/:id", get(tasks::get_task_status))
+        .route("/tasks/:id/detail", get(tasks::get_task_detail))
+        .route("/tasks/:id/replay", post(tasks::replay_task))
+        .route("/templates", post(templates::register_template))
+        .route(
+            "/templates/:name/instantiate",
+            post(templates::instantiate_template),
+        )
         .route("/llm/query", post(llm::llm_query))
         .route("/llm/research-campaign", post(llm::start_research_campaign))
+        .route("/physics/constants", get(physics::get_constants))
+        .route("/physics/compute", post(physics::compute))
+        .route("/quaternion/multiply", post(quaternion::multiply))
+        .route("/quaternion/rotate", post(quaternion::rotate))
+        .route("/quaternion/slerp", post(quaternion::slerp))
+        .route(
+            "/quaternion/from_axis_angle",
+            post(quaternion::from_axis_angle),
+        )
+        .route("/records/query", post(records::query_records))
+        .route("/rpc", post(rpc::rpc))
         .route("/rules", post(rules::register_rule))
+        .route(
+            "/simulate/orientation",
+            post(simulate::simulate_orientation),
+        )
+        .route("/stats/operators", get(stats::get_operator_stats))
         .route("/rules/:name", delete(rules::delete_rule))
-        .route("/visualization/packet", get(visualization::get_packet))
+        .route("/visualization", post(visualization::create_job))
+        .route(
+            "/visualization/metrics.png",
+            get(visualization::get_metrics_png),
+        )
+        .route("/visualization/:job_id", get(visualization::get_job))
+        .route(
+            "/visualization/packet",
+            get(visualization::get_packet).post(visualization::build_packet),
+        )
+        .route(
+            "/visualization/packet/compressed",
+            get(visualization::get_compressed_packet),
+        )
+        .route("/ws", get(ws::command_socket))
+        .route("/ws/metrics", get(ws::metrics_socket))
+}
+
+/// API router with request bodies capped at `max_body_bytes`; larger
+/// requests are rejected with 413 before any JSON is deserialized.
+pub fn build_router_with_body_limit(max_body_bytes: usize) -> Router<AppState> {
+    build_router()
+        .layer(DefaultBodyLimit::disable())
+        .layer(RequestBodyLimitLayer::new(max_body_bytes))
+}
+
+/// The whole site: `api` nested under `/api`, where unknown paths get a JSON
+/// 404, and files from `static_dir` for every other path.
+pub fn build_app(api: Router, static_dir: impl AsRef<Path>) -> Router {
+    Router::new()
+        .nest("/api", api.fallback(api_not_found))
+        .fallback_service(get_service(ServeDir::new(static_dir)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::test_support::{get, send, test_state};
+    use axum::body::{to_bytes, Body};
+    use axum::http::Request;
+    use tower::ServiceExt;
+
+    #[test]
+    fn test_capacity_exceeded_is_429_with_retry_after() {
+        let response = Error::CapacityExceeded {
+            message: "LLM rate limited".into(),
+            retry_after: std::time::Duration::from_millis(2500),
+        }
+        .into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "3");
+    }
+
+    #[tokio::test]
+    async fn test_api_fallback_is_json_and_static_files_still_served() {
+        let static_dir = tempfile::tempdir().unwrap();
+        std::fs::write(static_dir.path().join("index.html"), "<h1>MMSS</h1>").unwrap();
+        let app = build_app(build_router().with_state(test_state()), static_dir.path());
+
+        let (status, body) = get(app.clone(), "/api/nonexistent").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body["error"], "not found");
+        assert_eq!(body["path"], "/api/nonexistent");
+
+        // Real API routes are unaffected
+        let (status, _) = get(app.clone(), "/api/health").await;
+        assert_eq!(status, StatusCode::OK);
+
+        let request = Request::get("/index.html").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&bytes[..], b"<h1>MMSS</h1>");
+
+        let request = Request::get("/missing.css").body(Body::empty()).unwrap();
+        let (status, _) = send(app, request).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
 }
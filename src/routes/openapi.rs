@@ -0,0 +1,85 @@
+use axum::Json;
+use utoipa::OpenApi;
+
+use super::{
+    anchors, fields, health, llm, metrics, physics, quaternion, records, rpc, rules, simulate,
+    stats, tasks, templates, visualization, ws,
+};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "MMSS API", description = "Geometric task processing and metrics API"),
+    servers((url = "/api")),
+    paths(
+        anchors::list_anchors,
+        anchors::create_anchor,
+        anchors::delete_anchor,
+        health::health_check,
+        health::health_detail,
+        fields::interpolate_fields,
+        fields::interpolate_path,
+        metrics::get_metrics,
+        metrics::get_vectorized_metrics,
+        metrics::reset_metrics,
+        metrics::recompute_metrics,
+        metrics::stream_metrics,
+        metrics::diff_metrics_history,
+        metrics::export_metrics_arrow,
+        metrics::export_metrics_csv,
+        tasks::list_tasks,
+        tasks::stream_tasks,
+        tasks::create_task,
+        tasks::validate_task,
+        tasks::get_task_status,
+        tasks::get_task_detail,
+        tasks::replay_task,
+        templates::register_template,
+        templates::instantiate_template,
+        llm::llm_query,
+        llm::start_research_campaign,
+        physics::get_constants,
+        physics::compute,
+        quaternion::multiply,
+        quaternion::rotate,
+        quaternion::slerp,
+        quaternion::from_axis_angle,
+        records::query_records,
+        rpc::rpc,
+        rules::register_rule,
+        rules::delete_rule,
+        simulate::simulate_orientation,
+        stats::get_operator_stats,
+        visualization::get_packet,
+        visualization::build_packet,
+        visualization::get_compressed_packet,
+        visualization::create_job,
+        visualization::get_job,
+        visualization::get_metrics_png,
+        ws::command_socket,
+        ws::metrics_socket,
+    )
+)]
+pub struct ApiDoc;
+
+pub async fn openapi_spec() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::test_support::{get, test_app};
+    use axum::http::StatusCode;
+
+    #[tokio::test]
+    async fn test_openapi_declares_task_and_metric_paths() {
+        let (status, spec) = get(test_app(), "/openapi.json").await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+
+        let paths = &spec["paths"];
+        assert!(paths["/tasks"]["get"].is_object());
+        assert!(paths["/tasks"]["post"].is_object());
+        assert!(paths["/metrics"]["get"].is_object());
+        assert!(spec["components"]["schemas"]["CreateTaskRequest"].is_object());
+    }
+}
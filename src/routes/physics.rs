@@ -0,0 +1,87 @@
+use axum::{response::IntoResponse, Json};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::core::error::Error;
+use crate::state::{DerivedConstants, PhysicalConstants};
+
+use super::ApiResult;
+
+#[derive(Serialize, ToSchema)]
+pub struct PhysicsResponse {
+    /// The constants the values were derived from
+    pub constants: PhysicalConstants,
+    pub derived: DerivedConstants,
+}
+
+impl From<PhysicalConstants> for PhysicsResponse {
+    fn from(constants: PhysicalConstants) -> Self {
+        Self {
+            constants,
+            derived: constants.derive(),
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/physics/constants",
+    tag = "physics",
+    responses(
+        (status = 200, description = "The built-in constants and the values derived from them", body = PhysicsResponse)
+    )
+)]
+pub async fn get_constants() -> Json<PhysicsResponse> {
+    Json(PhysicalConstants::default().into())
+}
+
+#[utoipa::path(
+    post,
+    path = "/physics/compute",
+    tag = "physics",
+    request_body = PhysicalConstants,
+    responses(
+        (status = 200, description = "Values derived from the given constants; omitted constants keep their defaults. Nothing is stored", body = PhysicsResponse),
+        (status = 400, description = "A constant is not a positive number", body = super::ValidationErrorResponse)
+    )
+)]
+pub async fn compute(Json(constants): Json<PhysicalConstants>) -> ApiResult<Json<PhysicsResponse>> {
+    constants.validate().map_err(Error::into_response)?;
+    Ok(Json(constants.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::test_support::{get, post_json, test_app};
+    use crate::state::{compute_electron_mass, compute_fine_structure};
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_get_constants_returns_defaults() {
+        let (status, body) = get(test_app(), "/physics/constants").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["derived"]["electron_mass"], compute_electron_mass());
+        assert_eq!(body["derived"]["fine_structure"], compute_fine_structure());
+        assert_eq!(body["constants"]["zitter_amplitude"], 1.93e-13);
+    }
+
+    #[tokio::test]
+    async fn test_compute_with_changed_amplitude() {
+        let app = test_app();
+        let request = json!({ "zitter_amplitude": 3.86e-13 });
+        let (status, body) = post_json(app.clone(), "/physics/compute", &request).await;
+        assert_eq!(status, StatusCode::OK);
+        let mass = body["derived"]["electron_mass"].as_f64().unwrap();
+        assert!((mass - compute_electron_mass() / 2.0).abs() < 1e-40);
+        assert_eq!(body["derived"]["fine_structure"], compute_fine_structure());
+
+        // The defaults are untouched
+        let (_, body) = get(app.clone(), "/physics/constants").await;
+        assert_eq!(body["derived"]["electron_mass"], compute_electron_mass());
+
+        let (status, body) = post_json(app, "/physics/compute", &json!({ "c": -1.0 })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["errors"][0]["path"], "c");
+    }
+}
@@ -0,0 +1,209 @@
+use axum::Json;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::core::types::Quaternion;
+
+use super::extract::ApiJson;
+use super::{bad_request, ApiResult};
+
+// Quaternion components are checked for NaN and infinity when they are
+// deserialized; the other numeric inputs are checked here.
+
+#[derive(Deserialize, ToSchema)]
+pub struct MultiplyRequest {
+    pub a: Quaternion,
+    pub b: Quaternion,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct RotateRequest {
+    /// Rotation to apply; normalized first
+    pub q: Quaternion,
+    pub vector: [f64; 3],
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SlerpRequest {
+    pub from: Quaternion,
+    pub to: Quaternion,
+    /// Interpolation parameter in [0, 1]
+    pub t: f64,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct FromAxisAngleRequest {
+    /// Rotation axis; need not be unit length
+    pub axis: [f64; 3],
+    /// Angle in radians
+    pub angle: f64,
+}
+
+fn all_finite(values: &[f64]) -> bool {
+    values.iter().all(|value| value.is_finite())
+}
+
+#[utoipa::path(
+    post,
+    path = "/quaternion/multiply",
+    tag = "quaternion",
+    request_body = MultiplyRequest,
+    responses(
+        (status = 200, description = "The Hamilton product a * b", body = Quaternion),
+        (status = 400, description = "Malformed or non-finite quaternion", body = String)
+    )
+)]
+pub async fn multiply(ApiJson(request): ApiJson<MultiplyRequest>) -> Json<Quaternion> {
+    Json(request.a.multiply(&request.b))
+}
+
+#[utoipa::path(
+    post,
+    path = "/quaternion/rotate",
+    tag = "quaternion",
+    request_body = RotateRequest,
+    responses(
+        (status = 200, description = "The rotated vector", body = [f64]),
+        (status = 400, description = "Malformed or non-finite input", body = String)
+    )
+)]
+pub async fn rotate(ApiJson(request): ApiJson<RotateRequest>) -> ApiResult<Json<[f64; 3]>> {
+    if !all_finite(&request.vector) {
+        return Err(bad_request("vector must be finite"));
+    }
+    Ok(Json(request.q.rotate_vector(request.vector)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/quaternion/slerp",
+    tag = "quaternion",
+    request_body = SlerpRequest,
+    responses(
+        (status = 200, description = "Interpolated rotation along the shorter arc", body = Quaternion),
+        (status = 400, description = "Malformed or non-finite input, or t outside [0, 1]", body = String)
+    )
+)]
+pub async fn slerp(ApiJson(request): ApiJson<SlerpRequest>) -> ApiResult<Json<Quaternion>> {
+    if !(0.0..=1.0).contains(&request.t) {
+        return Err(bad_request("t must be within [0, 1]"));
+    }
+    Ok(Json(request.from.slerp(&request.to, request.t)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/quaternion/from_axis_angle",
+    tag = "quaternion",
+    request_body = FromAxisAngleRequest,
+    responses(
+        (status = 200, description = "Unit quaternion for the rotation; the identity for a zero axis", body = Quaternion),
+        (status = 400, description = "Malformed or non-finite input", body = String)
+    )
+)]
+pub async fn from_axis_angle(
+    ApiJson(request): ApiJson<FromAxisAngleRequest>,
+) -> ApiResult<Json<Quaternion>> {
+    if !all_finite(&request.axis) || !request.angle.is_finite() {
+        return Err(bad_request("axis and angle must be finite"));
+    }
+    Ok(Json(Quaternion::from_axis_angle(
+        request.axis,
+        request.angle,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::types::Quaternion;
+    use crate::routes::test_support::{post_json, send, test_app};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_multiply_route_matches_method() {
+        let (a, b) = (
+            Quaternion::new(0.5, 0.5, -0.5, 0.5),
+            Quaternion::new(0.9, 0.1, 0.3, -0.2),
+        );
+        let (status, body) = post_json(
+            test_app(),
+            "/quaternion/multiply",
+            &json!({ "a": a, "b": b }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!(a.multiply(&b)));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_route_matches_method() {
+        let rotation = Quaternion::from_axis_angle([1.0, 2.0, -0.5], 0.8);
+        let request = json!({ "q": rotation, "vector": [0.3, -1.0, 2.5] });
+        let (status, body) = post_json(test_app(), "/quaternion/rotate", &request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!(rotation.rotate_vector([0.3, -1.0, 2.5])));
+    }
+
+    #[tokio::test]
+    async fn test_slerp_route_matches_method() {
+        let from = Quaternion::identity();
+        let to = Quaternion::from_axis_angle([0.0, 1.0, 0.0], 2.0);
+        let (status, body) = post_json(
+            test_app(),
+            "/quaternion/slerp",
+            &json!({ "from": from, "to": to, "t": 0.3 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!(from.slerp(&to, 0.3)));
+
+        let (status, _) = post_json(
+            test_app(),
+            "/quaternion/slerp",
+            &json!({ "from": from, "to": to, "t": 1.5 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_from_axis_angle_route_matches_method() {
+        let (status, body) = post_json(
+            test_app(),
+            "/quaternion/from_axis_angle",
+            &json!({ "axis": [0.0, 3.0, 4.0], "angle": 1.2 }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            json!(Quaternion::from_axis_angle([0.0, 3.0, 4.0], 1.2))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quaternion_routes_reject_non_finite_input() {
+        // JSON has no NaN literal, and numbers too large for an f64 are
+        // refused by the parser; both come back as descriptive 400s
+        for (uri, body) in [
+            (
+                "/quaternion/multiply",
+                r#"{"a": {"w": NaN, "x": 0, "y": 0, "z": 0}, "b": {"w": 1, "x": 0, "y": 0, "z": 0}}"#,
+            ),
+            (
+                "/quaternion/from_axis_angle",
+                r#"{"axis": [1e400, 0, 0], "angle": 1}"#,
+            ),
+        ] {
+            let request = Request::post(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap();
+            let (status, body) = send(test_app(), request).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{uri}");
+            assert!(body["error"].is_string(), "{uri}: {body}");
+        }
+    }
+}
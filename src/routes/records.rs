@@ -0,0 +1,133 @@
+use axum::Json;
+use mmss_core::structex_bridge::{MmssRecord, PatternMatcher, PayloadSchema};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use super::{bad_request, internal_error, ApiResult};
+
+#[derive(Deserialize, ToSchema)]
+pub struct RecordQueryRequest {
+    pub pattern: String,
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub records: Vec<MmssRecord>,
+    /// JSON Schema every record payload must satisfy; the request is
+    /// rejected if any record does not
+    #[serde(default)]
+    #[schema(value_type = Option<Object>)]
+    pub schema: Option<serde_json::Value>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/records/query",
+    tag = "records",
+    request_body = RecordQueryRequest,
+    responses(
+        (status = 200, description = "Records matching the pattern", body = Vec<Object>),
+        (status = 400, description = "Pattern or schema failed to compile, or a record violates the schema", body = String),
+        (status = 500, description = "Pattern failed to evaluate", body = String)
+    )
+)]
+pub async fn query_records(
+    Json(payload): Json<RecordQueryRequest>,
+) -> ApiResult<Json<Vec<MmssRecord>>> {
+    let matcher = PatternMatcher::new(&payload.pattern).map_err(bad_request)?;
+    if let Some(schema) = &payload.schema {
+        let schema = PayloadSchema::new(schema).map_err(bad_request)?;
+        for record in &payload.records {
+            schema.check(record).map_err(bad_request)?;
+        }
+    }
+
+    let mut matched = Vec::new();
+    for record in payload.records {
+        if matcher.matches(&record).map_err(internal_error)? {
+            matched.push(record);
+        }
+    }
+
+    Ok(Json(matched))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::test_support::{post_json, test_app};
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    async fn post_query(body: Value) -> (StatusCode, Value) {
+        post_json(test_app(), "/records/query", &body).await
+    }
+
+    #[tokio::test]
+    async fn test_query_records_returns_matching_kind() {
+        let records: Vec<Value> = ["cpu", "memory", "cpu", "disk"]
+            .iter()
+            .enumerate()
+            .map(|(i, kind)| {
+                json!({
+                    "id": i,
+                    "kind": kind,
+                    "timestamp": 1732400000 + i as i64 * 60,
+                    "payload": { "value": i as f64 * 10.0 }
+                })
+            })
+            .collect();
+
+        let (status, body) = post_query(json!({
+            "pattern": "kind == \"cpu\"",
+            "records": records,
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let ids: Vec<u64> = body
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["id"].as_u64().unwrap())
+            .collect();
+        assert_eq!(ids, vec![0, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_query_records_rejects_invalid_pattern() {
+        let (status, _) = post_query(json!({ "pattern": "kind ~ cpu", "records": [] })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_query_records_enforces_payload_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": { "value": { "type": "number" } },
+            "required": ["value"]
+        });
+        let record = |id: u64, value: Value| {
+            json!({
+                "id": id,
+                "kind": "cpu",
+                "timestamp": 1732400000,
+                "payload": { "value": value }
+            })
+        };
+
+        let (status, body) = post_query(json!({
+            "pattern": "",
+            "records": [record(1, json!(1.5))],
+            "schema": schema,
+        }))
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.as_array().unwrap().len(), 1);
+
+        let (status, _) = post_query(json!({
+            "pattern": "",
+            "records": [record(1, json!(1.5)), record(9, json!("high"))],
+            "schema": schema,
+        }))
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,277 @@
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use tracing::Span;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::core::error::Error;
+use crate::core::semantic_task_processor::TaskStatus;
+use crate::state::AppState;
+
+use super::tasks::{CreateTaskRequest, CreateTaskResponse, TaskListItem};
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+/// Server-defined: the referenced task does not exist
+pub const TASK_NOT_FOUND: i64 = -32001;
+
+/// A JSON-RPC 2.0 request. A request without an `id` is a notification and
+/// gets no response body.
+#[derive(Deserialize, ToSchema)]
+pub struct RpcRequest {
+    /// Must be `"2.0"`
+    pub jsonrpc: String,
+    /// One of `submit_task`, `get_status`, `list_tasks`, `get_metrics`
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+    pub id: Option<Value>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+    /// Echoes the request `id`; `null` when it could not be read
+    pub id: Value,
+}
+
+impl RpcResponse {
+    fn new(id: Value, outcome: Result<Value, RpcError>) -> Self {
+        let (result, error) = match outcome {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        Self {
+            jsonrpc: "2.0",
+            result,
+            error,
+            id,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TaskIdParams {
+    task_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/rpc",
+    tag = "tasks",
+    request_body = RpcRequest,
+    responses(
+        (status = 200, description = "JSON-RPC 2.0 response; failures are reported in `error`", body = RpcResponse),
+        (status = 204, description = "The request was a notification")
+    )
+)]
+pub async fn rpc(State(state): State<AppState>, body: Bytes) -> Response {
+    let value: Value = match serde_json::from_slice(&body) {
+        Ok(value) => value,
+        Err(err) => {
+            let error = RpcError::new(PARSE_ERROR, format!("Parse error: {}", err));
+            return Json(RpcResponse::new(Value::Null, Err(error))).into_response();
+        }
+    };
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+
+    let request = match serde_json::from_value::<RpcRequest>(value) {
+        Ok(request) if request.jsonrpc == "2.0" => request,
+        Ok(_) => {
+            let error = RpcError::new(INVALID_REQUEST, "Invalid request: jsonrpc must be \"2.0\"");
+            return Json(RpcResponse::new(id, Err(error))).into_response();
+        }
+        Err(err) => {
+            let error = RpcError::new(INVALID_REQUEST, format!("Invalid request: {}", err));
+            return Json(RpcResponse::new(id, Err(error))).into_response();
+        }
+    };
+
+    let outcome = dispatch(&state, &request.method, request.params).await;
+    match request.id {
+        Some(id) => Json(RpcResponse::new(id, outcome)).into_response(),
+        None => StatusCode::NO_CONTENT.into_response(),
+    }
+}
+
+async fn dispatch(state: &AppState, method: &str, params: Value) -> Result<Value, RpcError> {
+    match method {
+        "submit_task" => {
+            let request: CreateTaskRequest = parse_params(params)?;
+            let scheduled = request.task.execute_at.is_some();
+            let task_id = state
+                .processor
+                .submit_task_with(request.task, request.deterministic)
+                .map_err(processor_error)?;
+
+            let response = if request.execute && !scheduled {
+                // Execution sleeps for the configured delay, so keep it off
+                // the async workers as the REST route does.
+                let processor = state.processor.clone();
+                let span = Span::current();
+                let result = tokio::task::spawn_blocking(move || {
+                    span.in_scope(|| processor.execute_task(task_id))
+                })
+                .await
+                .unwrap_or_else(|err| {
+                    Err(Error::TaskExecution(format!("execution panicked: {}", err)))
+                })
+                .map_err(processor_error)?;
+                CreateTaskResponse {
+                    task_id,
+                    status: TaskStatus::from_result(&result),
+                    execution_result: Some(result),
+                }
+            } else {
+                CreateTaskResponse {
+                    task_id,
+                    status: TaskStatus::Pending,
+                    execution_result: None,
+                }
+            };
+            to_result(response)
+        }
+        "get_status" => {
+            let TaskIdParams { task_id } = parse_params(params)?;
+            let (_, status) = state.processor.get_task(task_id).map_err(processor_error)?;
+            to_result(TaskListItem { task_id, status })
+        }
+        "list_tasks" => {
+            let tasks = state.processor.list_tasks().map_err(processor_error)?;
+            let items: Vec<_> = tasks
+                .into_iter()
+                .map(|(task_id, status)| TaskListItem { task_id, status })
+                .collect();
+            to_result(items)
+        }
+        "get_metrics" => to_result(state.processor.get_metrics().map_err(processor_error)?),
+        other => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            format!("Method not found: {}", other),
+        )),
+    }
+}
+
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|err| RpcError::new(INVALID_PARAMS, format!("Invalid params: {}", err)))
+}
+
+fn to_result<T: Serialize>(value: T) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|err| RpcError::new(INTERNAL_ERROR, err.to_string()))
+}
+
+fn processor_error(err: Error) -> RpcError {
+    match err {
+        Error::Validation(ref errors) => RpcError {
+            code: INVALID_PARAMS,
+            message: err.to_string(),
+            data: serde_json::to_value(errors).ok(),
+        },
+        Error::TaskNotFound(_) => RpcError::new(TASK_NOT_FOUND, err.to_string()),
+        other => RpcError::new(INTERNAL_ERROR, other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::test_support::{post_json, test_app};
+    use serde_json::json;
+
+    fn submit_params() -> Value {
+        json!({
+            "task": {
+                "task_name": "RPC probe",
+                "geometric_operator": "QuaternionRotation",
+                "target_module": "sys7_core",
+                "parameters": { "theta": 0.5 },
+                "expected_output_metric": "v_geometric"
+            },
+            "execute": false
+        })
+    }
+
+    #[tokio::test]
+    async fn test_rpc_submit_and_get_status() {
+        let app = test_app();
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": "submit_task",
+            "params": submit_params(),
+            "id": 1
+        });
+        let (status, response) = post_json(app.clone(), "/rpc", &request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["jsonrpc"], "2.0");
+        assert_eq!(response["id"], 1);
+        assert!(response.get("error").is_none());
+        let task_id = response["result"]["task_id"].clone();
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": "get_status",
+            "params": { "task_id": task_id },
+            "id": "status-1"
+        });
+        let (_, response) = post_json(app, "/rpc", &request).await;
+        assert_eq!(response["id"], "status-1");
+        assert_eq!(response["result"]["task_id"], task_id);
+        assert_eq!(response["result"]["status"], "Pending");
+    }
+
+    #[tokio::test]
+    async fn test_rpc_method_not_found() {
+        let request = json!({ "jsonrpc": "2.0", "method": "drop_tables", "id": 7 });
+        let (status, response) = post_json(test_app(), "/rpc", &request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response["id"], 7);
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+        assert!(response.get("result").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rpc_invalid_params() {
+        let request = json!({
+            "jsonrpc": "2.0",
+            "method": "get_status",
+            "params": { "task_id": "not-a-uuid" },
+            "id": 3
+        });
+        let (_, response) = post_json(test_app(), "/rpc", &request).await;
+        assert_eq!(response["id"], 3);
+        assert_eq!(response["error"]["code"], INVALID_PARAMS);
+    }
+}
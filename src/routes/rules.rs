@@ -3,13 +3,14 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use crate::core::types::GeometricMetrics;
 use crate::state::AppState;
 
 use super::{bad_request, not_found, ApiResult};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct RegisterRuleRequest {
     pub name: String,
     pub delta_v: Option<f64>,
@@ -17,12 +18,22 @@ pub struct RegisterRuleRequest {
     pub delta_q: Option<f64>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct RegisterRuleResponse {
     pub registered: bool,
     pub rule_count: usize,
 }
 
+#[utoipa::path(
+    post,
+    path = "/rules",
+    tag = "rules",
+    request_body = RegisterRuleRequest,
+    responses(
+        (status = 200, description = "Rule registered", body = RegisterRuleResponse),
+        (status = 400, description = "Invalid rule", body = String)
+    )
+)]
 pub async fn register_rule(
     State(state): State<AppState>,
     Json(payload): Json<RegisterRuleRequest>,
@@ -54,6 +65,16 @@ pub async fn register_rule(
     Ok(Json(response))
 }
 
+#[utoipa::path(
+    delete,
+    path = "/rules/{name}",
+    tag = "rules",
+    params(("name" = String, Path, description = "Rule name")),
+    responses(
+        (status = 200, description = "Rule removed", body = RegisterRuleResponse),
+        (status = 404, description = "Rule not found", body = String)
+    )
+)]
 pub async fn delete_rule(
     Path(name): Path<String>,
     State(state): State<AppState>,
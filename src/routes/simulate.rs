@@ -0,0 +1,106 @@
+use axum::Json;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::core::types::Quaternion;
+
+use super::extract::ApiJson;
+use super::{bad_request, ApiResult};
+
+/// Most steps accepted by `/simulate/orientation`
+pub const MAX_ORIENTATION_STEPS: usize = 10_000;
+
+#[derive(Deserialize, ToSchema)]
+pub struct OrientationStep {
+    /// World-frame angular velocity in rad/s
+    pub angular_velocity: [f64; 3],
+    /// Step length in seconds
+    pub dt: f64,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct OrientationRequest {
+    pub initial: Quaternion,
+    pub steps: Vec<OrientationStep>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/simulate/orientation",
+    tag = "physics",
+    request_body = OrientationRequest,
+    responses(
+        (status = 200, description = "Orientation after each step, in step order", body = Vec<Quaternion>),
+        (status = 400, description = "Too many steps, or a non-finite velocity or dt", body = String)
+    )
+)]
+pub async fn simulate_orientation(
+    ApiJson(payload): ApiJson<OrientationRequest>,
+) -> ApiResult<Json<Vec<Quaternion>>> {
+    if payload.steps.len() > MAX_ORIENTATION_STEPS {
+        return Err(bad_request(format!(
+            "at most {} steps are allowed, got {}",
+            MAX_ORIENTATION_STEPS,
+            payload.steps.len()
+        )));
+    }
+    if let Some(index) = payload.steps.iter().position(|step| {
+        !step.dt.is_finite() || step.angular_velocity.iter().any(|w| !w.is_finite())
+    }) {
+        return Err(bad_request(format!(
+            "step {} has a non-finite angular velocity or dt",
+            index
+        )));
+    }
+
+    let orientations = payload
+        .steps
+        .iter()
+        .scan(payload.initial.normalize(), |orientation, step| {
+            *orientation = orientation.integrate(step.angular_velocity, step.dt);
+            Some(*orientation)
+        })
+        .collect();
+    Ok(Json(orientations))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MAX_ORIENTATION_STEPS;
+    use crate::routes::test_support::{post_json, test_app};
+    use axum::http::StatusCode;
+    use serde_json::json;
+    use std::f64::consts::FRAC_PI_2;
+
+    #[tokio::test]
+    async fn test_constant_angular_velocity_reaches_known_rotation() {
+        // A quarter turn about z, in ten equal steps
+        let step = json!({ "angular_velocity": [0.0, 0.0, 1.0], "dt": FRAC_PI_2 / 10.0 });
+        let request = json!({
+            "initial": { "w": 1.0, "x": 0.0, "y": 0.0, "z": 0.0 },
+            "steps": vec![step; 10],
+        });
+        let (status, body) = post_json(test_app(), "/simulate/orientation", &request).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let path = body.as_array().unwrap();
+        assert_eq!(path.len(), 10);
+        let last = &path[9];
+        let half = FRAC_PI_2 / 2.0;
+        for (key, expected) in [("w", half.cos()), ("x", 0.0), ("y", 0.0), ("z", half.sin())] {
+            let delta = last[key].as_f64().unwrap() - expected;
+            assert!(delta.abs() < 1e-12, "{key}: {last}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_orientation_step_cap() {
+        let step = json!({ "angular_velocity": [1.0, 0.0, 0.0], "dt": 0.01 });
+        let request = json!({
+            "initial": { "w": 1.0, "x": 0.0, "y": 0.0, "z": 0.0 },
+            "steps": vec![step; MAX_ORIENTATION_STEPS + 1],
+        });
+        let (status, _) = post_json(test_app(), "/simulate/orientation", &request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use axum::{extract::State, Json};
+
+use crate::core::types::GeometricOperator;
+use crate::state::AppState;
+
+use super::{internal_error, ApiResult};
+
+#[utoipa::path(
+    get,
+    path = "/stats/operators",
+    tag = "stats",
+    responses(
+        (status = 200, description = "Execution count per geometric operator", body = HashMap<String, u64>),
+        (status = 500, description = "Statistics unavailable", body = String)
+    )
+)]
+pub async fn get_operator_stats(
+    State(state): State<AppState>,
+) -> ApiResult<Json<HashMap<GeometricOperator, u64>>> {
+    let stats = state.processor.operator_stats().map_err(internal_error)?;
+    Ok(Json(stats))
+}
@@ -1,76 +1,238 @@
 use axum::{
-    extract::{Path, State},
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap},
+    response::{IntoResponse, Response},
     Json,
 };
+use futures_util::stream;
 use serde::{Deserialize, Serialize};
+use tracing::{Instrument, Span};
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
+use crate::core::error::{Error, FieldError};
 use crate::core::semantic_task_processor::TaskStatus;
+pub use crate::core::types::CreateTaskResponse;
 use crate::core::types::{GeometricTaskCommand, TaskExecutionResult};
+use crate::core::validation::lint_task_command;
+use crate::state::idempotency::Reservation;
 use crate::state::AppState;
 
-use super::{bad_request, internal_error, not_found, ApiResult};
+use super::extract::ApiJson;
+use super::{bad_request, conflict, internal_error, not_found, ApiResult};
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct CreateTaskRequest {
     pub task: GeometricTaskCommand,
     #[serde(default = "default_execute")]
     pub execute: bool,
+    /// Derive the task ID from the command's content when it has none, so a
+    /// retried submission is rejected instead of duplicated
+    #[serde(default)]
+    pub deterministic: bool,
 }
 
-#[derive(Serialize)]
-pub struct CreateTaskResponse {
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct TaskListItem {
     pub task_id: Uuid,
     pub status: TaskStatus,
-    pub execution_result: Option<TaskExecutionResult>,
 }
 
-#[derive(Serialize)]
-pub struct TaskListItem {
+#[derive(Serialize, ToSchema)]
+pub struct TaskDetail {
     pub task_id: Uuid,
+    /// The command as it was submitted
+    pub command: GeometricTaskCommand,
     pub status: TaskStatus,
 }
 
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
 fn default_execute() -> bool {
     true
 }
 
+#[utoipa::path(
+    post,
+    path = "/tasks",
+    tag = "tasks",
+    request_body = CreateTaskRequest,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays the original response for a repeated key. If execution failed, the replay carries the failed task's status.")
+    ),
+    responses(
+        (status = 200, description = "Task submitted and optionally executed", body = CreateTaskResponse),
+        (status = 409, description = "A request with the same Idempotency-Key is still in progress", body = String),
+        (status = 400, description = "Body is not a valid task request, or the task failed validation", body = super::ValidationErrorResponse),
+        (status = 413, description = "Request body too large"),
+        (status = 500, description = "Task execution failed", body = String)
+    )
+)]
 pub async fn create_task(
     State(state): State<AppState>,
-    Json(payload): Json<CreateTaskRequest>,
+    headers: HeaderMap,
+    ApiJson(payload): ApiJson<CreateTaskRequest>,
 ) -> ApiResult<Json<CreateTaskResponse>> {
-    let task_id = state
-        .processor
-        .submit_task(payload.task)
-        .map_err(|err| bad_request(err.to_string()))?;
+    let key = match headers.get(IDEMPOTENCY_KEY) {
+        Some(value) => Some(
+            value
+                .to_str()
+                .map_err(|_| bad_request("Invalid Idempotency-Key header"))?
+                .to_string(),
+        ),
+        None => None,
+    };
 
-    if payload.execute {
-        let result = state
-            .processor
-            .execute_task(task_id)
-            .map_err(|err| internal_error(err.to_string()))?;
+    if let Some(key) = &key {
+        match state.idempotency.lock().await.reserve(key) {
+            Reservation::Stored(previous) => return Ok(Json(previous)),
+            Reservation::InFlight => {
+                return Err(conflict(
+                    "A request with this Idempotency-Key is still in progress",
+                ))
+            }
+            Reservation::Reserved => {}
+        }
+    }
 
-        let response = CreateTaskResponse {
-            task_id,
-            status: TaskStatus::Completed(result.metrics.clone()),
-            execution_result: Some(result),
-        };
-        Ok(Json(response))
-    } else {
+    // Run detached, so a client disconnecting mid-request cannot leave the
+    // key reserved or the task half recorded.
+    let outcome = tokio::spawn({
+        let state = state.clone();
+        async move {
+            let outcome = submit_and_execute(&state, payload).await;
+            if let Some(key) = key {
+                let mut store = state.idempotency.lock().await;
+                match &outcome {
+                    Ok((response, _)) => store.insert(key, response.clone()),
+                    // Nothing was created, so a corrected retry may reuse the key
+                    Err(_) => store.release(&key),
+                }
+            }
+            outcome
+        }
+        .in_current_span()
+    })
+    .await
+    .map_err(internal_error)?;
+
+    match outcome {
+        Ok((response, None)) => Ok(Json(response)),
+        Ok((_, Some(err))) => Err(err.into_response()),
+        Err(err @ Error::Validation(_)) => Err(err.into_response()),
+        Err(other) => Err(bad_request(other)),
+    }
+}
+
+/// Submit the requested task and run it unless it is scheduled or `execute`
+/// is off. Once the task exists a response is always produced, together with
+/// the execution error if running it failed.
+async fn submit_and_execute(
+    state: &AppState,
+    request: CreateTaskRequest,
+) -> Result<(CreateTaskResponse, Option<Error>), Error> {
+    // Scheduled tasks are left for the scheduler even when `execute` is set
+    let scheduled = request.task.execute_at.is_some();
+    let task_id = state
+        .processor
+        .submit_task_with(request.task, request.deterministic)?;
+    if !request.execute || scheduled {
         let response = CreateTaskResponse {
             task_id,
             status: TaskStatus::Pending,
             execution_result: None,
         };
-        Ok(Json(response))
+        return Ok((response, None));
     }
+
+    // Execution sleeps for the configured delay, so keep it off the async
+    // workers as the scheduler does.
+    let processor = state.processor.clone();
+    let span = Span::current();
+    let executed =
+        tokio::task::spawn_blocking(move || span.in_scope(|| processor.execute_task(task_id)))
+            .await
+            .unwrap_or_else(|err| {
+                Err(Error::TaskExecution(format!("execution panicked: {}", err)))
+            });
+
+    Ok(match executed {
+        Ok(result) => {
+            let response = CreateTaskResponse {
+                task_id,
+                status: TaskStatus::from_result(&result),
+                execution_result: Some(result),
+            };
+            (response, None)
+        }
+        Err(err) => {
+            let status = state
+                .processor
+                .get_task_status(task_id)
+                .unwrap_or_else(|_| TaskStatus::Failed(err.to_string()));
+            let response = CreateTaskResponse {
+                task_id,
+                status,
+                execution_result: None,
+            };
+            (response, Some(err))
+        }
+    })
 }
 
-pub async fn list_tasks(State(state): State<AppState>) -> ApiResult<Json<Vec<TaskListItem>>> {
-    let tasks = state
-        .processor
-        .list_tasks()
-        .map_err(|err| internal_error(err.to_string()))?;
+#[derive(Serialize, ToSchema)]
+pub struct ValidateTaskResponse {
+    pub valid: bool,
+    /// Every problem found; empty when `valid`
+    pub errors: Vec<FieldError>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/tasks/validate",
+    tag = "tasks",
+    request_body = GeometricTaskCommand,
+    responses(
+        (status = 200, description = "Validation outcome; nothing is submitted", body = ValidateTaskResponse),
+        (status = 400, description = "Body is not a task command", body = super::extract::JsonErrorResponse)
+    )
+)]
+pub async fn validate_task(
+    ApiJson(command): ApiJson<GeometricTaskCommand>,
+) -> Json<ValidateTaskResponse> {
+    let errors = lint_task_command(&command);
+    Json(ValidateTaskResponse {
+        valid: errors.is_empty(),
+        errors,
+    })
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct ListTasksQuery {
+    /// Only list tasks carrying this tag
+    pub tag: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/tasks",
+    tag = "tasks",
+    params(ListTasksQuery),
+    responses(
+        (status = 200, description = "All known tasks, or those with the given tag, oldest submission first", body = Vec<TaskListItem>),
+        (status = 500, description = "Task storage unavailable", body = String)
+    )
+)]
+pub async fn list_tasks(
+    State(state): State<AppState>,
+    Query(query): Query<ListTasksQuery>,
+) -> ApiResult<Json<Vec<TaskListItem>>> {
+    let tasks = match query.tag {
+        Some(tag) => state.processor.list_tasks_by_tag(&tag),
+        None => state.processor.list_tasks(),
+    }
+    .map_err(|err| internal_error(err.to_string()))?;
 
     let summaries = tasks
         .into_iter()
@@ -80,6 +242,52 @@ pub async fn list_tasks(State(state): State<AppState>) -> ApiResult<Json<Vec<Tas
     Ok(Json(summaries))
 }
 
+#[utoipa::path(
+    get,
+    path = "/tasks/stream",
+    tag = "tasks",
+    responses(
+        (status = 200, description = "One JSON-encoded task per line, in submission order",
+            body = TaskListItem, content_type = "application/x-ndjson")
+    )
+)]
+pub async fn stream_tasks(State(state): State<AppState>) -> ApiResult<Response> {
+    // Only the IDs are snapshotted up front; each status is looked up and
+    // serialized as its line is sent. Tasks removed in the meantime are skipped.
+    let ids = state
+        .processor
+        .task_ids()
+        .map_err(|err| internal_error(err.to_string()))?;
+    let processor = state.processor.clone();
+
+    let lines = ids.into_iter().filter_map(move |task_id| {
+        let status = processor.get_task_status(task_id).ok()?;
+        Some(
+            serde_json::to_vec(&TaskListItem { task_id, status }).map(|mut line| {
+                line.push(b'\n');
+                Bytes::from(line)
+            }),
+        )
+    });
+
+    Ok((
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(stream::iter(lines)),
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/tasks/{id}",
+    tag = "tasks",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Task status", body = TaskListItem),
+        (status = 400, description = "Invalid task ID", body = String),
+        (status = 404, description = "Task not found", body = String)
+    )
+)]
 pub async fn get_task_status(
     Path(task_id): Path<String>,
     State(state): State<AppState>,
@@ -96,3 +304,294 @@ pub async fn get_task_status(
         status,
     }))
 }
+
+#[utoipa::path(
+    get,
+    path = "/tasks/{id}/detail",
+    tag = "tasks",
+    params(("id" = Uuid, Path, description = "Task ID")),
+    responses(
+        (status = 200, description = "Submitted command and current status", body = TaskDetail),
+        (status = 400, description = "Invalid task ID", body = String),
+        (status = 404, description = "Task not found", body = String),
+        (status = 500, description = "Task storage unavailable", body = String)
+    )
+)]
+pub async fn get_task_detail(
+    Path(task_id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<TaskDetail>> {
+    let id = Uuid::parse_str(&task_id).map_err(|_| bad_request("Invalid task ID"))?;
+
+    let (command, status) = state.processor.get_task(id).map_err(|err| match err {
+        Error::TaskNotFound(_) => not_found(err),
+        other => internal_error(other),
+    })?;
+
+    Ok(Json(TaskDetail {
+        task_id: id,
+        command,
+        status,
+    }))
+}
+
+#[utoipa::path(
+    post,
+    path = "/tasks/{id}/replay",
+    tag = "tasks",
+    params(("id" = Uuid, Path, description = "Task to replay")),
+    responses(
+        (status = 200, description = "Result of the replayed execution", body = TaskExecutionResult),
+        (status = 400, description = "Invalid task ID", body = String),
+        (status = 404, description = "Task not found", body = String),
+        (status = 500, description = "Task execution failed", body = String)
+    )
+)]
+pub async fn replay_task(
+    Path(task_id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<TaskExecutionResult>> {
+    let id = Uuid::parse_str(&task_id).map_err(|_| bad_request("Invalid task ID"))?;
+
+    let result = state.processor.replay_task(id).map_err(|err| match err {
+        Error::TaskNotFound(_) => not_found(err),
+        other => internal_error(other),
+    })?;
+
+    Ok(Json(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::test_support::{get, post_json, send, test_app, test_state};
+    use crate::routes::{build_router, build_router_with_body_limit};
+    use axum::body::Body;
+    use axum::http::{Request, StatusCode};
+    use serde_json::json;
+
+    fn task_request(padding: usize) -> serde_json::Value {
+        json!({
+            "task": {
+                "task_name": "Body limit probe",
+                "geometric_operator": "QuaternionRotation",
+                "target_module": "sys7_core",
+                "parameters": { "padding": "x".repeat(padding) },
+                "expected_output_metric": "v_geometric"
+            },
+            "execute": false
+        })
+    }
+
+    #[tokio::test]
+    async fn test_create_task_respects_body_limit() {
+        let limit = 1024;
+        let app = build_router_with_body_limit(limit).with_state(test_state());
+
+        let small = task_request(16);
+        assert!(small.to_string().len() < limit);
+        let (status, _) = post_json(app.clone(), "/tasks", &small).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let large = task_request(limit);
+        assert!(large.to_string().len() > limit);
+        let (status, _) = post_json(app, "/tasks", &large).await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    }
+
+    async fn post_with_key(app: axum::Router, key: &str) -> serde_json::Value {
+        let request = Request::post("/tasks")
+            .header("content-type", "application/json")
+            .header("idempotency-key", key)
+            .body(Body::from(task_request(0).to_string()))
+            .unwrap();
+        let (status, body) = send(app, request).await;
+        assert_eq!(status, StatusCode::OK);
+        body
+    }
+
+    #[tokio::test]
+    async fn test_create_task_malformed_body_is_descriptive_400() {
+        let mut request = task_request(0);
+        request["task"]["geometric_operator"] = json!("Teleportation");
+        let (status, body) = post_json(test_app(), "/tasks", &request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let error = body["error"].as_str().unwrap();
+        assert!(error.contains("task.geometric_operator"), "{error}");
+        assert!(error.contains("Teleportation"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_validate_task_does_not_submit() {
+        let state = test_state();
+        let app = build_router().with_state(state.clone());
+
+        let task = task_request(0)["task"].clone();
+        let (status, body) = post_json(app.clone(), "/tasks/validate", &task).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!({ "valid": true, "errors": [] }));
+
+        let mut bad = task.clone();
+        bad["parameters"] = json!({ "theta": "quarter turn" });
+        let (status, body) = post_json(app, "/tasks/validate", &bad).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["valid"], false);
+        assert_eq!(body["errors"][0]["path"], "parameters.theta");
+
+        assert_eq!(state.processor.len().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_create_task_idempotency_key() {
+        let app = test_app();
+
+        let first = post_with_key(app.clone(), "retry-1").await;
+        let second = post_with_key(app.clone(), "retry-1").await;
+        assert_eq!(first, second);
+
+        let other = post_with_key(app.clone(), "retry-2").await;
+        assert_ne!(first["task_id"], other["task_id"]);
+
+        let (_, tasks) = get(app, "/tasks").await;
+        assert_eq!(tasks.as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_create_task_idempotency_key_survives_failed_execution() {
+        let app = test_app();
+        let mut body = task_request(0);
+        body["execute"] = json!(true);
+        body["task"]["expected_output_metric"] = json!("no_such_metric");
+        let request = || {
+            Request::post("/tasks")
+                .header("content-type", "application/json")
+                .header("idempotency-key", "failing")
+                .body(Body::from(body.to_string()))
+                .unwrap()
+        };
+
+        let (status, _) = send(app.clone(), request()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // The task was created, so the retry replays it instead of adding one
+        let (status, replay) = send(app.clone(), request()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(replay["status"]["Failed"].is_string(), "{replay}");
+
+        let (_, tasks) = get(app, "/tasks").await;
+        assert_eq!(tasks.as_array().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_create_task_reports_all_validation_errors() {
+        let mut body = task_request(0);
+        body["task"]["target_module"] = json!("");
+        body["task"]["parameters"] = json!({ "axis": [1.0, "y", 0.0] });
+
+        let (status, response) = post_json(test_app(), "/tasks", &body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let errors = response["errors"].as_array().unwrap();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0]["path"], "target_module");
+        assert_eq!(errors[1]["path"], "parameters.axis[1]");
+    }
+
+    #[tokio::test]
+    async fn test_create_task_deterministic_rejects_retry() {
+        let app = test_app();
+        let mut body = task_request(0);
+        body["deterministic"] = json!(true);
+
+        let (status, first) = post_json(app.clone(), "/tasks", &body).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _) = post_json(app.clone(), "/tasks", &body).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let task_id = first["task_id"].as_str().unwrap();
+        let (_, task) = get(app, &format!("/tasks/{}", task_id)).await;
+        assert_eq!(task["status"], "Pending");
+    }
+
+    #[tokio::test]
+    async fn test_get_task_detail() {
+        let app = test_app();
+        let request = task_request(0);
+        let (status, created) = post_json(app.clone(), "/tasks", &request).await;
+        assert_eq!(status, StatusCode::OK);
+        let task_id = created["task_id"].as_str().unwrap();
+
+        let (status, detail) = get(app.clone(), &format!("/tasks/{}/detail", task_id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(detail["task_id"], task_id);
+        assert_eq!(detail["command"], request["task"]);
+        assert_eq!(detail["status"], "Pending");
+
+        let unknown = uuid::Uuid::new_v4();
+        let (status, _) = get(app, &format!("/tasks/{}/detail", unknown)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_list_tasks_filters_by_tag() {
+        let app = test_app();
+        let mut tagged = task_request(0);
+        tagged["task"]["tags"] = json!(["exp-1"]);
+        let (_, body) = post_json(app.clone(), "/tasks", &tagged).await;
+        post_json(app.clone(), "/tasks", &task_request(0)).await;
+
+        let (status, all) = get(app.clone(), "/tasks").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(all.as_array().unwrap().len(), 2);
+
+        let (status, filtered) = get(app.clone(), "/tasks?tag=exp-1").await;
+        assert_eq!(status, StatusCode::OK);
+        let filtered = filtered.as_array().unwrap();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0]["task_id"], body["task_id"]);
+
+        let (_, none) = get(app, "/tasks?tag=exp-2").await;
+        assert!(none.as_array().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_stream_tasks_matches_list() {
+        use super::TaskListItem;
+        use futures_util::StreamExt;
+        use tower::ServiceExt;
+
+        let app = test_app();
+        for _ in 0..3 {
+            let (status, _) = post_json(app.clone(), "/tasks", &task_request(0)).await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(Request::get("/tasks/stream").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+
+        let mut chunks = response.into_body().into_data_stream();
+        let mut buffer = Vec::new();
+        let mut streamed = Vec::new();
+        while let Some(chunk) = chunks.next().await {
+            buffer.extend_from_slice(&chunk.unwrap());
+            while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = buffer.drain(..=end).collect();
+                let item: TaskListItem = serde_json::from_slice(&line).unwrap();
+                streamed.push(item.task_id);
+            }
+        }
+        assert!(buffer.is_empty());
+
+        let (_, listed) = get(app, "/tasks").await;
+        let listed = listed.as_array().unwrap();
+        assert_eq!(streamed.len(), listed.len());
+        for (id, item) in streamed.iter().zip(listed) {
+            assert_eq!(item["task_id"], id.to_string());
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::core::types::GeometricTaskCommand;
+use crate::state::AppState;
+
+use super::extract::ApiJson;
+use super::{not_found, ApiResult};
+
+#[derive(Deserialize, ToSchema)]
+pub struct RegisterTemplateRequest {
+    pub name: String,
+    /// Command whose `parameters` are the defaults for each instance
+    pub template: GeometricTaskCommand,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InstantiateTemplateRequest {
+    /// Merged into the template's parameters; nested objects merge key by key
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub overrides: serde_json::Value,
+}
+
+#[utoipa::path(
+    post,
+    path = "/templates",
+    tag = "tasks",
+    request_body = RegisterTemplateRequest,
+    responses(
+        (status = 201, description = "Template registered, replacing any with the same name", body = GeometricTaskCommand),
+        (status = 400, description = "Empty name or malformed template", body = String)
+    )
+)]
+pub async fn register_template(
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<RegisterTemplateRequest>,
+) -> ApiResult<(StatusCode, Json<GeometricTaskCommand>)> {
+    state
+        .processor
+        .register_template(request.name, request.template.clone())
+        .map_err(IntoResponse::into_response)?;
+
+    Ok((StatusCode::CREATED, Json(request.template)))
+}
+
+#[utoipa::path(
+    post,
+    path = "/templates/{name}/instantiate",
+    tag = "tasks",
+    params(("name" = String, Path, description = "Template name")),
+    request_body = InstantiateTemplateRequest,
+    responses(
+        (status = 200, description = "The template with the overrides applied; it is not submitted", body = GeometricTaskCommand),
+        (status = 400, description = "Overrides are not a JSON object", body = String),
+        (status = 404, description = "Template not found", body = String)
+    )
+)]
+pub async fn instantiate_template(
+    Path(name): Path<String>,
+    State(state): State<AppState>,
+    ApiJson(request): ApiJson<InstantiateTemplateRequest>,
+) -> ApiResult<Json<GeometricTaskCommand>> {
+    let command = state
+        .processor
+        .instantiate(&name, &request.overrides)
+        .map_err(IntoResponse::into_response)?
+        .ok_or_else(|| not_found(format!("Template '{}' not found", name)))?;
+
+    Ok(Json(command))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::routes::test_support::{post_json, test_app};
+    use axum::http::StatusCode;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_template_routes_instantiate_with_overrides() {
+        let app = test_app();
+        let template = json!({
+            "task_name": "Rotation sweep",
+            "geometric_operator": "QuaternionRotation",
+            "target_module": "sys7_core",
+            "parameters": { "theta": 0.1, "axis": [0.0, 0.0, 1.0] },
+            "expected_output_metric": "v_geometric"
+        });
+        let (status, _) = post_json(
+            app.clone(),
+            "/templates",
+            &json!({ "name": "sweep", "template": template }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, command) = post_json(
+            app.clone(),
+            "/templates/sweep/instantiate",
+            &json!({ "overrides": { "theta": 0.9 } }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let mut expected = template;
+        expected["parameters"]["theta"] = json!(0.9);
+        assert_eq!(command, expected);
+
+        let (status, _) = post_json(app, "/templates/missing/instantiate", &json!({})).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
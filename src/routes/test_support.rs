@@ -0,0 +1,50 @@
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use serde_json::Value;
+use std::time::Duration;
+use tower::ServiceExt;
+
+use crate::config::{AppConfig, LlmConfig};
+use crate::core::semantic_task_processor::ProcessorConfig;
+use crate::state::AppState;
+
+pub(crate) fn test_state() -> AppState {
+    let config = AppConfig {
+        llm: LlmConfig {
+            api_key: Some("test-key".into()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    AppState::initialize(&config)
+        .unwrap()
+        .with_processor_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            ..Default::default()
+        })
+}
+
+pub(crate) fn test_app() -> Router {
+    super::build_router().with_state(test_state())
+}
+
+pub(crate) async fn send(app: Router, request: Request<Body>) -> (StatusCode, Value) {
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let value = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+    (status, value)
+}
+
+pub(crate) async fn get(app: Router, uri: &str) -> (StatusCode, Value) {
+    send(app, Request::get(uri).body(Body::empty()).unwrap()).await
+}
+
+pub(crate) async fn post_json(app: Router, uri: &str, body: &Value) -> (StatusCode, Value) {
+    let request = Request::post(uri)
+        .header("content-type", "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap();
+    send(app, request).await
+}
@@ -1,30 +1,452 @@
-use axum::{extract::State, Json};
-use serde::Serialize;
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
-use crate::core::types::SemanticAnchor;
+use crate::core::eqgft_types::{
+    EQGFTAction, EQGFTFields, VisualizationPacket as EqgftPacket, VisualizationRequest,
+    VisualizationType,
+};
+use crate::core::types::GeometricMetrics;
+use crate::state::visualization_jobs::VisualizationJob;
 use crate::state::AppState;
+use crate::visualization::chart::{render_metrics_png, DEFAULT_CHART_HEIGHT, DEFAULT_CHART_WIDTH};
 use crate::visualization::protocol::VisualizationPacket;
 
-use super::{internal_error, ApiResult};
+use super::{bad_request, internal_error, not_found, ApiResult};
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct VisualizationResponse {
     pub packet: VisualizationPacket,
 }
 
+#[utoipa::path(
+    get,
+    path = "/visualization/packet",
+    tag = "visualization",
+    responses(
+        (status = 200, description = "Visualization packet for the current metrics", body = VisualizationResponse),
+        (status = 500, description = "Metrics unavailable", body = String)
+    )
+)]
 pub async fn get_packet(State(state): State<AppState>) -> ApiResult<Json<VisualizationResponse>> {
     let metrics = state.processor.get_metrics().map_err(internal_error)?;
-
-    let anchors = vec![SemanticAnchor {
-        id: Uuid::new_v4(),
-        name: "Root Anchor".into(),
-        description: "Placeholder semantic anchor".into(),
-        position: [0.0, 0.0, 0.0, 1.0],
-        metadata: serde_json::json!({ "note": "replace with real anchors" }),
-    }];
+    let anchors = state.processor.list_anchors().map_err(internal_error)?;
 
     let packet = VisualizationPacket::new(metrics, anchors);
 
     Ok(Json(VisualizationResponse { packet }))
 }
+
+#[derive(Deserialize, IntoParams)]
+pub struct ChartQuery {
+    /// Image width in pixels, defaults to 640 and capped at 2048
+    pub width: Option<u32>,
+    /// Image height in pixels, defaults to 360 and capped at 2048
+    pub height: Option<u32>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/visualization/metrics.png",
+    tag = "visualization",
+    params(ChartQuery),
+    responses(
+        (status = 200, description = "Line chart of the metrics history, one line per scalar field", content_type = "image/png"),
+        (status = 400, description = "Width or height out of range", body = String),
+        (status = 500, description = "History unavailable or rendering failed", body = String)
+    )
+)]
+pub async fn get_metrics_png(
+    State(state): State<AppState>,
+    Query(query): Query<ChartQuery>,
+) -> ApiResult<Response> {
+    let width = query.width.unwrap_or(DEFAULT_CHART_WIDTH);
+    let height = query.height.unwrap_or(DEFAULT_CHART_HEIGHT);
+    let history = state.processor.metrics_history().map_err(internal_error)?;
+    let png = render_metrics_png(&history, width, height).map_err(IntoResponse::into_response)?;
+
+    Ok(([(header::CONTENT_TYPE, "image/png")], png).into_response())
+}
+
+/// Assemble an EQGFT packet around the given metrics, using vacuum fields.
+fn eqgft_packet(
+    metrics: &GeometricMetrics,
+    visualization_type: VisualizationType,
+    metadata: serde_json::Value,
+) -> EqgftPacket {
+    let mut values: HashMap<String, f64> = metrics
+        .scalar_fields()
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect();
+    values.extend(metrics.custom_metrics.clone());
+
+    EqgftPacket {
+        id: Uuid::new_v4(),
+        timestamp: chrono::Utc::now(),
+        fields: EQGFTFields::default(),
+        action: EQGFTAction::default(),
+        metrics: values,
+        visualization_type,
+        metadata,
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/visualization/packet",
+    tag = "visualization",
+    request_body = VisualizationRequest,
+    responses(
+        (status = 200, description = "EQGFT packet assembled from the current metrics", body = EqgftPacket),
+        (status = 500, description = "Metrics unavailable", body = String)
+    )
+)]
+pub async fn build_packet(
+    State(state): State<AppState>,
+    Json(request): Json<VisualizationRequest>,
+) -> ApiResult<Json<EqgftPacket>> {
+    let metrics = state.processor.get_metrics().map_err(internal_error)?;
+
+    Ok(Json(packet_for_request(&metrics, &request)))
+}
+
+/// Packet for a request: its type is echoed, and its parameters (plus the tag
+/// of a custom type) are recorded in the metadata.
+fn packet_for_request(metrics: &GeometricMetrics, request: &VisualizationRequest) -> EqgftPacket {
+    let mut metadata = serde_json::json!({ "parameters": request.parameters });
+    if let VisualizationType::Custom(tag) = &request.visualization_type {
+        metadata["custom_type"] = serde_json::Value::String(tag.clone());
+    }
+
+    eqgft_packet(metrics, request.visualization_type.clone(), metadata)
+}
+
+#[utoipa::path(
+    get,
+    path = "/visualization/packet/compressed",
+    tag = "visualization",
+    responses(
+        (status = 200, description = "EQGFT animation packet for the current metrics, JSON sent with Content-Encoding: zstd", content_type = "application/json"),
+        (status = 500, description = "Metrics unavailable or compression failed", body = String)
+    )
+)]
+pub async fn get_compressed_packet(State(state): State<AppState>) -> ApiResult<Response> {
+    let metrics = state.processor.get_metrics().map_err(internal_error)?;
+    let packet = eqgft_packet(
+        &metrics,
+        VisualizationType::Animation,
+        serde_json::json!({}),
+    );
+    let bytes = packet.to_compressed_bytes().map_err(internal_error)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json"),
+            (header::CONTENT_ENCODING, "zstd"),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+#[utoipa::path(
+    post,
+    path = "/visualization",
+    tag = "visualization",
+    request_body = VisualizationRequest,
+    responses(
+        (status = 202, description = "Job queued; poll it or wait for the callback", body = VisualizationJob),
+        (status = 400, description = "Invalid callback URL", body = String)
+    )
+)]
+pub async fn create_job(
+    State(state): State<AppState>,
+    Json(request): Json<VisualizationRequest>,
+) -> ApiResult<(StatusCode, Json<VisualizationJob>)> {
+    if let Some(url) = &request.callback_url {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|err| bad_request(format!("Invalid callback_url '{}': {}", url, err)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(bad_request("callback_url must use http or https"));
+        }
+    }
+
+    let job = state.visualization_jobs.lock().await.enqueue(request);
+    tokio::spawn(run_job(state, job.job_id));
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+/// Build the packet for a queued job, then notify its callback URL if any.
+async fn run_job(state: AppState, job_id: Uuid) {
+    let request = {
+        let mut jobs = state.visualization_jobs.lock().await;
+        jobs.start(job_id);
+        match jobs.get(job_id) {
+            Some(job) => job.request,
+            None => return,
+        }
+    };
+
+    let outcome = state
+        .processor
+        .get_metrics()
+        .map(|metrics| packet_for_request(&metrics, &request))
+        .map_err(|err| err.to_string());
+    let job = state
+        .visualization_jobs
+        .lock()
+        .await
+        .finish(job_id, outcome);
+
+    if let (Some(job), Some(url)) = (job, request.callback_url) {
+        let delivery = state
+            .http_client
+            .post(&url)
+            .json(&job)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+        if let Err(err) = delivery {
+            warn!("Visualization callback to {} failed: {}", url, err);
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/visualization/{job_id}",
+    tag = "visualization",
+    params(("job_id" = Uuid, Path, description = "Visualization job ID")),
+    responses(
+        (status = 200, description = "Current state of the job", body = VisualizationJob),
+        (status = 400, description = "Invalid job ID", body = String),
+        (status = 404, description = "Job not found", body = String)
+    )
+)]
+pub async fn get_job(
+    Path(job_id): Path<String>,
+    State(state): State<AppState>,
+) -> ApiResult<Json<VisualizationJob>> {
+    let id = Uuid::parse_str(&job_id).map_err(|_| bad_request("Invalid job ID"))?;
+
+    let job = state
+        .visualization_jobs
+        .lock()
+        .await
+        .get(id)
+        .ok_or_else(|| not_found("Visualization job not found"))?;
+
+    Ok(Json(job))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::eqgft_types::{VisualizationPacket, VisualizationType};
+    use crate::routes::test_support::{get, post_json, test_app};
+    use axum::body::{to_bytes, Body};
+    use axum::http::{header, Request, StatusCode};
+    use axum::Json;
+    use serde_json::json;
+    use tower::ServiceExt;
+
+    #[tokio::test]
+    async fn test_metrics_png() {
+        let response = test_app()
+            .oneshot(
+                Request::get("/visualization/metrics.png?width=120&height=80")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "image/png");
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(bytes.starts_with(b"\x89PNG\r\n\x1a\n"));
+        // The IHDR chunk records the requested size
+        assert_eq!(&bytes[16..24], [0, 0, 0, 120, 0, 0, 0, 80]);
+
+        for uri in [
+            "/visualization/metrics.png?width=4096",
+            "/visualization/metrics.png?height=0",
+        ] {
+            let (status, _) = get(test_app(), uri).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_packet_includes_registered_anchors() {
+        let app = test_app();
+        let (_, packet) = get(app.clone(), "/visualization/packet").await;
+        assert_eq!(packet["packet"]["anchors"], json!([]));
+
+        let (status, anchor) = post_json(
+            app.clone(),
+            "/anchors",
+            &json!({ "name": "Root", "position": [0.0, 0.0, 0.0, 1.0] }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+
+        let (status, packet) = get(app, "/visualization/packet").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(packet["packet"]["anchors"], json!([anchor]));
+    }
+
+    #[tokio::test]
+    async fn test_build_packet_echoes_visualization_type() {
+        let app = test_app();
+
+        let (status, packet) = post_json(
+            app.clone(),
+            "/visualization/packet",
+            &json!({ "visualization_type": "Plot3D" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(packet["visualization_type"], "Plot3D");
+        assert!(packet["id"].is_string());
+        assert!(packet["timestamp"].is_string());
+
+        let request = json!({
+            "visualization_type": { "Custom": "phase_portrait" },
+            "parameters": { "resolution": 64 }
+        });
+        let (status, packet) = post_json(app, "/visualization/packet", &request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            packet["visualization_type"],
+            json!({ "Custom": "phase_portrait" })
+        );
+        assert_eq!(packet["metadata"]["custom_type"], "phase_portrait");
+        assert_eq!(packet["metadata"]["parameters"]["resolution"], 64);
+    }
+
+    #[tokio::test]
+    async fn test_build_packet_uses_processor_metrics() {
+        let app = test_app();
+        let (_, metrics) = get(app.clone(), "/metrics").await;
+
+        let (_, packet) = post_json(
+            app,
+            "/visualization/packet",
+            &json!({ "visualization_type": "Plot2D" }),
+        )
+        .await;
+        for field in [
+            "v_geometric",
+            "s_geometric",
+            "quaternion_coherence",
+            "topological_winding",
+        ] {
+            assert_eq!(packet["metrics"][field], metrics["metrics"][field]);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compressed_packet_route() {
+        let request = Request::get("/visualization/packet/compressed")
+            .body(Body::empty())
+            .unwrap();
+        let response = test_app().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_ENCODING], "zstd");
+
+        let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let packet = VisualizationPacket::from_compressed_bytes(&bytes).unwrap();
+        assert_eq!(packet.visualization_type, VisualizationType::Animation);
+        assert!(packet.metrics.contains_key("v_geometric"));
+    }
+
+    async fn wait_for_job(app: axum::Router, job_id: &str) -> serde_json::Value {
+        for _ in 0..100 {
+            let (status, job) = get(app.clone(), &format!("/visualization/{}", job_id)).await;
+            assert_eq!(status, StatusCode::OK);
+            if job["status"] == "Completed" || job["status"] == "Failed" {
+                return job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("visualization job {} did not finish", job_id);
+    }
+
+    #[tokio::test]
+    async fn test_visualization_job_queued_then_completed() {
+        let app = test_app();
+
+        let (status, job) = post_json(
+            app.clone(),
+            "/visualization",
+            &json!({ "visualization_type": "Animation" }),
+        )
+        .await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(job["status"], "Queued");
+        assert!(job["packet"].is_null());
+
+        let job_id = job["job_id"].as_str().unwrap();
+        let finished = wait_for_job(app.clone(), job_id).await;
+        assert_eq!(finished["status"], "Completed");
+        assert_eq!(finished["packet"]["visualization_type"], "Animation");
+        assert_eq!(
+            finished["result_url"],
+            format!("/api/visualization/{}", job_id)
+        );
+
+        let (status, _) = get(app, &format!("/visualization/{}", uuid::Uuid::new_v4())).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_visualization_job_posts_to_callback() {
+        let (sender, mut received) = tokio::sync::mpsc::unbounded_channel();
+        let mock = axum::Router::new().route(
+            "/callback",
+            axum::routing::post(move |Json(body): Json<serde_json::Value>| {
+                let sender = sender.clone();
+                async move {
+                    sender.send(body).unwrap();
+                    StatusCode::NO_CONTENT
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, mock).await.unwrap() });
+
+        let request = json!({
+            "visualization_type": "Topology",
+            "callback_url": format!("http://{}/callback", addr)
+        });
+        let (status, job) = post_json(test_app(), "/visualization", &request).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+
+        let callback = tokio::time::timeout(std::time::Duration::from_secs(5), received.recv())
+            .await
+            .expect("callback was not delivered")
+            .unwrap();
+        assert_eq!(callback["job_id"], job["job_id"]);
+        assert_eq!(callback["status"], "Completed");
+        assert_eq!(callback["packet"]["visualization_type"], "Topology");
+    }
+
+    #[tokio::test]
+    async fn test_visualization_job_rejects_invalid_callback() {
+        let request =
+            json!({ "visualization_type": "Plot2D", "callback_url": "ftp://example.com" });
+        let (status, _) = post_json(test_app(), "/visualization", &request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
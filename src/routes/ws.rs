@@ -0,0 +1,288 @@
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    response::Response,
+};
+use serde::Serialize;
+use tracing::{debug, warn};
+use utoipa::ToSchema;
+
+use crate::core::error::Error;
+use crate::core::metrics_stream::{MetricsEvent, MetricsSubscription};
+use crate::core::types::{GeometricTaskCommand, TaskExecutionResult};
+use crate::state::AppState;
+
+/// WebSocket subprotocol selecting MessagePack framing on `/ws`
+pub const MSGPACK_SUBPROTOCOL: &str = "mmss.msgpack";
+
+/// How `/ws` frames are encoded, negotiated once per connection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framing {
+    /// JSON in text frames, the default
+    Json,
+    /// MessagePack in binary frames, for clients that requested
+    /// [`MSGPACK_SUBPROTOCOL`]
+    MessagePack,
+}
+
+/// Frame sent back for each message received on `/ws`.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CommandFrame {
+    /// The command was submitted and executed
+    Result(TaskExecutionResult),
+    /// The message could not be parsed, validated or executed
+    Error { message: String },
+}
+
+#[utoipa::path(
+    get,
+    path = "/ws",
+    tag = "tasks",
+    responses(
+        (status = 101, description = "WebSocket upgrade. Each text message is a GeometricTaskCommand; each reply is a CommandFrame. With the `mmss.msgpack` subprotocol, commands and replies are MessagePack binary messages instead", body = CommandFrame)
+    )
+)]
+pub async fn command_socket(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    ws.protocols([MSGPACK_SUBPROTOCOL])
+        .on_upgrade(move |socket| handle_socket(socket, state))
+}
+
+async fn handle_socket(mut socket: WebSocket, state: AppState) {
+    let framing = match socket.protocol() {
+        Some(protocol) if protocol == MSGPACK_SUBPROTOCOL => Framing::MessagePack,
+        _ => Framing::Json,
+    };
+
+    while let Some(message) = socket.recv().await {
+        let frame = match message {
+            Ok(Message::Text(text)) => match serde_json::from_str(&text) {
+                Ok(command) => run_command(&state, command).await,
+                Err(err) => CommandFrame::Error {
+                    message: format!("Malformed command: {}", err),
+                },
+            },
+            Ok(Message::Binary(bytes)) if framing == Framing::MessagePack => {
+                match rmp_serde::from_slice(&bytes) {
+                    Ok(command) => run_command(&state, command).await,
+                    Err(err) => CommandFrame::Error {
+                        message: format!("Malformed command: {}", err),
+                    },
+                }
+            }
+            Ok(Message::Binary(_)) => CommandFrame::Error {
+                message: format!(
+                    "Binary messages need the {} subprotocol; send JSON text",
+                    MSGPACK_SUBPROTOCOL
+                ),
+            },
+            Ok(Message::Close(_)) => break,
+            // Pings are answered by axum
+            Ok(Message::Ping(_) | Message::Pong(_)) => continue,
+            Err(err) => {
+                debug!("WebSocket receive failed: {}", err);
+                break;
+            }
+        };
+
+        let payload = match framing {
+            Framing::Json => serde_json::to_string(&frame)
+                .map(Message::Text)
+                .map_err(|err| err.to_string()),
+            // Named fields, so optional fields can be skipped and the frame
+            // stays self-describing
+            Framing::MessagePack => rmp_serde::to_vec_named(&frame)
+                .map(Message::Binary)
+                .map_err(|err| err.to_string()),
+        };
+        let payload = match payload {
+            Ok(payload) => payload,
+            Err(err) => {
+                warn!("Failed to serialize command frame: {}", err);
+                continue;
+            }
+        };
+        if socket.send(payload).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/ws/metrics",
+    tag = "metrics",
+    responses(
+        (status = 101, description = "WebSocket upgrade. Sends a MetricsEvent for every recorded snapshot; a slow client gets a `lagged` event instead of unbounded buffering", body = MetricsEvent)
+    )
+)]
+pub async fn metrics_socket(ws: WebSocketUpgrade, State(state): State<AppState>) -> Response {
+    let subscription = state.processor.subscribe_metrics();
+    ws.on_upgrade(move |socket| stream_metrics(socket, subscription))
+}
+
+async fn stream_metrics(mut socket: WebSocket, mut subscription: MetricsSubscription) {
+    loop {
+        let event = tokio::select! {
+            event = subscription.recv() => match event {
+                Some(event) => event,
+                None => break,
+            },
+            // Incoming messages are ignored; this only notices the client leaving
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        let payload = match serde_json::to_string(&event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                warn!("Failed to serialize metrics event: {}", err);
+                continue;
+            }
+        };
+        if socket.send(Message::Text(payload)).await.is_err() {
+            break;
+        }
+    }
+}
+
+async fn run_command(state: &AppState, command: GeometricTaskCommand) -> CommandFrame {
+    // Execution sleeps for the configured delay, so keep it off the async
+    // worker driving this socket as the task routes do.
+    let processor = state.processor.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        processor
+            .submit_task(command)
+            .and_then(|task_id| processor.execute_task(task_id))
+    })
+    .await
+    .unwrap_or_else(|err| Err(Error::TaskExecution(format!("execution panicked: {}", err))));
+    match outcome {
+        Ok(result) => CommandFrame::Result(result),
+        Err(err) => CommandFrame::Error {
+            message: err.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MSGPACK_SUBPROTOCOL;
+    use crate::core::types::{GeometricTaskCommand, TaskExecutionResult};
+    use crate::routes::build_router;
+    use crate::routes::test_support::test_state;
+    use futures_util::{SinkExt, StreamExt};
+    use serde_json::{json, Value};
+    use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+    use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+    #[tokio::test]
+    async fn test_command_socket_replies_in_order() {
+        let app = build_router().with_state(test_state());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let (mut socket, _) = connect_async(format!("ws://{}/ws", addr)).await.unwrap();
+        let command = |name: &str| {
+            json!({
+                "task_name": name,
+                "geometric_operator": "QuaternionRotation",
+                "target_module": "sys7_core",
+                "parameters": { "theta": 0.4 },
+                "expected_output_metric": "v_geometric"
+            })
+            .to_string()
+        };
+
+        socket.send(Message::text(command("First"))).await.unwrap();
+        socket.send(Message::text("not json")).await.unwrap();
+        socket.send(Message::text(command("Second"))).await.unwrap();
+
+        let mut frames = Vec::new();
+        while frames.len() < 3 {
+            if let Message::Text(text) = socket.next().await.unwrap().unwrap() {
+                frames.push(serde_json::from_str::<Value>(&text).unwrap());
+            }
+        }
+
+        assert_eq!(frames[0]["type"], "result");
+        assert_eq!(frames[0]["success"], true);
+        assert_eq!(frames[1]["type"], "error");
+        assert!(frames[1]["message"]
+            .as_str()
+            .unwrap()
+            .starts_with("Malformed command"));
+        assert_eq!(frames[2]["type"], "result");
+        assert_ne!(frames[0]["task_id"], frames[2]["task_id"]);
+    }
+
+    #[tokio::test]
+    async fn test_command_socket_speaks_msgpack_when_negotiated() {
+        let app = build_router().with_state(test_state());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let mut request = format!("ws://{}/ws", addr).into_client_request().unwrap();
+        request.headers_mut().insert(
+            "sec-websocket-protocol",
+            MSGPACK_SUBPROTOCOL.parse().unwrap(),
+        );
+        let (mut socket, response) = connect_async(request).await.unwrap();
+        assert_eq!(
+            response.headers()["sec-websocket-protocol"],
+            MSGPACK_SUBPROTOCOL
+        );
+
+        let command = GeometricTaskCommand::quaternion_rotation("Packed", [0.0, 0.0, 1.0], 0.4);
+        let payload = rmp_serde::to_vec_named(&command).unwrap();
+        socket.send(Message::binary(payload)).await.unwrap();
+
+        let bytes = loop {
+            match socket.next().await.unwrap().unwrap() {
+                Message::Binary(bytes) => break bytes,
+                Message::Text(text) => panic!("expected a binary frame, got {}", text),
+                _ => continue,
+            }
+        };
+        // The frame is the result with a `type` tag alongside its fields
+        #[derive(serde::Deserialize)]
+        struct Tagged {
+            r#type: String,
+        }
+        let tagged: Tagged = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(tagged.r#type, "result");
+        let result: TaskExecutionResult = rmp_serde::from_slice(&bytes).unwrap();
+        assert!(result.success);
+        assert!(result.metrics.v_geometric.is_finite());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_socket_streams_snapshots() {
+        let state = test_state();
+        let processor = state.processor.clone();
+        let app = build_router().with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let (mut socket, _) = connect_async(format!("ws://{}/ws/metrics", addr))
+            .await
+            .unwrap();
+        // The subscription is taken before the upgrade completes
+        processor.reset_metrics().unwrap();
+
+        let frame = loop {
+            if let Message::Text(text) = socket.next().await.unwrap().unwrap() {
+                break serde_json::from_str::<Value>(&text).unwrap();
+            }
+        };
+        assert_eq!(frame["type"], "metrics");
+        assert!(frame["v_geometric"].is_number());
+    }
+}
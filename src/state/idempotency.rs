@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::clock::{has_elapsed, Clock, SystemClock};
+
+/// Default lifetime of an idempotency key.
+pub const DEFAULT_IDEMPOTENCY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// What [`IdempotencyStore::reserve`] found for a key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reservation<V> {
+    /// The key was free and is now held by the caller, who must
+    /// [`insert`](IdempotencyStore::insert) or
+    /// [`release`](IdempotencyStore::release) it
+    Reserved,
+    /// An earlier request holds the key and has not finished
+    InFlight,
+    /// The value stored for the key
+    Stored(V),
+}
+
+/// Remembers the response produced for each idempotency key until it expires.
+///
+/// A key can be reserved before its response exists, so that concurrent
+/// requests with the same key see it as in flight without holding a lock for
+/// the duration of the request. Reservations expire like stored values.
+pub struct IdempotencyStore<V> {
+    ttl: Duration,
+    clock: Arc<dyn Clock>,
+    /// `None` while the key is reserved
+    entries: HashMap<String, (DateTime<Utc>, Option<V>)>,
+}
+
+impl<V: Clone> IdempotencyStore<V> {
+    pub fn new(ttl: Duration) -> Self {
+        Self::with_clock(ttl, Arc::new(SystemClock))
+    }
+
+    /// Store whose expiry is measured by `clock`.
+    pub fn with_clock(ttl: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            ttl,
+            clock,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// Return the stored value for `key` if it has not expired.
+    pub fn get(&mut self, key: &str) -> Option<V> {
+        let now = self.clock.now();
+        match self.entries.get(key) {
+            Some((stored_at, value)) if !has_elapsed(*stored_at, now, self.ttl) => value.clone(),
+            Some(_) => {
+                self.entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Reserve `key` unless it already has a value or an unexpired
+    /// reservation, dropping any expired entries.
+    pub fn reserve(&mut self, key: &str) -> Reservation<V> {
+        let now = self.clock.now();
+        self.prune(now);
+        match self.entries.get(key) {
+            Some((_, Some(value))) => Reservation::Stored(value.clone()),
+            Some((_, None)) => Reservation::InFlight,
+            None => {
+                self.entries.insert(key.to_string(), (now, None));
+                Reservation::Reserved
+            }
+        }
+    }
+
+    /// Give up a reservation on `key`; a stored value is left in place.
+    pub fn release(&mut self, key: &str) {
+        if matches!(self.entries.get(key), Some((_, None))) {
+            self.entries.remove(key);
+        }
+    }
+
+    /// Store `value` under `key`, completing any reservation and dropping
+    /// expired entries.
+    pub fn insert(&mut self, key: impl Into<String>, value: V) {
+        let now = self.clock.now();
+        self.prune(now);
+        self.entries.insert(key.into(), (now, Some(value)));
+    }
+
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let ttl = self.ttl;
+        self.entries
+            .retain(|_, (stored_at, _)| !has_elapsed(*stored_at, now, ttl));
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl<V: Clone> Default for IdempotencyStore<V> {
+    fn default() -> Self {
+        Self::new(DEFAULT_IDEMPOTENCY_TTL)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::MockClock;
+
+    #[test]
+    fn test_idempotency_store_expires_entries() {
+        let mut store = IdempotencyStore::new(Duration::from_secs(60));
+        store.insert("key-1", 1);
+        assert_eq!(store.get("key-1"), Some(1));
+        assert_eq!(store.get("key-2"), None);
+
+        let mut expired = IdempotencyStore::new(Duration::ZERO);
+        expired.insert("key-1", 1);
+        assert_eq!(expired.get("key-1"), None);
+        assert!(expired.is_empty());
+    }
+
+    #[test]
+    fn test_idempotency_store_expiry_follows_clock() {
+        let clock = MockClock::default();
+        let mut store =
+            IdempotencyStore::with_clock(Duration::from_secs(60), Arc::new(clock.clone()));
+        store.insert("key-1", 1);
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(store.get("key-1"), Some(1));
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(store.get("key-1"), None);
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_idempotency_store_reservations() {
+        let clock = MockClock::default();
+        let mut store =
+            IdempotencyStore::with_clock(Duration::from_secs(60), Arc::new(clock.clone()));
+
+        assert_eq!(store.reserve("key-1"), Reservation::Reserved);
+        assert_eq!(store.reserve("key-1"), Reservation::InFlight);
+        assert_eq!(store.get("key-1"), None);
+
+        store.insert("key-1", 1);
+        assert_eq!(store.reserve("key-1"), Reservation::Stored(1));
+        store.release("key-1");
+        assert_eq!(store.get("key-1"), Some(1));
+
+        assert_eq!(store.reserve("key-2"), Reservation::Reserved);
+        store.release("key-2");
+        assert_eq!(store.reserve("key-2"), Reservation::Reserved);
+
+        // An abandoned reservation frees the key once it expires
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(store.reserve("key-2"), Reservation::Reserved);
+    }
+}
@@ -0,0 +1,123 @@
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+
+use crate::api::data_io::{Journal, JournalEvent};
+use crate::core::metrics_stream::MetricsEvent;
+use crate::core::semantic_task_processor::{SemanticTaskProcessor, TaskChange};
+use crate::Result;
+
+/// Record every task change and metrics snapshot of `processor` in
+/// `journal`. Task changes are queued by a listener and appended, along with
+/// the published snapshots, by a background task that stops once the
+/// processor is dropped.
+pub fn spawn(processor: &SemanticTaskProcessor, journal: Journal) -> Result<JoinHandle<()>> {
+    // Unbounded so a listener never blocks the processor or drops a change
+    let (sender, mut changes) = mpsc::unbounded_channel();
+    processor.add_task_listener(Arc::new(move |task_id, change: &TaskChange| {
+        let event = match change.clone() {
+            TaskChange::Submitted(command) => JournalEvent::TaskSubmitted { task_id, command },
+            TaskChange::Status(status) => JournalEvent::TaskStatusChanged { task_id, status },
+        };
+        // Only fails once the journaling task is gone
+        let _ = sender.send(event);
+    }))?;
+    let mut updates = processor.subscribe_metrics();
+    let journal = Arc::new(journal);
+
+    Ok(tokio::spawn(async move {
+        // Both close with the processor; drain each before stopping
+        let (mut changes_open, mut metrics_open) = (true, true);
+        while changes_open || metrics_open {
+            let event = tokio::select! {
+                change = changes.recv(), if changes_open => match change {
+                    Some(event) => event,
+                    None => {
+                        changes_open = false;
+                        continue;
+                    }
+                },
+                update = updates.recv(), if metrics_open => match update {
+                    Some(MetricsEvent::Metrics(metrics)) => JournalEvent::Metrics { metrics },
+                    Some(MetricsEvent::Lagged { skipped }) => {
+                        warn!("Journal missed {} metrics snapshots", skipped);
+                        continue;
+                    }
+                    None => {
+                        metrics_open = false;
+                        continue;
+                    }
+                },
+            };
+
+            let journal = journal.clone();
+            match tokio::task::spawn_blocking(move || journal.append(event)).await {
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => error!("Failed to journal event: {}", err),
+                Err(err) => error!("Journal task failed: {}", err),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::core::semantic_task_processor::{ProcessorConfig, TaskStatus};
+    use crate::core::types::{GeometricOperator, GeometricTaskCommand};
+
+    #[tokio::test]
+    async fn test_processor_changes_are_journaled() {
+        let dir = tempfile::tempdir().unwrap();
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            ..Default::default()
+        });
+        let handle = spawn(&processor, Journal::open(dir.path()).unwrap()).unwrap();
+
+        let command = GeometricTaskCommand {
+            task_name: "Rotate".into(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "sys7_core".into(),
+            parameters: serde_json::json!({ "theta": 0.9 }),
+            expected_output_metric: "v_geometric".into(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+        let task_id = processor.submit_task(command.clone()).unwrap();
+        let executed = processor.execute_task(task_id).unwrap().metrics;
+
+        // Dropping the processor ends the journaling task once it has
+        // appended everything queued
+        drop(processor);
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("journaling never stopped")
+            .unwrap();
+
+        let (metrics, task_events): (Vec<_>, Vec<_>) = Journal::replay(dir.path())
+            .unwrap()
+            .map(|entry| entry.event)
+            .partition(|event| matches!(event, JournalEvent::Metrics { .. }));
+        assert_eq!(
+            task_events,
+            vec![
+                JournalEvent::TaskSubmitted { task_id, command },
+                JournalEvent::TaskStatusChanged {
+                    task_id,
+                    status: TaskStatus::InProgress,
+                },
+                JournalEvent::TaskStatusChanged {
+                    task_id,
+                    status: TaskStatus::Completed(executed.clone()),
+                },
+            ]
+        );
+        assert_eq!(metrics, vec![JournalEvent::Metrics { metrics: executed }]);
+    }
+}
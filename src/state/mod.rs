@@ -1,43 +1,215 @@
+pub mod idempotency;
+pub mod journaler;
+pub mod persister;
+pub mod scheduler;
+pub mod sweeper;
+pub mod visualization_jobs;
+
+use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use crate::api::data_io::{DataIoGateway, Journal};
 use crate::api::llm_gateway::LlmGateway;
+use crate::config::AppConfig;
+use crate::core::error::{Error, FieldError};
 use crate::core::geometric_metrics::GeometricMetricEngine;
-use crate::core::semantic_task_processor::SemanticTaskProcessor;
+use crate::core::semantic_task_processor::{ProcessorConfig, SemanticTaskProcessor};
+use crate::core::types::CreateTaskResponse;
 use crate::Result;
-use tokio::sync::RwLock;
+use idempotency::IdempotencyStore;
+use serde::{Deserialize, Serialize};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use visualization_jobs::VisualizationJobStore;
 
 pub const HBAR: f64 = 1.054_571_817e-34; // J·s
 pub const C: f64 = 299_792_458.0; // m/s
 pub const ZITTER_FREQUENCY: f64 = 1.55e21; // rad/s
 pub const ZITTER_AMPLITUDE: f64 = 1.93e-13; // m
+pub const FINE_STRUCTURE_INVERSE: f64 = 137.035_999_084;
+
+/// Limit on each outbound HTTP call made through [`AppState::http_client`]
+pub const OUTBOUND_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
 
 #[derive(Clone)]
 pub struct AppState {
     pub processor: Arc<SemanticTaskProcessor>,
     pub metric_engine: Arc<RwLock<GeometricMetricEngine>>,
-    pub llm_gateway: Arc<LlmGateway>,
+    /// `None` when no Mistral API key is available; LLM routes answer 503
+    pub llm_gateway: Option<Arc<LlmGateway>>,
+    pub idempotency: Arc<Mutex<IdempotencyStore<CreateTaskResponse>>>,
+    pub visualization_jobs: Arc<Mutex<VisualizationJobStore>>,
+    /// Client for outbound calls such as visualization callbacks
+    pub http_client: reqwest::Client,
+    pub started_at: Instant,
 }
 
 impl AppState {
-    pub fn initialize(api_key: Option<String>) -> Result<Self> {
-        let processor = Arc::new(SemanticTaskProcessor::new());
+    /// Build the state from loaded configuration. Metrics persisted under
+    /// the data directory are recovered; LLM routes are disabled when no API
+    /// key is configured.
+    pub fn initialize(config: &AppConfig) -> Result<Self> {
+        let initial_metrics = DataIoGateway::load_metrics(&config.data_dir)?;
+        if initial_metrics.is_some() {
+            info!("Recovered metrics from {}", config.data_dir.display());
+        }
+        let processor = Arc::new(SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            rule_set: config.rule_set()?,
+            initial_metrics,
+            ..Default::default()
+        }));
         let metric_engine = Arc::new(RwLock::new(GeometricMetricEngine::new()));
-        let llm_gateway = Arc::new(LlmGateway::new(api_key)?);
+        let llm_gateway = match LlmGateway::from_config(&config.llm) {
+            Ok(gateway) => Some(Arc::new(gateway)),
+            Err(err) => {
+                warn!("LLM features disabled: {}", err);
+                None
+            }
+        };
 
         Ok(Self {
             processor,
             metric_engine,
             llm_gateway,
+            idempotency: Arc::new(Mutex::new(IdempotencyStore::new(config.idempotency_ttl()))),
+            visualization_jobs: Arc::new(Mutex::new(VisualizationJobStore::new())),
+            http_client: reqwest::Client::builder()
+                .timeout(OUTBOUND_REQUEST_TIMEOUT)
+                .build()
+                .map_err(|err| Error::Other(err.into()))?,
+            started_at: Instant::now(),
         })
     }
+
+    /// Replace the idempotency key store with one using the given TTL.
+    pub fn with_idempotency_ttl(mut self, ttl: Duration) -> Self {
+        self.idempotency = Arc::new(Mutex::new(IdempotencyStore::new(ttl)));
+        self
+    }
+
+    /// Replace the task processor with a fresh one using the given configuration.
+    pub fn with_processor_config(mut self, config: ProcessorConfig) -> Self {
+        self.processor = Arc::new(SemanticTaskProcessor::new_with_config(config));
+        self
+    }
+
+    /// Start running this state's scheduled tasks in the background. Call it
+    /// after the processor is configured; replacing the processor later leaves
+    /// the scheduler driving the old one.
+    pub fn spawn_scheduler(&self, tick: Duration) -> tokio::task::JoinHandle<()> {
+        scheduler::spawn(self.processor.clone(), tick)
+    }
+
+    /// Start failing tasks stuck `InProgress` for at least `max_age`, checking
+    /// every `interval`. Like the scheduler, it drives the current processor.
+    pub fn spawn_stale_sweeper(
+        &self,
+        interval: Duration,
+        max_age: Duration,
+    ) -> tokio::task::JoinHandle<()> {
+        sweeper::spawn(self.processor.clone(), interval, max_age)
+    }
+
+    /// Start persisting the current processor's metrics under `data_dir`
+    /// whenever they change, for [`initialize`](Self::initialize) to recover.
+    pub fn spawn_metrics_persister(&self, data_dir: PathBuf) -> tokio::task::JoinHandle<()> {
+        persister::spawn(&self.processor, data_dir)
+    }
+
+    /// Start journaling the current processor's task changes and metrics
+    /// snapshots under `journal_dir`, creating it if needed.
+    pub fn spawn_journal(&self, journal_dir: PathBuf) -> Result<tokio::task::JoinHandle<()>> {
+        journaler::spawn(&self.processor, Journal::open(journal_dir)?)
+    }
+}
+
+/// Inputs to the derived physical values. Omitted fields take the built-in
+/// constants above.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct PhysicalConstants {
+    /// Reduced Planck constant, J·s
+    pub hbar: f64,
+    /// Speed of light, m/s
+    pub c: f64,
+    /// Zitterbewegung angular frequency, rad/s
+    pub zitter_frequency: f64,
+    /// Zitterbewegung amplitude, m
+    pub zitter_amplitude: f64,
+    /// 1/α
+    pub fine_structure_inverse: f64,
+}
+
+impl Default for PhysicalConstants {
+    fn default() -> Self {
+        Self {
+            hbar: HBAR,
+            c: C,
+            zitter_frequency: ZITTER_FREQUENCY,
+            zitter_amplitude: ZITTER_AMPLITUDE,
+            fine_structure_inverse: FINE_STRUCTURE_INVERSE,
+        }
+    }
+}
+
+/// Values derived from a set of [`PhysicalConstants`]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, ToSchema)]
+pub struct DerivedConstants {
+    pub electron_mass: f64,
+    pub fine_structure: f64,
+    pub quaternion_coherence: f64,
+    pub zitter_entropy: f64,
+}
+
+impl PhysicalConstants {
+    /// Every constant must be finite and positive.
+    pub fn validate(&self) -> Result<()> {
+        let errors: Vec<FieldError> = [
+            ("hbar", self.hbar),
+            ("c", self.c),
+            ("zitter_frequency", self.zitter_frequency),
+            ("zitter_amplitude", self.zitter_amplitude),
+            ("fine_structure_inverse", self.fine_structure_inverse),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !(value.is_finite() && *value > 0.0))
+        .map(|(path, _)| FieldError::new(path, "must be a positive number"))
+        .collect();
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Validation(errors))
+        }
+    }
+
+    /// `m = ħ / (2 c a)`
+    pub fn electron_mass(&self) -> f64 {
+        self.hbar / (2.0 * self.c * self.zitter_amplitude)
+    }
+
+    pub fn fine_structure(&self) -> f64 {
+        1.0 / self.fine_structure_inverse
+    }
+
+    pub fn derive(&self) -> DerivedConstants {
+        DerivedConstants {
+            electron_mass: self.electron_mass(),
+            fine_structure: self.fine_structure(),
+            quaternion_coherence: compute_quaternion_coherence(),
+            zitter_entropy: compute_zitter_entropy(),
+        }
+    }
 }
 
 pub fn compute_electron_mass() -> f64 {
-    HBAR / (2.0 * C * ZITTER_AMPLITUDE)
+    PhysicalConstants::default().electron_mass()
 }
 
 pub fn compute_fine_structure() -> f64 {
-    1.0 / 137.035_999_084
+    PhysicalConstants::default().fine_structure()
 }
 
 pub fn compute_quaternion_coherence() -> f64 {
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use tokio::task::JoinHandle;
+use tracing::error;
+
+use crate::api::data_io::DataIoGateway;
+use crate::core::metrics_stream::MetricsEvent;
+use crate::core::semantic_task_processor::SemanticTaskProcessor;
+
+/// Spawn a background task that persists every metrics snapshot `processor`
+/// publishes under `data_dir`, so the latest metrics survive a restart. It
+/// stops once the processor is dropped.
+pub fn spawn(processor: &SemanticTaskProcessor, data_dir: PathBuf) -> JoinHandle<()> {
+    let mut updates = processor.subscribe_metrics();
+    tokio::spawn(async move {
+        while let Some(event) = updates.recv().await {
+            let metrics = match event {
+                MetricsEvent::Metrics(metrics) => metrics,
+                // A newer snapshot follows and supersedes the dropped ones
+                MetricsEvent::Lagged { .. } => continue,
+            };
+            let dir = data_dir.clone();
+            let persisted =
+                tokio::task::spawn_blocking(move || DataIoGateway::persist_metrics(&dir, &metrics))
+                    .await;
+            match persisted {
+                Ok(Ok(())) => {}
+                Ok(Err(err)) => error!("Failed to persist metrics: {}", err),
+                Err(err) => error!("Metrics persistence task failed: {}", err),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    use crate::core::semantic_task_processor::ProcessorConfig;
+    use crate::core::types::{GeometricOperator, GeometricTaskCommand};
+
+    #[tokio::test]
+    async fn test_persisted_metrics_seed_the_next_processor() {
+        let dir = tempfile::tempdir().unwrap();
+        let processor = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            ..Default::default()
+        });
+        let handle = spawn(&processor, dir.path().to_path_buf());
+
+        let task_id = processor
+            .submit_task(GeometricTaskCommand {
+                task_name: "Rotate".into(),
+                geometric_operator: GeometricOperator::QuaternionRotation,
+                target_module: "sys7_core".into(),
+                parameters: serde_json::json!({ "theta": 0.9 }),
+                expected_output_metric: "v_geometric".into(),
+                task_id: None,
+                execute_at: None,
+                tags: Vec::new(),
+            })
+            .unwrap();
+        let executed = processor.execute_task(task_id).unwrap().metrics;
+
+        // Dropping the processor stops the persister once it has written
+        // every snapshot; only then may the startup path load them
+        drop(processor);
+        tokio::time::timeout(Duration::from_secs(5), handle)
+            .await
+            .expect("persister never stopped")
+            .unwrap();
+
+        let persisted = DataIoGateway::load_metrics(dir.path()).unwrap().unwrap();
+        assert_eq!(persisted, executed);
+        let restarted = SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            initial_metrics: Some(persisted),
+            ..Default::default()
+        });
+        assert_eq!(restarted.get_metrics().unwrap(), executed);
+    }
+}
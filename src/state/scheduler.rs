@@ -0,0 +1,91 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::{error, info};
+
+use crate::core::semantic_task_processor::SemanticTaskProcessor;
+
+/// How often the scheduler checks for tasks whose `execute_at` has passed
+pub const DEFAULT_SCHEDULER_TICK: Duration = Duration::from_millis(250);
+
+/// Spawn a background task that runs the processor's due scheduled tasks
+/// every `tick`. Due times are judged by the processor's clock, so a mock
+/// clock controls when tasks fire.
+pub fn spawn(processor: Arc<SemanticTaskProcessor>, tick: Duration) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tick);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            interval.tick().await;
+            // Execution sleeps for the configured delay, so keep it off the
+            // async workers.
+            let processor = processor.clone();
+            match tokio::task::spawn_blocking(move || processor.execute_due()).await {
+                Ok(Ok(results)) => {
+                    for result in results {
+                        info!("Executed scheduled task {}", result.task_id);
+                    }
+                }
+                Ok(Err(err)) => error!("Scheduler failed to run due tasks: {}", err),
+                Err(err) => error!("Scheduler tick panicked: {}", err),
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::{Clock, MockClock};
+    use crate::core::semantic_task_processor::{ProcessorConfig, TaskStatus};
+    use crate::core::types::{GeometricOperator, GeometricTaskCommand};
+
+    #[tokio::test]
+    async fn test_scheduled_task_waits_for_clock() {
+        let clock = MockClock::default();
+        let processor = Arc::new(SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            clock: Arc::new(clock.clone()),
+            ..Default::default()
+        }));
+        let handle = spawn(processor.clone(), Duration::from_millis(5));
+
+        let task_id = processor
+            .submit_task(GeometricTaskCommand {
+                task_name: "Scheduled rotation".into(),
+                geometric_operator: GeometricOperator::QuaternionRotation,
+                target_module: "sys7_core".into(),
+                parameters: serde_json::json!({ "theta": 0.5 }),
+                expected_output_metric: "v_geometric".into(),
+                task_id: None,
+                execute_at: Some(clock.now() + chrono::Duration::seconds(10)),
+                tags: Vec::new(),
+            })
+            .unwrap();
+
+        // Several ticks pass in real time, but none on the processor's clock
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            processor.get_task_status(task_id).unwrap(),
+            TaskStatus::Pending
+        );
+        assert!(processor.execute_next().unwrap().is_none());
+
+        clock.advance(Duration::from_secs(10));
+        let completed = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let status = processor.get_task_status(task_id).unwrap();
+                if matches!(status, TaskStatus::Completed(_)) {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        assert!(completed.is_ok(), "scheduled task never ran");
+
+        handle.abort();
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::error;
+
+use crate::core::semantic_task_processor::SemanticTaskProcessor;
+
+/// How often the sweeper looks for stale in-progress tasks
+pub const DEFAULT_SWEEP_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a task may stay `InProgress` before it is failed as stale
+pub const DEFAULT_MAX_EXECUTION_AGE: Duration = Duration::from_secs(600);
+
+/// Spawn a background task that, every `interval`, fails tasks that have
+/// been `InProgress` for at least `max_age`. Ages are judged by the
+/// processor's clock, so a mock clock controls when tasks are reaped.
+pub fn spawn(
+    processor: Arc<SemanticTaskProcessor>,
+    interval: Duration,
+    max_age: Duration,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticks = tokio::time::interval(interval);
+        ticks.set_missed_tick_behavior(MissedTickBehavior::Skip);
+        loop {
+            ticks.tick().await;
+            if let Err(err) = processor.fail_stale(max_age) {
+                error!("Stale task sweep failed: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::clock::MockClock;
+    use crate::core::semantic_task_processor::{ProcessorConfig, TaskStatus};
+    use crate::core::types::{GeometricOperator, GeometricTaskCommand};
+
+    #[tokio::test]
+    async fn test_stalled_task_is_reaped_after_max_age() {
+        let clock = MockClock::default();
+        let processor = Arc::new(SemanticTaskProcessor::new_with_config(ProcessorConfig {
+            exec_delay: Duration::ZERO,
+            clock: Arc::new(clock.clone()),
+            ..Default::default()
+        }));
+        let task = |name: &str| GeometricTaskCommand {
+            task_name: name.into(),
+            geometric_operator: GeometricOperator::QuaternionRotation,
+            target_module: "sys7_core".into(),
+            parameters: serde_json::json!({}),
+            expected_output_metric: "v_geometric".into(),
+            task_id: None,
+            execute_at: None,
+            tags: Vec::new(),
+        };
+        let stalled = processor.submit_task(task("Stalled")).unwrap();
+        processor.mark_in_progress(stalled);
+        let handle = spawn(
+            processor.clone(),
+            Duration::from_millis(5),
+            Duration::from_secs(60),
+        );
+
+        // Several sweeps pass in real time, but none on the processor's clock
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(
+            processor.get_task_status(stalled).unwrap(),
+            TaskStatus::InProgress
+        );
+
+        clock.advance(Duration::from_secs(60));
+        let fresh = processor.submit_task(task("Fresh")).unwrap();
+        processor.mark_in_progress(fresh);
+        let reaped = tokio::time::timeout(Duration::from_secs(5), async {
+            while processor.get_task_status(stalled).unwrap() == TaskStatus::InProgress {
+                tokio::time::sleep(Duration::from_millis(5)).await;
+            }
+        })
+        .await;
+        assert!(reaped.is_ok(), "stalled task was never reaped");
+        assert_eq!(
+            processor.get_task_status(stalled).unwrap(),
+            TaskStatus::Failed("stale".into())
+        );
+        assert_eq!(
+            processor.get_task_status(fresh).unwrap(),
+            TaskStatus::InProgress
+        );
+
+        handle.abort();
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::core::eqgft_types::{
+    VisualizationPacket, VisualizationRequest, VisualizationResponse, VisualizationStatus,
+};
+
+/// A queued visualization request and its current outcome.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct VisualizationJob {
+    pub job_id: Uuid,
+    #[serde(flatten)]
+    pub response: VisualizationResponse,
+    /// The assembled packet, once the job has completed
+    pub packet: Option<VisualizationPacket>,
+    #[serde(skip)]
+    pub request: VisualizationRequest,
+}
+
+/// Default number of finished jobs a [`VisualizationJobStore`] keeps.
+pub const DEFAULT_MAX_FINISHED_JOBS: usize = 10_000;
+
+/// In-memory store of visualization jobs, keyed by job ID.
+///
+/// Only the most recently finished `max_finished` jobs are kept; finishing
+/// another evicts the oldest. Queued and processing jobs are never evicted.
+pub struct VisualizationJobStore {
+    jobs: HashMap<Uuid, VisualizationJob>,
+    /// Finished job IDs, oldest first
+    finished: VecDeque<Uuid>,
+    max_finished: usize,
+}
+
+impl Default for VisualizationJobStore {
+    fn default() -> Self {
+        Self::with_max_finished(DEFAULT_MAX_FINISHED_JOBS)
+    }
+}
+
+impl VisualizationJobStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Store keeping at most `max_finished` completed or failed jobs.
+    pub fn with_max_finished(max_finished: usize) -> Self {
+        Self {
+            jobs: HashMap::new(),
+            finished: VecDeque::new(),
+            max_finished,
+        }
+    }
+
+    /// Record a new job in the `Queued` state.
+    pub fn enqueue(&mut self, request: VisualizationRequest) -> VisualizationJob {
+        let job_id = Uuid::new_v4();
+        let job = VisualizationJob {
+            job_id,
+            response: VisualizationResponse {
+                status: VisualizationStatus::Queued,
+                result_url: None,
+                error: None,
+                metadata: serde_json::json!({}),
+            },
+            packet: None,
+            request,
+        };
+        self.jobs.insert(job_id, job.clone());
+        job
+    }
+
+    pub fn get(&self, job_id: Uuid) -> Option<VisualizationJob> {
+        self.jobs.get(&job_id).cloned()
+    }
+
+    /// Mark a job as being processed.
+    pub fn start(&mut self, job_id: Uuid) {
+        if let Some(job) = self.jobs.get_mut(&job_id) {
+            job.response.status = VisualizationStatus::Processing;
+        }
+    }
+
+    /// Store the finished packet (or the failure) and return the updated
+    /// job, evicting the oldest finished jobs beyond the cap.
+    pub fn finish(
+        &mut self,
+        job_id: Uuid,
+        outcome: Result<VisualizationPacket, String>,
+    ) -> Option<VisualizationJob> {
+        let job = self.jobs.get_mut(&job_id)?;
+        let newly_finished = matches!(
+            job.response.status,
+            VisualizationStatus::Queued | VisualizationStatus::Processing
+        );
+        match outcome {
+            Ok(packet) => {
+                job.response.status = VisualizationStatus::Completed;
+                job.response.result_url = Some(format!("/api/visualization/{}", job_id));
+                job.packet = Some(packet);
+            }
+            Err(message) => {
+                job.response.status = VisualizationStatus::Failed;
+                job.response.error = Some(message);
+            }
+        }
+        let job = job.clone();
+
+        if newly_finished {
+            self.finished.push_back(job_id);
+            while self.finished.len() > self.max_finished {
+                if let Some(oldest) = self.finished.pop_front() {
+                    self.jobs.remove(&oldest);
+                }
+            }
+        }
+        Some(job)
+    }
+
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::eqgft_types::VisualizationType;
+
+    fn request() -> VisualizationRequest {
+        VisualizationRequest {
+            visualization_type: VisualizationType::Plot2D,
+            parameters: serde_json::json!({}),
+            callback_url: None,
+        }
+    }
+
+    #[test]
+    fn test_finished_jobs_are_capped() {
+        let mut store = VisualizationJobStore::with_max_finished(2);
+        let ids: Vec<Uuid> = (0..3).map(|_| store.enqueue(request()).job_id).collect();
+        let pending = store.enqueue(request()).job_id;
+
+        for id in &ids {
+            store.start(*id);
+            store.finish(*id, Err("no metrics".into())).unwrap();
+        }
+
+        assert!(store.get(ids[0]).is_none());
+        assert!(store.get(ids[1]).is_some());
+        assert!(store.get(ids[2]).is_some());
+        assert!(store.get(pending).is_some());
+        assert_eq!(store.len(), 3);
+    }
+}
@@ -0,0 +1,173 @@
+use axum::http::{HeaderName, Request};
+use axum::Router;
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::{MakeSpan, TraceLayer};
+use tracing::{Span, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+use crate::core::error::{Error, Result};
+
+/// Header carrying the per-request ID, generated when the client sends none.
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Filter used when `RUST_LOG` is unset.
+pub const DEFAULT_LOG_FILTER: &str = "info";
+
+/// Output format selected by `MMSS_LOG_FORMAT`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines for local development
+    #[default]
+    Pretty,
+    /// Newline-delimited JSON for log aggregators
+    Json,
+}
+
+/// Build a subscriber writing in `format` to `writer`.
+pub fn subscriber<W>(
+    format: LogFormat,
+    filter: EnvFilter,
+    writer: W,
+) -> Box<dyn Subscriber + Send + Sync>
+where
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(writer);
+    match format {
+        LogFormat::Pretty => Box::new(builder.finish()),
+        LogFormat::Json => Box::new(
+            builder
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .finish(),
+        ),
+    }
+}
+
+/// Install the global subscriber, honouring `RUST_LOG`. `log` records from
+/// dependencies are forwarded as well.
+pub fn init(format: LogFormat) -> Result<()> {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    subscriber(format, filter, std::io::stdout)
+        .try_init()
+        .map_err(|err| Error::Other(anyhow::anyhow!("Failed to install logger: {}", err)))
+}
+
+/// Span wrapping each HTTP request, tagged with its request ID.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestSpan;
+
+impl<B> MakeSpan<B> for RequestSpan {
+    fn make_span(&mut self, request: &Request<B>) -> Span {
+        let request_id = request
+            .headers()
+            .get(&REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+
+        tracing::info_span!(
+            "request",
+            method = %request.method(),
+            uri = %request.uri(),
+            request_id,
+        )
+    }
+}
+
+/// Assign every request an `x-request-id` (keeping one supplied by the
+/// client), trace it in a [`RequestSpan`] and echo the ID in the response.
+pub fn with_request_tracing<S>(router: Router<S>) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    router
+        .layer(PropagateRequestIdLayer::new(REQUEST_ID_HEADER))
+        .layer(TraceLayer::new_for_http().make_span_with(RequestSpan))
+        .layer(SetRequestIdLayer::new(REQUEST_ID_HEADER, MakeRequestUuid))
+}
+
+/// In-memory log sink for tests; clones share one buffer.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct LogCapture(std::sync::Arc<std::sync::Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl LogCapture {
+    /// Everything written so far
+    pub(crate) fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[cfg(test)]
+impl std::io::Write for LogCapture {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::routes::test_support::{send, test_app};
+    use axum::body::Body;
+    use axum::http::StatusCode;
+    use serde_json::{json, Value};
+
+    #[tokio::test]
+    async fn test_json_logs_include_request_id() {
+        let capture = LogCapture::default();
+        let writer = capture.clone();
+        let _guard = tracing::subscriber::set_default(subscriber(
+            LogFormat::Json,
+            EnvFilter::new("info"),
+            move || writer.clone(),
+        ));
+
+        let body = json!({
+            "task": {
+                "task_name": "Logging probe",
+                "geometric_operator": "QuaternionRotation",
+                "target_module": "sys7_core",
+                "parameters": {},
+                "expected_output_metric": "v_geometric"
+            },
+            "execute": false
+        });
+        let request = Request::post("/tasks")
+            .header("content-type", "application/json")
+            .header(REQUEST_ID_HEADER, "req-42")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let (status, _) = send(with_request_tracing(test_app()), request).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let output = capture.contents();
+        let event = output
+            .lines()
+            .map(|line| serde_json::from_str::<Value>(line).unwrap())
+            .find(|event| {
+                event["fields"]["message"]
+                    .as_str()
+                    .is_some_and(|message| message.starts_with("Submitted task"))
+            })
+            .expect("submission event was not logged");
+
+        assert_eq!(event["level"], "INFO");
+        assert!(event["timestamp"].is_string());
+        assert_eq!(event["span"]["name"], "request");
+        assert_eq!(event["span"]["request_id"], "req-42");
+        assert_eq!(event["span"]["method"], "POST");
+    }
+}
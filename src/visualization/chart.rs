@@ -0,0 +1,128 @@
+use tiny_skia::{Color, Paint, PathBuilder, Pixmap, Rect, Stroke, Transform};
+
+use crate::core::error::{Error, Result};
+use crate::core::types::GeometricMetrics;
+
+pub const DEFAULT_CHART_WIDTH: u32 = 640;
+pub const DEFAULT_CHART_HEIGHT: u32 = 360;
+/// Largest width or height a chart may be rendered at
+pub const MAX_CHART_DIMENSION: u32 = 2048;
+
+/// Smallest width or height that leaves room for the plot inside the margin
+const MIN_CHART_DIMENSION: u32 = 2 * MARGIN as u32 + 1;
+const MARGIN: f32 = 16.0;
+
+/// One line colour per scalar field, in `scalar_fields` order
+const SERIES_COLORS: [(u8, u8, u8); 8] = [
+    (31, 119, 180),
+    (255, 127, 14),
+    (44, 160, 44),
+    (214, 39, 40),
+    (148, 103, 189),
+    (140, 86, 75),
+    (227, 119, 194),
+    (127, 127, 127),
+];
+
+/// Render a metrics history as a PNG line chart.
+///
+/// Each scalar field is drawn as its own line, scaled to its own range so
+/// fields of very different magnitude stay readable; a constant field is a
+/// flat line through the middle. Fails with `Error::InvalidParameter` when
+/// either dimension is outside `[33, MAX_CHART_DIMENSION]`.
+pub fn render_metrics_png(
+    history: &[GeometricMetrics],
+    width: u32,
+    height: u32,
+) -> Result<Vec<u8>> {
+    for (name, value) in [("width", width), ("height", height)] {
+        if !(MIN_CHART_DIMENSION..=MAX_CHART_DIMENSION).contains(&value) {
+            return Err(Error::InvalidParameter(
+                name.into(),
+                format!(
+                    "must be within [{}, {}], got {}",
+                    MIN_CHART_DIMENSION, MAX_CHART_DIMENSION, value
+                ),
+            ));
+        }
+    }
+
+    let mut pixmap = Pixmap::new(width, height).ok_or_else(|| {
+        Error::Other(anyhow::anyhow!(
+            "Failed to allocate {}x{} chart",
+            width,
+            height
+        ))
+    })?;
+    pixmap.fill(Color::WHITE);
+
+    let plot_width = width as f32 - 2.0 * MARGIN;
+    let plot_height = height as f32 - 2.0 * MARGIN;
+    if let Some(frame) = Rect::from_xywh(MARGIN, MARGIN, plot_width, plot_height) {
+        let mut paint = Paint::default();
+        paint.set_color_rgba8(200, 200, 200, 255);
+        let path = PathBuilder::from_rect(frame);
+        pixmap.stroke_path(
+            &path,
+            &paint,
+            &Stroke::default(),
+            Transform::identity(),
+            None,
+        );
+    }
+
+    let series: Vec<Vec<f64>> = (0..SERIES_COLORS.len())
+        .map(|index| {
+            history
+                .iter()
+                .map(|metrics| metrics.scalar_fields()[index].1)
+                .collect()
+        })
+        .collect();
+    let step = plot_width / history.len().saturating_sub(1).max(1) as f32;
+
+    for (values, (r, g, b)) in series.iter().zip(SERIES_COLORS) {
+        let finite = values.iter().copied().filter(|v| v.is_finite());
+        let min = finite.clone().fold(f64::INFINITY, f64::min);
+        let max = finite.fold(f64::NEG_INFINITY, f64::max);
+        if !min.is_finite() {
+            continue;
+        }
+
+        let y_of = |value: f64| {
+            let fraction = if max > min {
+                (value - min) / (max - min)
+            } else {
+                0.5
+            };
+            MARGIN + plot_height * (1.0 - fraction as f32)
+        };
+        let mut builder = PathBuilder::new();
+        for (index, value) in values.iter().enumerate() {
+            let (x, y) = (MARGIN + step * index as f32, y_of(*value));
+            if index == 0 {
+                builder.move_to(x, y);
+            } else {
+                builder.line_to(x, y);
+            }
+        }
+        if values.len() == 1 {
+            builder.line_to(MARGIN + plot_width, y_of(values[0]));
+        }
+
+        if let Some(path) = builder.finish() {
+            let mut paint = Paint::default();
+            paint.set_color_rgba8(r, g, b, 255);
+            paint.anti_alias = true;
+            let stroke = Stroke {
+                width: 2.0,
+                ..Default::default()
+            };
+            pixmap.stroke_path(&path, &paint, &stroke, Transform::identity(), None);
+        }
+    }
+
+    pixmap
+        .encode_png()
+        .map_err(|err| Error::Other(anyhow::anyhow!("Failed to encode chart: {}", err)))
+}
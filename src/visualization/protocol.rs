@@ -1,9 +1,11 @@
 //! Placeholder visualization protocol module.
 
-use crate::core::types::{GeometricMetrics, SemanticAnchor};
+use crate::core::types::{GeometricMetrics, Quaternion, SemanticAnchor};
 use serde::{Deserialize, Serialize};
+use std::f64::consts::FRAC_PI_2;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct VisualizationPacket {
     pub metrics: GeometricMetrics,
     pub anchors: Vec<SemanticAnchor>,
@@ -14,3 +16,109 @@ impl VisualizationPacket {
         Self { metrics, anchors }
     }
 }
+
+/// Largest pitch magnitude an [`OrbitCamera`] will use. Stopping just short of
+/// the poles keeps the up vector from flipping.
+pub const MAX_ORBIT_PITCH: f64 = FRAC_PI_2 - 1e-3;
+
+/// Camera orbiting a target point, Y-up.
+///
+/// With zero yaw and pitch the camera sits on +Z and looks down -Z. Yaw turns
+/// around +Y; positive pitch raises the camera so it looks down at the target.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OrbitCamera {
+    /// Rotation around the vertical axis, in radians
+    pub yaw: f64,
+    /// Elevation above the target's horizontal plane, in radians
+    pub pitch: f64,
+    /// Distance from the target
+    pub distance: f64,
+}
+
+impl OrbitCamera {
+    pub fn new(yaw: f64, pitch: f64, distance: f64) -> Self {
+        Self {
+            yaw,
+            pitch,
+            distance,
+        }
+    }
+
+    /// Pitch limited to `±MAX_ORBIT_PITCH`.
+    pub fn clamped_pitch(&self) -> f64 {
+        self.pitch.clamp(-MAX_ORBIT_PITCH, MAX_ORBIT_PITCH)
+    }
+
+    /// Camera orientation: yaw about +Y applied after pitch about +X.
+    pub fn view_quaternion(&self) -> Quaternion {
+        let yaw = Quaternion::from_axis_angle([0.0, 1.0, 0.0], self.yaw);
+        let pitch = Quaternion::from_axis_angle([1.0, 0.0, 0.0], -self.clamped_pitch());
+        yaw.multiply(&pitch)
+    }
+
+    /// Unit vector the camera looks along.
+    pub fn forward(&self) -> [f64; 3] {
+        self.view_quaternion().rotate_vector([0.0, 0.0, -1.0])
+    }
+
+    /// Camera position for the given target.
+    pub fn eye_position(&self, target: [f64; 3]) -> [f64; 3] {
+        let [fx, fy, fz] = self.forward();
+        [
+            target[0] - fx * self.distance,
+            target[1] - fy * self.distance,
+            target[2] - fz * self.distance,
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    #[test]
+    fn test_orbit_camera_default_looks_down_negative_z() {
+        let camera = OrbitCamera::new(0.0, 0.0, 5.0);
+
+        let forward = camera.forward();
+        assert_relative_eq!(forward[0], 0.0, epsilon = 1e-10);
+        assert_relative_eq!(forward[1], 0.0, epsilon = 1e-10);
+        assert_relative_eq!(forward[2], -1.0, epsilon = 1e-10);
+
+        let eye = camera.eye_position([1.0, 2.0, 3.0]);
+        assert_relative_eq!(eye[0], 1.0, epsilon = 1e-10);
+        assert_relative_eq!(eye[1], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(eye[2], 8.0, epsilon = 1e-10);
+    }
+
+    #[test]
+    fn test_orbit_camera_yaw_and_pitch() {
+        // A quarter turn of yaw moves the camera onto +X
+        let eye = OrbitCamera::new(FRAC_PI_2, 0.0, 2.0).eye_position([0.0; 3]);
+        assert_relative_eq!(eye[0], 2.0, epsilon = 1e-10);
+        assert_relative_eq!(eye[2], 0.0, epsilon = 1e-10);
+
+        // Positive pitch raises the camera and tilts it down towards the target
+        let camera = OrbitCamera::new(0.0, 0.5, 2.0);
+        let eye = camera.eye_position([0.0; 3]);
+        assert_relative_eq!(eye[1], 2.0 * 0.5f64.sin(), epsilon = 1e-10);
+        assert!(camera.forward()[1] < 0.0);
+    }
+
+    #[test]
+    fn test_orbit_camera_pitch_clamps() {
+        let over = OrbitCamera::new(0.3, 10.0, 1.0);
+        let limit = OrbitCamera::new(0.3, MAX_ORBIT_PITCH, 1.0);
+        assert_eq!(over.clamped_pitch(), MAX_ORBIT_PITCH);
+        assert_eq!(over.view_quaternion(), limit.view_quaternion());
+
+        // The camera's up vector never points below the horizon
+        for pitch in [-10.0, -FRAC_PI_2, FRAC_PI_2, 10.0] {
+            let up = OrbitCamera::new(0.3, pitch, 1.0)
+                .view_quaternion()
+                .rotate_vector([0.0, 1.0, 0.0]);
+            assert!(up[1] > 0.0);
+        }
+    }
+}